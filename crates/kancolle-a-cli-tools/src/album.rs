@@ -0,0 +1,177 @@
+//! Rendering for kca-album's static HTML album.
+
+use itertools::Itertools;
+use kancolle_a::cards::Card;
+use kancolle_a::importer::kancolle_arcade_net::BookShipCardPageSourceDiscriminants;
+use kancolle_a::ships::{Ship, ShipMod};
+use std::fmt::{Result, Write};
+
+pub const STYLE: &str = r#"body { font-family: sans-serif; margin: 1em 2em; }
+table.ships td, table.ships th { padding: 0.2em 0.6em; text-align: left; }
+.shipmod { margin-bottom: 2em; }
+.stats span { margin-right: 1.5em; }
+.page { display: inline-block; vertical-align: top; margin: 0 1em 1em 0; }
+.page h3 { font-size: 1em; margin: 0.3em 0; }
+.cards { display: flex; gap: 0.3em; }
+.card { margin: 0; text-align: center; font-size: 0.8em; }
+.card img, .card .missing { width: 120px; height: 168px; display: block; }
+.card .missing { border: 2px dashed #aaa; box-sizing: border-box; background: #f4f4f4; }
+.card.unowned figcaption { color: #888; }
+"#;
+
+/// Escape text for use in HTML content and attribute values
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn page_header(title: &str, style_path: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"ja\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<link rel=\"stylesheet\" href=\"{style_path}\">\n</head>\n<body>\n",
+        escape(title)
+    )
+}
+
+const PAGE_FOOTER: &str = "</body>\n</html>\n";
+
+/// The lowest book number across the ShipMods
+pub fn book_no(ship: &Ship) -> Option<u16> {
+    ship.mods()
+        .iter()
+        .filter_map(|shipmod| shipmod.book().as_ref().map(|book| book.book_no))
+        .min()
+}
+
+/// The file name of a ship's page, from the base ship name so it stays the same as
+/// ships are added or acquired. Characters other than letters, digits and `-` become `_`.
+pub fn ship_file_name(ship: &Ship) -> String {
+    let name: String = ship
+        .name()
+        .chars()
+        .map(|c| match c.is_alphanumeric() || c == '-' {
+            true => c,
+            false => '_',
+        })
+        .collect();
+    format!("{name}.html")
+}
+
+fn render_card(html: &mut String, card: &Card, image_base: &str) -> Result {
+    let variation = card.variation();
+    match card
+        .image_ref()
+        .and_then(|image| image.url_with_base(image_base))
+    {
+        Some(url) => writeln!(
+            html,
+            "<figure class=\"card owned\"><img src=\"{}\" alt=\"{variation}\" loading=\"lazy\"><figcaption>{variation}</figcaption></figure>",
+            escape(&url)
+        ),
+        None => writeln!(
+            html,
+            "<figure class=\"card unowned\"><div class=\"missing\"></div><figcaption>{variation}</figcaption></figure>"
+        ),
+    }
+}
+
+fn render_shipmod(html: &mut String, shipmod: &ShipMod, image_base: &str) -> Result {
+    writeln!(html, "<section class=\"shipmod\">")?;
+    writeln!(html, "<h2>{}</h2>", escape(shipmod.name()))?;
+
+    let character = shipmod.character().as_ref();
+    let book = shipmod.book().as_ref();
+    let level = character
+        .map(|character| character.lv)
+        .or(book.map(|book| book.lv));
+    let married = character.is_some_and(|character| character.married)
+        || book.is_some_and(|book| book.is_married.iter().flatten().any(|married| *married));
+    write!(html, "<p class=\"stats\">")?;
+    if let Some(level) = level {
+        write!(html, "<span>Lv {level}</span>")?;
+    }
+    if let Some(character) = character {
+        write!(
+            html,
+            "<span>{}</span>",
+            "★".repeat(character.star_num as usize)
+        )?;
+    }
+    if married {
+        write!(html, "<span>ケッコンカッコカリ</span>")?;
+    }
+    writeln!(html, "</p>")?;
+
+    for (_, cards) in &shipmod.cards().chunk_by(|card| card.priority()) {
+        let cards: Vec<Card> = cards.collect();
+        let source = BookShipCardPageSourceDiscriminants::from(cards[0].source());
+        writeln!(
+            html,
+            "<div class=\"page\"><h3>{source}</h3><div class=\"cards\">"
+        )?;
+        for card in cards.iter() {
+            render_card(html, card, image_base)?;
+        }
+        writeln!(html, "</div></div>")?;
+    }
+
+    writeln!(html, "</section>")
+}
+
+/// A ship's page, to be written to `ships/` next to the index.
+/// Unowned cards are shown as placeholders.
+pub fn render_ship(ship: &Ship, image_base: &str) -> std::result::Result<String, std::fmt::Error> {
+    let mut html = page_header(ship.name(), "../style.css");
+    writeln!(html, "<p><a href=\"../index.html\">Album</a></p>")?;
+    writeln!(html, "<h1>{}</h1>", escape(ship.name()))?;
+    if let Some(blueprint) = ship.blueprint() {
+        writeln!(
+            html,
+            "<p class=\"stats\"><span>Blueprints: {}</span></p>",
+            blueprint.blueprint_total_num
+        )?;
+    }
+    for shipmod in ship.mods() {
+        render_shipmod(&mut html, shipmod, image_base)?;
+    }
+    html += PAGE_FOOTER;
+    Ok(html)
+}
+
+/// The album's index page, linking to each ship's page per `ship_file_name`.
+pub fn render_index(ships: &[&Ship]) -> std::result::Result<String, std::fmt::Error> {
+    let mut index = page_header("Album", "style.css");
+    writeln!(index, "<h1>Album</h1>")?;
+    writeln!(
+        index,
+        "<table class=\"ships\">\n<tr><th>#</th><th>Ship</th><th>Cards</th><th>Blueprints</th></tr>"
+    )?;
+    for ship in ships {
+        let cards: Vec<Card> = ship
+            .mods()
+            .iter()
+            .flat_map(|shipmod| shipmod.cards())
+            .collect();
+        writeln!(
+            index,
+            "<tr><td>{}</td><td><a href=\"ships/{}\">{}</a></td><td>{}/{}</td><td>{}</td></tr>",
+            book_no(ship)
+                .map(|book_no| book_no.to_string())
+                .unwrap_or_default(),
+            escape(&ship_file_name(ship)),
+            escape(ship.name()),
+            cards.iter().filter(|card| card.owned()).count(),
+            cards.len(),
+            ship.blueprint()
+                .as_ref()
+                .map_or(0, |blueprint| blueprint.blueprint_total_num),
+        )?;
+    }
+    writeln!(index, "</table>")?;
+    index += PAGE_FOOTER;
+    Ok(index)
+}
+
+#[cfg(test)]
+mod tests;
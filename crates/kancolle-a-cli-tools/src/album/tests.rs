@@ -0,0 +1,64 @@
+use super::*;
+use kancolle_a::ships::{Ships, ShipsBuilder};
+
+const TCBOOK: &[u8] = include_bytes!("../../../kancolle-a/tests/fixtures/latest/TcBook_info.json");
+const CHARLIST: &[u8] =
+    include_bytes!("../../../kancolle-a/tests/fixtures/latest/CharacterList_info.json");
+
+async fn ships() -> Ships {
+    ShipsBuilder::default()
+        .book_from_reader(TCBOOK)
+        .character_from_reader(CHARLIST)
+        .build()
+        .await
+        .unwrap()
+}
+
+#[test]
+fn test_escape() {
+    assert_eq!(
+        escape(r#"<a href="?a=1&b=2">"#),
+        "&lt;a href=&quot;?a=1&amp;b=2&quot;&gt;"
+    );
+    assert_eq!(escape("長門改二"), "長門改二");
+}
+
+#[tokio::test]
+async fn test_render_ship() {
+    let ships = ships().await;
+    let 長門 = ships.get("長門").unwrap();
+    let cards: Vec<Card> = 長門
+        .mods()
+        .iter()
+        .flat_map(|shipmod| shipmod.cards())
+        .collect();
+    let owned = cards.iter().filter(|card| card.owned()).count();
+    assert!(owned > 0);
+    assert!(owned < cards.len());
+
+    let html = render_ship(長門, "https://example.com/?base=1&path=").unwrap();
+    assert!(html.contains("<h1>長門</h1>"));
+    assert!(html.contains("<h2>長門改</h2>"));
+    assert_eq!(html.matches("<figure class=\"card owned\">").count(), owned);
+    assert_eq!(
+        html.matches("<figure class=\"card unowned\"><div class=\"missing\"></div>")
+            .count(),
+        cards.len() - owned
+    );
+    assert!(html.contains("src=\"https://example.com/?base=1&amp;path="));
+    assert!(!html.contains("base=1&path="));
+}
+
+#[tokio::test]
+async fn test_ship_file_name() {
+    let ships = ships().await;
+    assert_eq!(ship_file_name(ships.get("長門").unwrap()), "長門.html");
+    assert_eq!(
+        ship_file_name(ships.get("Prinz Eugen").unwrap()),
+        "Prinz_Eugen.html"
+    );
+
+    let ship_list: Vec<&Ship> = ships.values().sorted().collect();
+    let index = render_index(&ship_list).unwrap();
+    assert!(index.contains("<a href=\"ships/Prinz_Eugen.html\">Prinz Eugen</a>"));
+}
@@ -0,0 +1,103 @@
+use anyhow::{bail, Result};
+use itertools::Itertools;
+use kancolle_a::ships::{Ship, ShipsBuilder};
+use kancolle_a_cli_tools::{album, cli_helpers};
+use std::collections::HashSet;
+use std::fs;
+
+pub(crate) mod args {
+    use bpaf::*;
+    use kancolle_a::importer::kancolle_arcade_net::RESOURCES_BASE;
+    use kancolle_a_cli_tools::cli_helpers::{self, ShipSourceDataOptions};
+    use std::path::PathBuf;
+
+    #[derive(Debug, Clone)]
+    pub(crate) struct Options {
+        pub(crate) data: ShipSourceDataOptions,
+        pub(crate) output: PathBuf,
+        pub(crate) image_base: String,
+    }
+
+    pub fn options() -> OptionParser<Options> {
+        let data = cli_helpers::ship_source_data_parser();
+        let output = long("output")
+            .help("The directory to write the album to")
+            .argument::<PathBuf>("OUTPUT")
+            .fallback(PathBuf::from("album"))
+            .debug_fallback();
+        let image_base = long("image-base")
+            .help("Where the album should load images from, e.g. a kca-image-download directory relative to the ship pages")
+            .argument::<String>("IMAGE_BASE")
+            .fallback(RESOURCES_BASE.to_string())
+            .display_fallback();
+        construct!(Options {
+            data,
+            output,
+            image_base
+        })
+        .to_options()
+        .descr("A tool to render your collection as a static HTML album.")
+        .header("Use --image-base ../images/ with kca-image-download --output OUTPUT/images for an album which works offline.")
+    }
+
+    #[test]
+    fn kca_album_check_options() {
+        options().check_invariants(false)
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    let args = args::options().run();
+
+    let ships = cli_helpers::ship_source_data_applier(&args.data, ShipsBuilder::default())?
+        .build()
+        .await?;
+    cli_helpers::report_warnings(&ships);
+
+    let ship_list: Vec<&Ship> = ships.values().sorted().collect();
+
+    let ships_dir = args.output.join("ships");
+    fs::create_dir_all(&ships_dir)?;
+    fs::write(args.output.join("style.css"), album::STYLE)?;
+
+    let mut file_names = HashSet::new();
+    for ship in ship_list.iter() {
+        let file_name = album::ship_file_name(ship);
+        if !file_names.insert(file_name.clone()) {
+            bail!("Two ships would be written to {file_name}");
+        }
+        fs::write(
+            ships_dir.join(&file_name),
+            album::render_ship(ship, &args.image_base)?,
+        )?;
+    }
+
+    // Remove pages for ships we no longer know of, or from older, differently named, runs.
+    for entry in fs::read_dir(&ships_dir)? {
+        let path = entry?.path();
+        let stale = path
+            .extension()
+            .is_some_and(|extension| extension == "html")
+            && path
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .is_none_or(|file_name| !file_names.contains(file_name));
+        if stale {
+            fs::remove_file(path)?;
+        }
+    }
+
+    fs::write(
+        args.output.join("index.html"),
+        album::render_index(&ship_list)?,
+    )?;
+
+    println!(
+        "Wrote {} ships to {}",
+        ship_list.len(),
+        args.output.join("index.html").display()
+    );
+
+    Ok(())
+}
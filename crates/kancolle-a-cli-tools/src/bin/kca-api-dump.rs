@@ -1,7 +1,9 @@
 use anyhow::{bail, Result};
 use jsonxf::Formatter;
-use kancolle_a::importer::kancolle_arcade_net::{ApiEndpoint, Client, ClientBuilder};
+use kancolle_a::importer::kancolle_arcade_net::{ApiEndpoint, ClientBuilder};
+use kancolle_a_cli_tools::cli_helpers;
 use std::fs;
+use std::io::Read;
 
 pub(crate) mod args {
     use bpaf::*;
@@ -36,14 +38,14 @@ pub(crate) mod args {
     }
 }
 
-async fn fetch_to_fixture(
-    client: &Client,
+fn write_fixture(
     formatter: &mut Formatter,
     endpoint: &ApiEndpoint,
+    mut response: Box<dyn Read>,
 ) -> Result<()> {
     let mut data = String::new();
-    let filename = fixture_filename(endpoint);
-    client.fetch(endpoint).await?.read_to_string(&mut data)?;
+    let filename = cli_helpers::fixture_filename(endpoint)?;
+    response.read_to_string(&mut data)?;
     let data = match formatter.format(&data) {
         Ok(data) => data,
         // Erk, it returns a Result<_, String>. We could open-code it, it
@@ -58,48 +60,6 @@ async fn fetch_to_fixture(
     Ok(())
 }
 
-fn fixture_filename(endpoint: &ApiEndpoint) -> String {
-    use ApiEndpoint::*;
-    match endpoint {
-        KanmusuList => "kanmusu_list.json".to_string(),
-
-        AimeCampaignHold => "AimeCampaign_hold.json".to_string(),
-        AimeCampaignInfo => "AimeCampaign_info.json".to_string(),
-        AreaCaptureInfo => "Area_captureInfo.json".to_string(),
-        BlueprintListInfo => "BlueprintList_info.json".to_string(),
-        CampaignHistory => "Capmpaign_history.json".to_string(),
-        CampaignInfo => "Campaign_info.json".to_string(),
-        CampaignPresent => "Campaign_present.json".to_string(),
-        CharacterListInfo => "CharacterList_info.json".to_string(),
-        CopCheckreward => "Cop_checkreward.json".to_string(),
-        CopHold => "Cop_hold.json".to_string(),
-        CopInfo => "Cop_info.json".to_string(),
-        EpFesHold => "EpFes_hold.json".to_string(),
-        EpFesProgress => "EpFes_progress.json".to_string(),
-        EquipBookInfo => "EquipBook_info.json".to_string(),
-        EquipListInfo => "EquipList_info.json".to_string(),
-        EventHold => "Event_hold.json".to_string(),
-        EventInfo => "Event_info.json".to_string(),
-        ExerciseInfo => "Exercise_info.json".to_string(),
-        NCampInfo => "NCamp_info.json".to_string(),
-        PersonalBasicInfo => "Personal_basicInfo.json".to_string(),
-        PlaceDistricts => "Place_districts.json".to_string(),
-        PlacePlaces => "Place_places.json".to_string(),
-        QuestInfo => "Quest_info.json".to_string(),
-        RankingMonthlyCurrent => "Ranking_monthly_current.json".to_string(),
-        RankingMonthlyPrev => "Ranking_monthly_prev.json".to_string(),
-        RankingTotal => "Ranking_total.json".to_string(),
-        RoomItemListInfo => "RoomItemList_info.json".to_string(),
-        TcBookInfo => "TcBook_info.json".to_string(),
-        TcErrorDispFlag => "TcError_dispFlag.json".to_string(),
-
-        AuthLogin => panic!("Unsupported data fixture"),
-
-        // TODO: Turn slash into underscore, and drop prefix
-        Other(path) => format!("other_{path}.json"),
-    }
-}
-
 #[tokio::main(flavor = "current_thread")]
 async fn main() -> Result<()> {
     let args = args::options().run();
@@ -118,44 +78,28 @@ async fn main() -> Result<()> {
     formatter.indent = "    ".to_string();
     formatter.trailing_output = "\n".to_string();
 
-    // Auth not required for these
-
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::KanmusuList).await?;
+    let endpoints = cli_helpers::fixture_endpoints();
 
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::EventHold).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::EventInfo).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::PlaceDistricts).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::PlacePlaces).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::RankingMonthlyCurrent).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::RankingMonthlyPrev).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::RankingTotal).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::TcErrorDispFlag).await?;
+    let snapshot = client.fetch_snapshot(&endpoints).await;
 
-    // Auth is required for the below
+    // Write out everything we can, then report everything we couldn't.
+    let mut failures = 0;
+    for (endpoint, response) in endpoints.iter().zip(snapshot) {
+        if let Err(error) = response
+            .map_err(anyhow::Error::from)
+            .and_then(|response| write_fixture(&mut formatter, endpoint, response))
+        {
+            eprintln!("{endpoint:?}: {error}");
+            failures += 1;
+        }
+    }
 
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::AimeCampaignHold).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::AimeCampaignInfo).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::AreaCaptureInfo).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::BlueprintListInfo).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::CampaignHistory).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::CampaignInfo).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::CampaignPresent).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::CharacterListInfo).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::CopCheckreward).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::CopHold).await?;
-    // Skipping CopInfo, it includes store locations and as such is TMI to commit.
-    // It is an empty file when "Cop" (location) events are not running.
-    //fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::CopInfo).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::EpFesHold).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::EpFesProgress).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::EquipBookInfo).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::EquipListInfo).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::ExerciseInfo).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::NCampInfo).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::PersonalBasicInfo).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::QuestInfo).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::RoomItemListInfo).await?;
-    fetch_to_fixture(&client, &mut formatter, &ApiEndpoint::TcBookInfo).await?;
+    if failures > 0 {
+        bail!(
+            "Failed to fetch {failures} of {} endpoints",
+            endpoints.len()
+        );
+    }
 
     Ok(())
 }
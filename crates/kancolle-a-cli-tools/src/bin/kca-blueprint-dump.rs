@@ -33,6 +33,7 @@ async fn main() -> Result<()> {
     let ships = cli_helpers::ship_source_data_applier(&args.data, ShipsBuilder::default())?
         .build()
         .await?;
+    cli_helpers::report_warnings(&ships);
 
     let mut bp_per_month = BTreeMap::new();
 
@@ -0,0 +1,132 @@
+use anyhow::Result;
+use chrono::{Datelike, Months, NaiveDate, NaiveTime};
+use itertools::Itertools;
+use kancolle_a::blueprint_forecast::{BlueprintForecast, ExpiryBasis};
+use kancolle_a::ships::ShipsBuilder;
+use kancolle_a::time::{japan, today_in_japan};
+use kancolle_a_cli_tools::cli_helpers;
+
+pub(crate) mod args {
+    use bpaf::*;
+    use kancolle_a_cli_tools::cli_helpers::{self, ShipSourceDataOptions};
+
+    #[derive(Debug, Clone)]
+    pub(crate) struct Options {
+        pub(crate) data: ShipSourceDataOptions,
+        pub(crate) months: u32,
+        pub(crate) month_end: bool,
+    }
+
+    pub fn options() -> OptionParser<Options> {
+        let data = cli_helpers::ship_source_data_parser();
+        let months = long("months")
+            .help("How many months ahead to forecast, including this one")
+            .argument::<u32>("MONTHS")
+            .guard(|months| *months > 0, "must be at least 1")
+            .fallback(1)
+            .display_fallback();
+        let month_end = long("month-end")
+            .help("Assume blueprints expire at the end of the month, rather than on the reported 11th")
+            .switch();
+        construct!(Options {
+            data,
+            months,
+            month_end
+        })
+        .to_options()
+        .descr(
+            "A tool to forecast which blueprints will expire unused, and how to spend them first.",
+        )
+        .header("Assumes the oldest blueprints are spent first, and no new blueprints arrive.")
+    }
+
+    #[test]
+    fn kca_blueprint_forecast_check_options() {
+        options().check_invariants(false)
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    let args = args::options().run();
+
+    let ships = cli_helpers::ship_source_data_applier(&args.data, ShipsBuilder::default())?
+        .build()
+        .await?;
+    cli_helpers::report_warnings(&ships);
+
+    // Blueprint expiry is in Japan time.
+    let today = today_in_japan();
+    // The last moment of the last month forecast
+    let until =
+        NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap() + Months::new(args.months);
+    let until = until
+        .pred_opt()
+        .unwrap()
+        .and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap())
+        .and_local_timezone(japan())
+        .unwrap();
+
+    let basis = match args.month_end {
+        true => ExpiryBasis::MonthEnd,
+        false => ExpiryBasis::Reported,
+    };
+    let forecast = BlueprintForecast::new(&ships, until, basis);
+
+    if forecast.forecasts().is_empty() {
+        println!("No blueprints expiring unused by {}", until.date_naive());
+        return Ok(());
+    }
+    println!(
+        "{} blueprints expiring unused by {}",
+        forecast.expiring_count(),
+        until.date_naive()
+    );
+    println!();
+
+    for ship_forecast in forecast.forecasts() {
+        let expiring = ship_forecast
+            .expiring()
+            .iter()
+            .map(|batch| format!("{} on {}", batch.count, batch.expires_at.date_naive()))
+            .join(", ");
+        let target = match ship_forecast.target() {
+            Some(target) if ship_forecast.target_affordable() => {
+                format!(
+                    "building {} ({})",
+                    target.shipmod().name(),
+                    target.blueprints()
+                )
+            }
+            Some(target) => format!(
+                "saving for {} ({}/{})",
+                target.shipmod().name(),
+                ship_forecast.held(),
+                target.blueprints()
+            ),
+            None => format!("no target ({})", ship_forecast.held()),
+        };
+        println!("{}\t{target}\t{expiring}", ship_forecast.ship().name());
+
+        if ship_forecast.spend_options().is_empty() {
+            println!("  No remodel affordable, these will be lost");
+        }
+        for remodel in ship_forecast.spend_options() {
+            let missing_mark = match remodel.card_missing() {
+                true => "※ ",
+                false => "",
+            };
+            let uses = match ship_forecast.uses_all_expiring(remodel) {
+                true => "uses all",
+                false => "uses some",
+            };
+            println!(
+                "  {missing_mark}=> {}\t{} blueprints, {uses}",
+                remodel.shipmod().name(),
+                remodel.blueprints()
+            );
+        }
+    }
+
+    Ok(())
+}
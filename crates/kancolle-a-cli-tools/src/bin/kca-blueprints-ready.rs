@@ -31,6 +31,7 @@ async fn main() -> Result<()> {
     let ships = cli_helpers::ship_source_data_applier(&args.data, ShipsBuilder::default())?
         .build()
         .await?;
+    cli_helpers::report_warnings(&ships);
 
     /// Status of the ship chain based on current blueprint inventory
     #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
@@ -40,27 +41,24 @@ async fn main() -> Result<()> {
         Complete,
     }
 
-    // Ship name, current count, needed for next level, status
-    let mut ship_status: Vec<(&str, u16, u16, Status)> = vec![];
+    // ShipMod, current count, needed for next level, status
+    let mut ship_status: Vec<(&ShipMod, u16, u16, Status)> = vec![];
 
     let has_normal_card = |shipmod: &ShipMod| match shipmod.book() {
         None => false,
         Some(book_ship) => !book_ship.card_list[0].card_img_list[0].is_empty(),
     };
 
-    // Reference: https://wikiwiki.jp/kancolle-a/%E5%BB%BA%E9%80%A0#kaizou
-    // Does not take into account ships that have just been released in events and hence are not
-    // yet upgradable. (At time of writing, 最上改二/最上改二特 and 武蔵改二 are examples of this.)
-    // I doubt there's anywhere machine-readable for that data.
+    // Ships that have just been released in events and hence are not yet upgradable have no
+    // blueprint cost, per the "notRemodelable" entries in the blueprint cost table.
 
     // NOTE: This loop will prefer to save for a level we don't have a card for, versus
     // backfilling a level we can afford now. That's usually what you want, but sometimes
-    // you want to know what you can do _now_, e.g., when blueprints are expiring.
-    // TODO: Represent both cases somehow. I could include any such match in the output, rather than
-    // only taking one-per-Ship.
+    // you want to know what you can do _now_, e.g., when blueprints are expiring. For that,
+    // see kca-blueprint-forecast, which uses the same savings target.
     for (ship, blueprint) in ships
-        .iter()
-        .filter_map(|(_, ship)| ship.blueprint().as_ref().map(|blueprint| (ship, blueprint)))
+        .values()
+        .filter_map(|ship| ship.blueprint().as_ref().map(|blueprint| (ship, blueprint)))
     {
         match ship
             .mods()
@@ -70,17 +68,12 @@ async fn main() -> Result<()> {
         {
             Some(shipmod) => match ship.shipmod_blueprint_cost(shipmod.remodel_level()) {
                 None => {
-                    ship_status.push((
-                        shipmod.name(),
-                        blueprint.blueprint_total_num,
-                        0,
-                        Status::Complete,
-                    ));
+                    ship_status.push((shipmod, blueprint.blueprint_total_num, 0, Status::Complete));
                     continue;
                 }
                 Some((bp_cost, _)) if bp_cost > blueprint.blueprint_total_num => {
                     ship_status.push((
-                        shipmod.name(),
+                        shipmod,
                         blueprint.blueprint_total_num,
                         bp_cost,
                         Status::SavingFor,
@@ -89,7 +82,7 @@ async fn main() -> Result<()> {
                 }
                 Some((bp_cost, _)) => {
                     ship_status.push((
-                        shipmod.name(),
+                        shipmod,
                         blueprint.blueprint_total_num,
                         bp_cost,
                         Status::ReadyFor,
@@ -106,7 +99,7 @@ async fn main() -> Result<()> {
                     .shipmod_blueprint_cost(last_known_shipmod.remodel_level() + 1)
                     .is_none());
                 ship_status.push((
-                    last_known_shipmod.name(),
+                    last_known_shipmod,
                     blueprint.blueprint_total_num,
                     0,
                     Status::Complete,
@@ -116,14 +109,11 @@ async fn main() -> Result<()> {
         }
     }
 
-    // TODO: Ships and ship_mods can supply a sort key if they have a character.
-    // For now, sort by name and then by status.
-    ship_status.sort_by_key(|(ship_name, _, _, _)| *ship_name);
-
-    ship_status.sort_by_key(|(_, _, _, status)| *status);
+    // Sort by status, then in the game's order.
+    ship_status.sort_by_key(|(shipmod, _, _, status)| (*status, *shipmod));
 
-    for (ship_name, bp_count, bp_needed, status) in ship_status {
-        println!("{status:?}:\t{ship_name}\t{bp_count}/{bp_needed}");
+    for (shipmod, bp_count, bp_needed, status) in ship_status {
+        println!("{status:?}:\t{}\t{bp_count}/{bp_needed}", shipmod.name());
     }
 
     Ok(())
@@ -0,0 +1,101 @@
+use anyhow::Result;
+use chrono::Utc;
+use kancolle_a::blueprint_forecast::ExpiryBasis;
+use kancolle_a::calendar::Calendar;
+use kancolle_a::importer::kancolle_arcade_net::{
+    read_aime_campaign_info, read_event_hold, read_quest_info,
+};
+use kancolle_a::ships::ShipsBuilder;
+use kancolle_a_cli_tools::cli_helpers;
+use std::fs::{self, File};
+use std::io::BufReader;
+
+pub(crate) mod args {
+    use bpaf::*;
+    use kancolle_a_cli_tools::cli_helpers::{self, ShipSourceDataOptions};
+    use std::path::PathBuf;
+
+    #[derive(Debug, Clone)]
+    pub(crate) struct Options {
+        pub(crate) data: ShipSourceDataOptions,
+        pub(crate) quest_info: Option<PathBuf>,
+        pub(crate) event_hold: Option<PathBuf>,
+        pub(crate) aime_campaign_info: Option<PathBuf>,
+        pub(crate) month_end: bool,
+        pub(crate) output: Option<PathBuf>,
+    }
+
+    pub fn options() -> OptionParser<Options> {
+        let data = cli_helpers::ship_source_data_parser();
+        let quest_info = long("quest-info")
+            .help(
+                "A copy of your https://kancolle-arcade.net/ac/api/Quest/info, for quest end times",
+            )
+            .argument::<PathBuf>("QUEST_INFO")
+            .optional();
+        let event_hold = long("event-hold")
+            .help("A copy of https://kancolle-arcade.net/ac/api/Event/hold, for the event start")
+            .argument::<PathBuf>("EVENT_HOLD")
+            .optional();
+        let aime_campaign_info = long("aime-campaign-info")
+            .help("A copy of your https://kancolle-arcade.net/ac/api/AimeCampaign/info, for campaign periods")
+            .argument::<PathBuf>("AIME_CAMPAIGN_INFO")
+            .optional();
+        let month_end = long("month-end")
+            .help("Show blueprints expiring at the end of the month, rather than on the reported 11th")
+            .switch();
+        let output = long("output")
+            .help("The .ics file to write, rather than standard output")
+            .argument::<PathBuf>("OUTPUT")
+            .optional();
+        construct!(Options {
+            data,
+            quest_info,
+            event_hold,
+            aime_campaign_info,
+            month_end,
+            output
+        })
+        .to_options()
+        .descr("A tool to export blueprint expiries, quest deadlines, and event and campaign periods as an iCalendar file.")
+    }
+
+    #[test]
+    fn kca_calendar_check_options() {
+        options().check_invariants(false)
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    let args = args::options().run();
+
+    let ships = cli_helpers::ship_source_data_applier(&args.data, ShipsBuilder::default())?
+        .build()
+        .await?;
+    cli_helpers::report_warnings(&ships);
+
+    let basis = match args.month_end {
+        true => ExpiryBasis::MonthEnd,
+        false => ExpiryBasis::Reported,
+    };
+    let mut calendar = Calendar::new(Utc::now()).blueprint_expiries(&ships, basis);
+    if let Some(path) = &args.quest_info {
+        calendar = calendar.quests(&read_quest_info(BufReader::new(File::open(path)?))?);
+    }
+    if let Some(path) = &args.event_hold {
+        calendar = calendar.event_hold(&read_event_hold(BufReader::new(File::open(path)?))?);
+    }
+    if let Some(path) = &args.aime_campaign_info {
+        calendar =
+            calendar.aime_campaigns(&read_aime_campaign_info(BufReader::new(File::open(path)?))?);
+    }
+
+    let ics = calendar.to_ics();
+    match &args.output {
+        Some(path) => fs::write(path, ics)?,
+        None => print!("{ics}"),
+    }
+
+    Ok(())
+}
@@ -14,7 +14,7 @@ pub(crate) mod args {
     pub fn options() -> OptionParser<Options> {
         let data = cli_helpers::ship_source_data_parser();
         construct!(Options { data })
-        .to_options().descr("A tool to report on cardpage data gaps.").header("Please share any reported knowable gaps with the tool author to update the source.")
+        .to_options().descr("A tool to report on cardpage data gaps.").header("Knowable gaps can be filled in with a --card-page-sources file, in the format of the bundled card_page_sources.json.")
     }
 
     #[test]
@@ -30,17 +30,18 @@ async fn main() -> Result<()> {
     let ships = cli_helpers::ship_source_data_applier(&args.data, ShipsBuilder::default())?
         .build()
         .await?;
+    cli_helpers::report_warnings(&ships);
 
     let mut unknown_pages: Vec<(u16, &str, Vec<u16>, Vec<u16>)> = vec![];
 
-    for (ship_name, book_ship) in ships
+    for (shipmod, book_ship) in ships
         .shipmod_iter()
-        .filter_map(|shipmod| shipmod.book().as_ref().map(|ship| (shipmod.name(), ship)))
+        .filter_map(|shipmod| shipmod.book().as_ref().map(|ship| (shipmod, ship)))
     {
         let mut knowable: Vec<u16> = vec![];
         let mut unknown: Vec<u16> = vec![];
-        for page in &book_ship.card_list[1..] {
-            match book_ship.source(page.priority) {
+        for (source, page) in shipmod.card_pages().skip(1) {
+            match source {
                 BookShipCardPageSource::Normal => panic!("Normal page after page 1"),
                 BookShipCardPageSource::Unknown => {
                     if page.acquire_num_in_page > 0 {
@@ -54,7 +55,7 @@ async fn main() -> Result<()> {
             }
         }
         if !knowable.is_empty() || !unknown.is_empty() {
-            unknown_pages.push((book_ship.book_no, ship_name, knowable, unknown));
+            unknown_pages.push((book_ship.book_no, shipmod.name(), knowable, unknown));
         }
     }
 
@@ -0,0 +1,133 @@
+use anyhow::Result;
+use kancolle_a::importer::kancolle_arcade_net::{
+    card_page_inference::{default_event_runs, infer_card_page_sources, BookSnapshot},
+    CardPageSources,
+};
+use kancolle_a::time::today_in_japan;
+use std::fs::File;
+use std::io::BufReader;
+
+pub(crate) mod args {
+    use anyhow::{anyhow, Result};
+    use bpaf::*;
+    use chrono::NaiveDate;
+    use kancolle_a::importer::kancolle_arcade_net::card_page_inference::Confidence;
+    use std::path::PathBuf;
+
+    /// A TcBook dump, given as [PLAYER:]DATE=PATH
+    #[derive(Debug, Clone)]
+    pub(crate) struct BookArg {
+        pub(crate) player: String,
+        pub(crate) date: NaiveDate,
+        pub(crate) path: PathBuf,
+    }
+
+    impl std::str::FromStr for BookArg {
+        type Err = anyhow::Error;
+
+        fn from_str(s: &str) -> Result<Self> {
+            let (label, path) = s
+                .split_once('=')
+                .ok_or_else(|| anyhow!("expected [PLAYER:]DATE=PATH"))?;
+            let (player, date) = label.rsplit_once(':').unwrap_or(("", label));
+            Ok(BookArg {
+                player: player.to_string(),
+                date: date.parse()?,
+                path: PathBuf::from(path),
+            })
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub(crate) struct Options {
+        pub(crate) card_page_sources: Option<PathBuf>,
+        pub(crate) min_confidence: Confidence,
+        pub(crate) books: Vec<BookArg>,
+    }
+
+    pub fn options() -> OptionParser<Options> {
+        let card_page_sources = long("card-page-sources")
+            .help("Additional TcBook card page sources, in the format of the builtin card_page_sources.json")
+            .argument::<PathBuf>("SOURCES")
+            .optional();
+        let min_confidence = long("min-confidence")
+            .help("Only report proposals with at least this confidence: Low, Medium or High")
+            .argument::<String>("CONFIDENCE")
+            .parse(|confidence| confidence.parse())
+            .fallback(Confidence::Low)
+            .display_fallback();
+        let books = positional::<BookArg>("BOOK")
+            .help("A copy of a https://kancolle-arcade.net/ac/api/TcBook/info, as [PLAYER:]DATE=PATH, e.g. alice:2024-06-10=TcBook_info.json")
+            .some("At least one TcBook is needed");
+        construct!(Options {
+            card_page_sources,
+            min_confidence,
+            books
+        })
+        .to_options()
+        .descr("A tool to propose sources for unidentified TcBook pages, from TcBooks of one or more players taken on different dates.")
+        .header("More dates, and more players, narrow down when each page appeared and was first acquired.")
+    }
+
+    #[test]
+    fn kca_cardpage_infer_check_options() {
+        options().check_invariants(false)
+    }
+}
+
+fn main() -> Result<()> {
+    let args = args::options().run();
+
+    let mut card_page_sources = CardPageSources::bundled().clone();
+    if let Some(path) = &args.card_page_sources {
+        card_page_sources.overlay(CardPageSources::from_reader(BufReader::new(File::open(
+            path,
+        )?))?);
+    }
+
+    let snapshots = args
+        .books
+        .iter()
+        .map(|book| {
+            Ok(BookSnapshot::from_reader(
+                book.player.clone(),
+                book.date,
+                BufReader::new(File::open(&book.path)?),
+            )?)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let events = default_event_runs(today_in_japan());
+    let proposals: Vec<_> = infer_card_page_sources(&snapshots, &card_page_sources, &events)
+        .into_iter()
+        .filter(|proposal| proposal.confidence >= args.min_confidence)
+        .collect();
+
+    if proposals.is_empty() {
+        println!("No proposals!");
+        return Ok(());
+    }
+
+    println!("#\tPage\tConfidence\tCandidates\tShip\tReason");
+    for proposal in proposals {
+        let candidates = match proposal.candidates.is_empty() {
+            true => "?".to_string(),
+            false => proposal
+                .candidates
+                .iter()
+                .map(|source| source.to_string())
+                .collect::<Vec<_>>()
+                .join("/"),
+        };
+        println!(
+            "{}\t{}\t{}\t{candidates}\t{}\t{}",
+            proposal.book_no,
+            proposal.page,
+            proposal.confidence,
+            proposal.ship_name,
+            proposal.reason
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use anyhow::Result;
+use kancolle_a::development::{DevelopmentCost, DevelopmentPlan};
+use kancolle_a::importer::kancolle_arcade_net::{read_equipbook, read_personal_basic_info};
+use kancolle_a::ships::ShipsBuilder;
+use kancolle_a_cli_tools::cli_helpers;
+use std::fs::File;
+use std::io::BufReader;
+
+pub(crate) mod args {
+    use bpaf::*;
+    use kancolle_a_cli_tools::cli_helpers::{self, ShipSourceDataOptions};
+    use std::path::PathBuf;
+
+    #[derive(Debug, Clone)]
+    pub(crate) struct Options {
+        pub(crate) data: ShipSourceDataOptions,
+        pub(crate) equipbook: Option<PathBuf>,
+        pub(crate) basic_info: Option<PathBuf>,
+        pub(crate) available_only: bool,
+    }
+
+    pub fn options() -> OptionParser<Options> {
+        let data = cli_helpers::ship_source_data_parser();
+        let equipbook = long("equipbook")
+            .help("A copy of your https://kancolle-arcade.net/ac/api/EquipBook/info, to name the equipment")
+            .argument::<PathBuf>("EQUIPBOOK")
+            .optional();
+        let basic_info = long("basic-info")
+            .help("A copy of your https://kancolle-arcade.net/ac/api/Personal/basicInfo, to compare against your holdings")
+            .argument::<PathBuf>("BASIC_INFO")
+            .optional();
+        let available_only = long("available-only")
+            .help("Only list developments whose ship meets the level requirement")
+            .switch();
+        construct!(Options {
+            data,
+            equipbook,
+            basic_info,
+            available_only
+        })
+        .to_options()
+        .descr("A tool to plan equipment development across your fleet.")
+    }
+
+    #[test]
+    fn kca_development_plan_check_options() {
+        options().check_invariants(false)
+    }
+}
+
+fn print_cost(label: &str, cost: &DevelopmentCost) {
+    println!(
+        "{label}:\t{} strategy points\t{} medals",
+        cost.strategy_points, cost.material_medals
+    );
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    let args = args::options().run();
+
+    let ships = cli_helpers::ship_source_data_applier(&args.data, ShipsBuilder::default())?
+        .build()
+        .await?;
+    cli_helpers::report_warnings(&ships);
+
+    let equip_book = match &args.equipbook {
+        Some(path) => read_equipbook(BufReader::new(File::open(path)?))?,
+        None => vec![],
+    };
+    let basic_info = match &args.basic_info {
+        Some(path) => Some(read_personal_basic_info(BufReader::new(File::open(path)?))?),
+        None => None,
+    };
+
+    let plan = DevelopmentPlan::new(&ships);
+
+    println!("Lv\tShip\tEquipment\tLeft\tSP\tMedals");
+    for development in plan.developments() {
+        if args.available_only && !development.level_met() {
+            continue;
+        }
+        let equipment = development.equipment();
+        let name = match development.book_equipment(&equip_book) {
+            Some(book_equipment) => book_equipment.equip_name.as_str(),
+            None => equipment.develop_equip_img.as_str(),
+        };
+        let level_mark = match development.level_met() {
+            true => "",
+            false => "!",
+        };
+        let cost = development.cost();
+        println!(
+            "{level_mark}{}\t{}\t{name}\t{}\t{}\t{}",
+            equipment.require_lv,
+            development.shipmod().name(),
+            development.remaining(),
+            cost.strategy_points,
+            cost.material_medals
+        );
+    }
+    println!();
+
+    let available = plan.available_cost();
+    print_cost("Level met", &available);
+    if !args.available_only {
+        print_cost("All", &plan.total_cost());
+    }
+
+    if let Some(basic_info) = basic_info {
+        let held = DevelopmentCost::held(&basic_info);
+        print_cost("Held", &held);
+        print_cost("Short", &available.shortfall(&held));
+    }
+
+    Ok(())
+}
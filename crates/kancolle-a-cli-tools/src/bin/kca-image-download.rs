@@ -0,0 +1,73 @@
+use anyhow::Result;
+use kancolle_a::importer::kancolle_arcade_net::image_cache::ImageCacheBuilder;
+use kancolle_a::ships::ShipsBuilder;
+use kancolle_a_cli_tools::cli_helpers;
+
+pub(crate) mod args {
+    use bpaf::*;
+    use kancolle_a::importer::kancolle_arcade_net::RESOURCES_BASE;
+    use kancolle_a_cli_tools::cli_helpers::{self, ShipSourceDataOptions};
+    use std::path::PathBuf;
+
+    #[derive(Debug, Clone)]
+    pub(crate) struct Options {
+        pub(crate) data: ShipSourceDataOptions,
+        pub(crate) output: PathBuf,
+        pub(crate) base_url: String,
+    }
+
+    pub fn options() -> OptionParser<Options> {
+        let data = cli_helpers::ship_source_data_parser();
+        let output = long("output")
+            .help("The directory to store images in, which keeps a manifest of what it has")
+            .argument::<PathBuf>("OUTPUT")
+            .fallback(PathBuf::from("images"))
+            .debug_fallback();
+        let base_url = long("base-url")
+            .help("Where to fetch images from, instead of kancolle-arcade.net")
+            .argument::<String>("BASE_URL")
+            .fallback(RESOURCES_BASE.to_string())
+            .display_fallback();
+        construct!(Options {
+            data,
+            output,
+            base_url
+        })
+        .to_options()
+        .descr("A tool to download the card, status and equipment images for your collection.")
+        .header("Images already in OUTPUT are not downloaded again.")
+    }
+
+    #[test]
+    fn kca_image_download_check_options() {
+        options().check_invariants(false)
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    let args = args::options().run();
+
+    let ships = cli_helpers::ship_source_data_applier(&args.data, ShipsBuilder::default())?
+        .build()
+        .await?;
+    cli_helpers::report_warnings(&ships);
+
+    let mut cache = ImageCacheBuilder::new(&args.output)
+        .base_url(args.base_url)
+        .build()?;
+    let report = cache.fetch_all(ships.images()).await?;
+
+    for (image, error) in report.failed.iter() {
+        eprintln!("Failed to fetch {} {}: {error}", image.kind(), image.path());
+    }
+    println!(
+        "Fetched {}, already had {}, failed {}, in {}",
+        report.fetched.len(),
+        report.skipped.len(),
+        report.failed.len(),
+        cache.directory().display()
+    );
+
+    Ok(())
+}
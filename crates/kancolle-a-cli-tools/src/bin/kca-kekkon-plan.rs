@@ -0,0 +1,65 @@
+use anyhow::Result;
+use itertools::Itertools;
+use kancolle_a::kekkon::{KekkonPlan, KekkonStatus};
+use kancolle_a::ships::ShipsBuilder;
+use kancolle_a::time::today_in_japan;
+use kancolle_a_cli_tools::cli_helpers;
+
+pub(crate) mod args {
+    use bpaf::*;
+    use kancolle_a_cli_tools::cli_helpers::{self, ShipSourceDataOptions};
+
+    #[derive(Debug, Clone)]
+    pub(crate) struct Options {
+        pub(crate) data: ShipSourceDataOptions,
+        pub(crate) all: bool,
+    }
+
+    pub fn options() -> OptionParser<Options> {
+        let data = cli_helpers::ship_source_data_parser();
+        let all = long("all")
+            .help("Also list married ships, and ships not yet on the kekkon list")
+            .switch();
+        construct!(Options { data, all })
+            .to_options()
+            .descr("A tool to plan ケッコンカッコカリ for your collection.")
+    }
+
+    #[test]
+    fn kca_kekkon_plan_check_options() {
+        options().check_invariants(false)
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    let args = args::options().run();
+
+    let ships = cli_helpers::ship_source_data_applier(&args.data, ShipsBuilder::default())?
+        .build()
+        .await?;
+    cli_helpers::report_warnings(&ships);
+
+    // Kekkon start dates are in Japan time.
+    let today = today_in_japan();
+
+    let plan = KekkonPlan::new(&ships, today);
+
+    println!("Status\tLv\tExp\tShip\tMarried forms");
+    for candidate in plan.candidates() {
+        let status = match candidate.status() {
+            KekkonStatus::Married | KekkonStatus::NotListed if !args.all => continue,
+            KekkonStatus::NotYetAvailable(start_time) => format!("From {start_time}"),
+            status => status.to_string(),
+        };
+        println!(
+            "{status}\t{}\t{}%\t{}\t{}",
+            candidate.level(),
+            candidate.exp_percent(),
+            candidate.shipmod().name(),
+            candidate.married_forms().iter().join(", ")
+        );
+    }
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
 use anyhow::Result;
+use itertools::Itertools;
 use kancolle_a::{
-    importer::kancolle_arcade_net::BookShipCardPageSourceDiscriminants, ships::ShipsBuilder,
+    cards::{Card, CardVariation},
+    importer::kancolle_arcade_net::BookShipCardPageSourceDiscriminants,
+    ships::ShipsBuilder,
 };
 use kancolle_a_cli_tools::cli_helpers;
 
@@ -47,66 +50,47 @@ async fn main() -> Result<()> {
     let ships = cli_helpers::ship_source_data_applier(&args.data, ShipsBuilder::default())?
         .build()
         .await?;
+    cli_helpers::report_warnings(&ships);
 
-    let mut card_status: Vec<(u16, String, bool, bool, bool)> = vec![];
-
-    // TODO: OriginalIllustration1/2 needs to be handled specially, card_status assumes badly.
     let target_source = args.source;
     let skip_unseen = args.skip_unseen;
 
-    for shipmod in ships
-        .shipmod_iter()
-        .filter(|shipmod| shipmod.book().is_some())
-    {
-        let book_ship = shipmod.book().as_ref().unwrap();
-        for page in book_ship.card_list.iter().filter(|page| {
-            BookShipCardPageSourceDiscriminants::from(book_ship.source(page.priority))
-                == target_source
-        }) {
-            // Sort-of special case: 雪風 has no swimsuits, but 雪風改 does.
-            // So when the page was split, 雪風 page 1 ends up with an empty list of cards.
-            // TODO: Find a way to remove the page entirely without breaking callers of source()
-            if page.card_img_list.is_empty() {
-                continue;
-            }
-
-            card_status.push((
-                book_ship.book_no,
-                shipmod.name().clone(),
-                !page.card_img_list[0].is_empty(),
-                !page.card_img_list[1].is_empty(),
-                !page.card_img_list[2].is_empty(),
-            ));
-        }
-    }
-
-    if target_source == BookShipCardPageSourceDiscriminants::Normal {
-        card_status.sort_by_key(|(book_no, _, _, _, _)| *book_no);
-    } else {
-        card_status.sort_by_cached_key(|(_, name, _, _, _)| name.clone());
-    }
+    // Group the cards back into pages, so we can report a page per line.
+    let pages = ships
+        .cards()
+        .filter(|card| BookShipCardPageSourceDiscriminants::from(card.source()) == target_source)
+        .chunk_by(|card| (card.book_no(), card.ship_name(), card.priority()));
+    let mut card_status: Vec<(u16, &str, Vec<Card>)> = pages
+        .into_iter()
+        .map(|((book_no, ship_name, _), cards)| (book_no, ship_name, cards.collect()))
+        .collect();
+
+    // In the game's order, which keeps each ship's pages together.
+    card_status.sort_by_cached_key(|(_, ship_name, cards)| {
+        (ships.shipmod_by_name(ship_name), cards[0].priority())
+    });
 
     println!("Missing ({target_source:?})");
     println!("#\tNHD\tShip");
 
-    for (book_no, ship_name, normal, holo, damaged) in {
-        card_status
+    for (book_no, ship_name, cards) in card_status
+        .iter()
+        .filter(|(_, _, cards)| {
+            target_source != BookShipCardPageSourceDiscriminants::Normal
+                || cards
+                    .iter()
+                    .any(|card| card.variation() == CardVariation::Normal && !card.owned())
+        })
+        .filter(|(_, _, cards)| !skip_unseen || cards.iter().any(|card| card.owned()))
+    {
+        // Original Illustration pages have one or two O cards, rather than NHD.
+        let status: String = cards
             .iter()
-            .filter(|(_, _, normal, _, _)| {
-                target_source != BookShipCardPageSourceDiscriminants::Normal || !normal
+            .map(|card| match card.owned() {
+                true => card.variation().code(),
+                false => '.',
             })
-            .filter(|(_, _, normal, holo, damaged)| !skip_unseen || (*normal || *holo || *damaged))
-    } {
-        let status = match (normal, holo, damaged) {
-            (false, false, false) => "...",
-            (false, false, true) => "..D",
-            (false, true, false) => ".H.",
-            (false, true, true) => ".HD",
-            (true, false, false) => "N..",
-            (true, false, true) => "N.D",
-            (true, true, false) => "NH.",
-            (true, true, true) => "NHD",
-        };
+            .collect();
         println!("{book_no}\t{status}\t{ship_name}");
     }
 
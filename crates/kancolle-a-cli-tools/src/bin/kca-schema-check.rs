@@ -0,0 +1,148 @@
+use anyhow::{bail, Result};
+use kancolle_a::importer::kancolle_arcade_net::{
+    schema_check::schema_changes, ApiEndpoint, CardPageSources, ClientBuilder,
+};
+use kancolle_a_cli_tools::cli_helpers;
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+pub(crate) mod args {
+    use bpaf::*;
+    use std::path::PathBuf;
+
+    #[derive(Debug, Clone)]
+    pub(crate) struct Options {
+        pub(crate) jsessionid: Option<String>,
+        pub(crate) username: Option<String>,
+        pub(crate) known: PathBuf,
+        pub(crate) live: Option<PathBuf>,
+        pub(crate) card_page_sources: Option<PathBuf>,
+    }
+
+    pub fn options() -> OptionParser<Options> {
+        let jsessionid = long("jsessionid")
+            .help("The JSESSIONID cookie from a logged-in session at https://kancolle-arcade.net/ac/api")
+            .argument::<String>("JSESSIONID")
+            .optional();
+        let username = long("username")
+            .help("The USERNAME to log into https://kancolle-arcade.net/ac/")
+            .argument("USERNAME")
+            .optional();
+        let known = long("known")
+            .help("A directory of previous responses, as written by kca-api-dump, e.g. crates/kancolle-a/tests/fixtures/latest in a checkout of this repository")
+            .argument::<PathBuf>("KNOWN");
+        let live = long("live")
+            .help("A directory of responses to check, instead of fetching them")
+            .argument::<PathBuf>("LIVE")
+            .optional();
+        let card_page_sources = long("card-page-sources")
+            .help("Additional TcBook card page sources, in the format of the builtin card_page_sources.json")
+            .argument::<PathBuf>("SOURCES")
+            .optional();
+        construct!(Options {
+            jsessionid,
+            username,
+            known,
+            live,
+            card_page_sources
+        })
+        .to_options()
+        .descr("A tool to report changes in the data from https://kancolle-arcade.net/ac/")
+    }
+
+    #[test]
+    fn kca_schema_check_check_options() {
+        options().check_invariants(false)
+    }
+}
+
+/// Get the live data for each endpoint, from the given directory or from the API.
+async fn live_data(args: &args::Options, endpoints: &[ApiEndpoint]) -> Result<Vec<Result<String>>> {
+    if let Some(live) = &args.live {
+        return Ok(endpoints
+            .iter()
+            .map(|endpoint| read_fixture(live, endpoint))
+            .collect());
+    }
+
+    let mut client_builder = ClientBuilder::new();
+    if let Some(jsessionid) = &args.jsessionid {
+        client_builder = client_builder.jsessionid(jsessionid.clone());
+    }
+    if let Some(username) = &args.username {
+        let prompt = format!("Enter the password for {}:", username);
+        let password = rpassword::prompt_password(prompt)?;
+        client_builder = client_builder.userpass(username.clone(), password);
+    }
+    let client = client_builder.build()?;
+
+    Ok(client
+        .fetch_snapshot(endpoints)
+        .await
+        .into_iter()
+        .map(|response| {
+            let mut data = String::new();
+            response?.read_to_string(&mut data)?;
+            Ok(data)
+        })
+        .collect())
+}
+
+fn read_fixture(directory: &Path, endpoint: &ApiEndpoint) -> Result<String> {
+    Ok(fs::read_to_string(
+        directory.join(cli_helpers::fixture_filename(endpoint)?),
+    )?)
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    let args = args::options().run();
+
+    let mut card_page_sources = CardPageSources::bundled().clone();
+    if let Some(path) = &args.card_page_sources {
+        card_page_sources.overlay(CardPageSources::from_reader(BufReader::new(File::open(
+            path,
+        )?))?);
+    }
+
+    let endpoints = cli_helpers::fixture_endpoints();
+    let live = live_data(&args, &endpoints).await?;
+
+    let mut changed = 0;
+    let mut failures = 0;
+    for (endpoint, live) in endpoints.iter().zip(live) {
+        let filename = cli_helpers::fixture_filename(endpoint)?;
+        let changes = live.and_then(|live| {
+            let known = read_fixture(&args.known, endpoint)?;
+            Ok(schema_changes(endpoint, &known, &live, &card_page_sources)?)
+        });
+        match changes {
+            Ok(changes) if changes.is_empty() => (),
+            Ok(changes) => {
+                changed += 1;
+                println!("{filename}:");
+                for change in changes {
+                    println!("  {change}");
+                }
+            }
+            Err(error) => {
+                eprintln!("{filename}: {error}");
+                failures += 1;
+            }
+        }
+    }
+
+    if changed == 0 {
+        println!("No changes found");
+    }
+
+    if failures > 0 {
+        bail!(
+            "Failed to check {failures} of {} endpoints",
+            endpoints.len()
+        );
+    }
+
+    Ok(())
+}
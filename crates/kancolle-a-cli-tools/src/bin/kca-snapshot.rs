@@ -0,0 +1,98 @@
+use anyhow::{bail, Result};
+use kancolle_a::importer::kancolle_arcade_net::snapshot::SnapshotStore;
+use kancolle_a::importer::kancolle_arcade_net::ClientBuilder;
+use kancolle_a_cli_tools::cli_helpers;
+
+pub(crate) mod args {
+    use bpaf::*;
+    use std::path::PathBuf;
+
+    #[derive(Debug, Clone)]
+    pub(crate) struct Options {
+        pub(crate) jsessionid: Option<String>,
+        pub(crate) username: Option<String>,
+        pub(crate) store: PathBuf,
+        pub(crate) list: bool,
+    }
+
+    pub fn options() -> OptionParser<Options> {
+        let jsessionid = long("jsessionid")
+            .help("The JSESSIONID cookie value from https://kancolle-arcade.net/ac/")
+            .argument::<String>("JSESSIONID")
+            .optional();
+        let username = long("username")
+            .help("The USERNAME to log into https://kancolle-arcade.net/ac/")
+            .argument("USERNAME")
+            .optional();
+        let store = long("store")
+            .help("The directory to keep snapshots in")
+            .argument::<PathBuf>("STORE")
+            .fallback(PathBuf::from("snapshots"))
+            .debug_fallback();
+        let list = long("list")
+            .help("List the snapshots in the store, rather than taking one")
+            .switch();
+        construct!(Options {
+            jsessionid,
+            username,
+            store,
+            list
+        })
+        .to_options()
+        .descr("A tool to save a timestamped snapshot of all supported data from https://kancolle-arcade.net/ac/")
+        .header("Other tools can read a snapshot with --snapshot-store and --snapshot-at.")
+    }
+
+    #[test]
+    fn kca_snapshot_check_options() {
+        options().check_invariants(false)
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    let args = args::options().run();
+
+    let store = SnapshotStore::new(&args.store);
+
+    if args.list {
+        for snapshot in store.snapshots()? {
+            println!(
+                "{}\t{}",
+                snapshot.taken_at().to_rfc3339(),
+                snapshot.directory().display()
+            );
+        }
+        return Ok(());
+    }
+
+    let mut client_builder = ClientBuilder::new();
+    if let Some(jsessionid) = args.jsessionid {
+        client_builder = client_builder.jsessionid(jsessionid);
+    }
+    if let Some(username) = args.username {
+        let prompt = format!("Enter the password for {}:", username);
+        let password = rpassword::prompt_password(prompt)?;
+        client_builder = client_builder.userpass(username, password);
+    }
+    let client = client_builder.build()?;
+
+    let endpoints = cli_helpers::fixture_endpoints();
+    let report = store.fetch(&client, &endpoints).await?;
+
+    if let Some(snapshot) = &report.snapshot {
+        println!("Saved {}", snapshot.directory().display());
+    }
+    for (endpoint, error) in &report.failed {
+        eprintln!("{endpoint:?}: {error}");
+    }
+    if !report.failed.is_empty() {
+        bail!(
+            "Failed to fetch {} of {} endpoints",
+            report.failed.len(),
+            endpoints.len()
+        );
+    }
+
+    Ok(())
+}
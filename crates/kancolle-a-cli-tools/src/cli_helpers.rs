@@ -1,8 +1,12 @@
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use bpaf::*;
+use chrono::{DateTime, NaiveDate, Utc};
 use itertools;
 use kancolle_a::{
-    importer::kancolle_arcade_net::BookShipCardPageSourceDiscriminants, ships::ShipsBuilder,
+    importer::kancolle_arcade_net::{
+        snapshot::SnapshotStore, ApiEndpoint, BookShipCardPageSourceDiscriminants,
+    },
+    ships::{Ships, ShipsBuilder},
 };
 use std::fs::File;
 use std::io::BufReader;
@@ -84,6 +88,46 @@ fn kekkon_path_parser() -> impl Parser<Option<PathBuf>> {
         .optional()
 }
 
+fn card_page_sources_path_parser() -> impl Parser<Option<PathBuf>> {
+    long("card-page-sources")
+        .help("Additional TcBook card page sources, in the format of the builtin card_page_sources.json")
+        .argument::<PathBuf>("SOURCES")
+        .optional()
+}
+
+pub fn snapshot_store_parser() -> impl Parser<Option<PathBuf>> {
+    long("snapshot-store")
+        .help("A snapshot store from kca-snapshot, to read the latest snapshot from")
+        .argument::<PathBuf>("STORE")
+        .optional()
+}
+
+/// A UTC date and time, e.g. 2025-06-01T07:00:00Z, or a date meaning midnight UTC
+pub fn parse_snapshot_time(time: &str) -> Result<DateTime<Utc>> {
+    if let Ok(time) = time.parse::<DateTime<Utc>>() {
+        return Ok(time);
+    }
+    Ok(time
+        .parse::<NaiveDate>()?
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc())
+}
+
+fn snapshot_at_parser() -> impl Parser<Option<DateTime<Utc>>> {
+    long("snapshot-at")
+        .help("Read the latest snapshot taken at or before TIME, e.g. 2025-06-01 or 2025-06-01T07:00:00Z, rather than the latest")
+        .argument::<String>("TIME")
+        .parse(|time| parse_snapshot_time(&time))
+        .optional()
+}
+
+fn lenient_parser() -> impl Parser<bool> {
+    long("lenient")
+        .help("Accept kancolle-arcade.net data with unknown fields, reporting them as warnings")
+        .switch()
+}
+
 /// A common CLI parser for getting the data needed to populate ships::ShipsBuilder
 #[derive(Debug, Clone)]
 pub struct ShipSourceDataOptions {
@@ -91,8 +135,12 @@ pub struct ShipSourceDataOptions {
     pub bplist: Option<PathBuf>,
     pub charlist: Option<PathBuf>,
     pub kekkon: Option<PathBuf>,
+    pub card_page_sources: Option<PathBuf>,
+    pub snapshot_store: Option<PathBuf>,
+    pub snapshot_at: Option<DateTime<Utc>>,
     pub jsessionid: Option<String>,
     pub username: Option<String>,
+    pub lenient: bool,
 }
 
 pub fn ship_source_data_parser() -> impl Parser<ShipSourceDataOptions> {
@@ -102,13 +150,21 @@ pub fn ship_source_data_parser() -> impl Parser<ShipSourceDataOptions> {
     let bplist = bplist_path_parser();
     let charlist = charlist_path_parser();
     let kekkon = kekkon_path_parser();
+    let card_page_sources = card_page_sources_path_parser();
+    let snapshot_store = snapshot_store_parser();
+    let snapshot_at = snapshot_at_parser();
+    let lenient = lenient_parser();
     construct!(ShipSourceDataOptions {
         jsessionid,
         username,
         tcbook,
         bplist,
         charlist,
-        kekkon
+        kekkon,
+        card_page_sources,
+        snapshot_store,
+        snapshot_at,
+        lenient
     })
 }
 
@@ -116,6 +172,20 @@ pub fn ship_source_data_applier(
     args: &ShipSourceDataOptions,
     mut builder: ShipsBuilder,
 ) -> Result<ShipsBuilder> {
+    // Applied first, so any files given explicitly take precedence.
+    match (&args.snapshot_store, args.snapshot_at) {
+        (Some(snapshot_store), snapshot_at) => {
+            let store = SnapshotStore::new(snapshot_store);
+            let snapshot = match snapshot_at {
+                Some(time) => store.at(time)?,
+                None => store.latest()?,
+            }
+            .ok_or_else(|| anyhow!("No snapshot found in {}", snapshot_store.display()))?;
+            builder = snapshot.apply(builder)?;
+        }
+        (None, Some(_)) => bail!("--snapshot-at needs --snapshot-store"),
+        (None, None) => {}
+    }
     if let Some(tcbook) = &args.tcbook {
         builder = builder.book_from_reader(BufReader::new(File::open(tcbook)?));
     }
@@ -128,6 +198,10 @@ pub fn ship_source_data_applier(
     if let Some(kekkon) = &args.kekkon {
         builder = builder.kekkon_from_reader(BufReader::new(File::open(kekkon)?));
     }
+    if let Some(card_page_sources) = &args.card_page_sources {
+        builder =
+            builder.card_page_sources_from_reader(BufReader::new(File::open(card_page_sources)?));
+    }
     if let Some(jsessionid) = &args.jsessionid {
         builder = builder.jsessionid(jsessionid.clone());
     }
@@ -136,6 +210,63 @@ pub fn ship_source_data_applier(
         let password = rpassword::prompt_password(prompt)?;
         builder = builder.userpass(username.clone(), password);
     }
+    if args.lenient {
+        builder = builder.lenient();
+    }
 
     Ok(builder)
 }
+
+/// Report any problems found in the source data that didn't stop us building Ships.
+pub fn report_warnings(ships: &Ships) {
+    for warning in ships.warnings() {
+        eprintln!("Warning: {}", warning);
+    }
+}
+
+/// The endpoints we keep test fixtures for, i.e. everything `kca-api-dump` fetches.
+pub fn fixture_endpoints() -> Vec<ApiEndpoint> {
+    vec![
+        // Auth not required for these
+        ApiEndpoint::KanmusuList,
+        ApiEndpoint::EventHold,
+        ApiEndpoint::EventInfo,
+        ApiEndpoint::PlaceDistricts,
+        ApiEndpoint::PlacePlaces,
+        ApiEndpoint::RankingMonthlyCurrent,
+        ApiEndpoint::RankingMonthlyPrev,
+        ApiEndpoint::RankingTotal,
+        ApiEndpoint::TcErrorDispFlag,
+        // Auth is required for the below
+        ApiEndpoint::AimeCampaignHold,
+        ApiEndpoint::AimeCampaignInfo,
+        ApiEndpoint::AreaCaptureInfo,
+        ApiEndpoint::BlueprintListInfo,
+        ApiEndpoint::CampaignHistory,
+        ApiEndpoint::CampaignInfo,
+        ApiEndpoint::CampaignPresent,
+        ApiEndpoint::CharacterListInfo,
+        ApiEndpoint::CopCheckreward,
+        ApiEndpoint::CopHold,
+        // Skipping CopInfo, it includes store locations and as such is TMI to commit.
+        // It is an empty file when "Cop" (location) events are not running.
+        //ApiEndpoint::CopInfo,
+        ApiEndpoint::EpFesHold,
+        ApiEndpoint::EpFesProgress,
+        ApiEndpoint::EquipBookInfo,
+        ApiEndpoint::EquipListInfo,
+        ApiEndpoint::ExerciseInfo,
+        ApiEndpoint::NCampInfo,
+        ApiEndpoint::PersonalBasicInfo,
+        ApiEndpoint::QuestInfo,
+        ApiEndpoint::RoomItemListInfo,
+        ApiEndpoint::TcBookInfo,
+    ]
+}
+
+/// The name of the test fixture for the given endpoint.
+pub fn fixture_filename(endpoint: &ApiEndpoint) -> Result<String> {
+    endpoint
+        .file_name()
+        .ok_or_else(|| anyhow!("No fixture file name for {endpoint:?}"))
+}
@@ -1 +1,2 @@
+pub mod album;
 pub mod cli_helpers;
@@ -0,0 +1,188 @@
+//! The cost in blueprints (改造設計図) and large-scale blueprints (大型改装設計図) of
+//! remodelling a ship.
+//!
+//! Costs are generally by ship type, with overrides for special ship classes and
+//! individual ships, and some remodels cannot be bought with blueprints (yet), e.g.
+//! those just released in events. The bundled data is in
+//! `blueprint_costs/blueprint_costs.json`, based on
+//! https://wikiwiki.jp/kancolle-a/%E5%BB%BA%E9%80%A0#kaizou
+
+use chrono::NaiveDate;
+use derive_getters::Getters;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::sync::OnceLock;
+
+use crate::ship_attributes::ShipType;
+use crate::{Error, Result};
+
+#[cfg(not(target_arch = "wasm32"))]
+use lazy_static_include::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+lazy_static_include_bytes! {
+    pub(crate) BLUEPRINT_COSTS => "src/blueprint_costs/blueprint_costs.json",
+}
+
+// wasm32 has no filesystem to lazy-load from in debug builds, so always embed.
+#[cfg(target_arch = "wasm32")]
+pub(crate) static BLUEPRINT_COSTS: &[u8] = include_bytes!("blueprint_costs/blueprint_costs.json");
+
+static BUNDLED: OnceLock<BlueprintCostTable> = OnceLock::new();
+
+/// The version of the data format we understand
+const BLUEPRINT_COSTS_VERSION: u32 = 1;
+
+/// Blueprints and large-scale blueprints needed for each stage.
+/// Stage 0 is the cost to remodel _from_ the base ship, i.e. to remodel level 1.
+pub type StageCosts = Vec<(u16, u8)>;
+
+/// Costs for a set of ship types
+#[derive(Debug, Clone, Getters, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipTypeCosts {
+    ship_types: Vec<ShipType>,
+    stages: StageCosts,
+}
+
+/// Costs for a special ship class, identified by `shipClassId` if known, and otherwise
+/// by class name.
+#[derive(Debug, Clone, Getters, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipClassCosts {
+    #[serde(default)]
+    id: Option<u16>,
+    #[serde(default)]
+    name: Option<String>,
+    stages: StageCosts,
+}
+
+impl ShipClassCosts {
+    fn matches(&self, class_id: Option<u16>, class_name: Option<&str>) -> bool {
+        match (self.id, class_id) {
+            (Some(id), Some(class_id)) => id == class_id,
+            _ => self.name.is_some() && self.name.as_deref() == class_name,
+        }
+    }
+}
+
+/// Overrides for a single ship
+#[derive(Debug, Clone, Getters, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipCosts {
+    /// Base ship name
+    ship: String,
+
+    /// Replaces the ship type or class costs, if present
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stages: Option<StageCosts>,
+
+    /// ShipMods which cannot be bought with blueprints yet
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    not_remodelable: Vec<String>,
+}
+
+/// The blueprint cost table
+#[derive(Debug, Clone, Getters, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlueprintCostTable {
+    /// Version of the data format
+    version: u32,
+
+    /// When the data was last checked
+    updated: NaiveDate,
+
+    /// Costs for ship types not otherwise listed.
+    /// May be left out of a table to overlay, see `overlay`.
+    #[serde(default)]
+    default: StageCosts,
+
+    #[serde(default)]
+    ship_types: Vec<ShipTypeCosts>,
+
+    #[serde(default)]
+    ship_classes: Vec<ShipClassCosts>,
+
+    #[serde(default)]
+    ships: Vec<ShipCosts>,
+}
+
+impl BlueprintCostTable {
+    /// The blueprint cost table bundled with this crate
+    // BLUEPRINT_COSTS is already a &[u8] on wasm32
+    #[cfg_attr(target_arch = "wasm32", allow(clippy::useless_asref))]
+    pub fn bundled() -> &'static BlueprintCostTable {
+        BUNDLED.get_or_init(|| {
+            BlueprintCostTable::from_reader(BLUEPRINT_COSTS.as_ref())
+                .expect("bundled blueprint_costs.json is valid")
+        })
+    }
+
+    /// Parses a BlueprintCostTable from the provided JSON reader, in the same format as
+    /// the bundled `blueprint_costs.json`.
+    /// Fails if the data is not in a version of the format we understand.
+    pub fn from_reader(reader: impl Read) -> Result<BlueprintCostTable> {
+        let table: BlueprintCostTable = serde_json::from_reader(reader)?;
+        if table.version != BLUEPRINT_COSTS_VERSION {
+            return Err(Error::UnsupportedVersion {
+                data: "blueprint cost table".to_string(),
+                version: table.version,
+            });
+        }
+        Ok(table)
+    }
+
+    /// Add the entries from other, replacing any of ours for the same ship, ship class
+    /// or ship type. Our default is only replaced if other has one.
+    pub fn overlay(&mut self, other: BlueprintCostTable) {
+        self.updated = self.updated.max(other.updated);
+        if !other.default.is_empty() {
+            self.default = other.default;
+        }
+        // Lookups take the first match, so other's entries go first.
+        self.ship_types.splice(0..0, other.ship_types);
+        self.ship_classes.splice(0..0, other.ship_classes);
+        self.ships.splice(0..0, other.ships);
+    }
+
+    /// The overrides for the given base ship, if any
+    pub fn ship(&self, ship_name: &str) -> Option<&ShipCosts> {
+        self.ships.iter().find(|ship| ship.ship == ship_name)
+    }
+
+    /// The costs for each stage of the given ship.
+    /// Generally based on ship type, so is not aware of which specific ships have 改二
+    /// or later mods.
+    pub fn stage_costs(
+        &self,
+        ship_name: &str,
+        class_id: Option<u16>,
+        class_name: Option<&str>,
+        ship_type: ShipType,
+    ) -> &StageCosts {
+        if let Some(stages) = self.ship(ship_name).and_then(|ship| ship.stages.as_ref()) {
+            return stages;
+        }
+        if let Some(class) = self
+            .ship_classes
+            .iter()
+            .find(|class| class.matches(class_id, class_name))
+        {
+            return &class.stages;
+        }
+        self.ship_types
+            .iter()
+            .find(|costs| costs.ship_types.contains(&ship_type))
+            .map(|costs| &costs.stages)
+            .unwrap_or(&self.default)
+    }
+
+    /// True unless the given ShipMod is known to not be buyable with blueprints yet.
+    pub fn remodelable(&self, ship_name: &str, shipmod_name: &str) -> bool {
+        self.ship(ship_name)
+            .is_none_or(|ship| !ship.not_remodelable.iter().any(|name| name == shipmod_name))
+    }
+}
+
+#[cfg(test)]
+mod tests;
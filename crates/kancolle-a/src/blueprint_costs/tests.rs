@@ -0,0 +1,103 @@
+use super::*;
+
+#[test]
+fn test_ship_blueprint_costs() {
+    let table = BlueprintCostTable::bundled();
+    let cost = |class_id: Option<u16>, class_name: Option<&str>, ship_type, stage: usize| {
+        table
+            .stage_costs("", class_id, class_name, ship_type)
+            .get(stage)
+            .copied()
+    };
+
+    assert_eq!((3, 0), cost(None, None, ShipType::RepairShip, 0).unwrap());
+    assert_eq!(None, cost(None, None, ShipType::RepairShip, 6));
+
+    // Special classes, by id or by name
+    assert_eq!(
+        Some((8, 2)),
+        cost(Some(5), None, ShipType::SeaplaneTender, 4)
+    );
+    assert_eq!(
+        Some((8, 2)),
+        cost(None, Some("千歳型"), ShipType::SeaplaneTender, 4)
+    );
+    assert_eq!(
+        None,
+        cost(Some(1), Some("千歳型"), ShipType::SeaplaneTender, 4)
+    );
+    assert_eq!(
+        Some((5, 0)),
+        cost(Some(99), Some("春日丸級"), ShipType::LightCarrier, 1)
+    );
+
+    // By ship type
+    assert_eq!(Some((6, 3)), cost(None, None, ShipType::Destroyer, 2));
+    assert_eq!(Some((8, 4)), cost(None, None, ShipType::HeavyCruiser, 2));
+
+    assert!(!table.remodelable("最上", "最上改二"));
+    assert!(table.remodelable("最上", "最上改"));
+    assert!(table.remodelable("鳥海", "鳥海改二"));
+}
+
+#[test]
+fn parse_blueprint_costs_overrides() {
+    let json = r#"{
+        "version": 1,
+        "updated": "2026-01-01",
+        "default": [[3, 0]],
+        "shipTypes": [{"shipTypes": ["駆逐艦"], "stages": [[3, 0], [6, 1], [6, 3]]}],
+        "ships": [{"ship": "時雨", "stages": [[3, 0], [6, 1], [6, 3], [10, 5]]}]
+    }"#;
+    let table = BlueprintCostTable::from_reader(json.as_bytes()).unwrap();
+    assert_eq!(
+        table.updated(),
+        &NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()
+    );
+    assert_eq!(
+        table.stage_costs("時雨", None, None, ShipType::Destroyer)[3],
+        (10, 5)
+    );
+    assert_eq!(
+        table
+            .stage_costs("夕立", None, None, ShipType::Destroyer)
+            .len(),
+        3
+    );
+
+    let future = json.replace(r#""version": 1"#, r#""version": 2"#);
+    assert!(matches!(
+        BlueprintCostTable::from_reader(future.as_bytes()),
+        Err(Error::UnsupportedVersion { version: 2, .. })
+    ));
+}
+
+#[test]
+fn test_blueprint_costs_overlay() {
+    let json = r#"{
+        "version": 1,
+        "updated": "2099-01-01",
+        "shipClasses": [{"name": "千歳型", "stages": [[3, 0], [4, 1]]}],
+        "ships": [{"ship": "最上", "notRemodelable": ["最上改二特"]}]
+    }"#;
+    let mut table = BlueprintCostTable::bundled().clone();
+    table.overlay(BlueprintCostTable::from_reader(json.as_bytes()).unwrap());
+
+    assert_eq!(
+        table.updated(),
+        &NaiveDate::from_ymd_opt(2099, 1, 1).unwrap()
+    );
+    // Not given, so kept
+    assert_eq!(table.default(), BlueprintCostTable::bundled().default());
+    assert_eq!(
+        table.stage_costs("", None, None, ShipType::Destroyer),
+        &vec![(3, 0), (6, 1), (6, 3)]
+    );
+    // Replaced
+    assert_eq!(
+        table.stage_costs("", Some(5), Some("千歳型"), ShipType::SeaplaneTender),
+        &vec![(3, 0), (4, 1)]
+    );
+    assert!(table.remodelable("最上", "最上改二"));
+    assert!(!table.remodelable("最上", "最上改二特"));
+}
@@ -0,0 +1,256 @@
+//! Forecasting blueprint (改装設計図) expiry against what each ship is saving for.
+//!
+//! Each Ship saves its blueprints for a target remodel: its highest remodel whose
+//! Normal card we don't have. Blueprints are assumed to be spent oldest first, so
+//! if the target is affordable now its cost comes off the earliest batches. What
+//! remains, and expires before the forecast horizon, would be lost, unless spent on
+//! a remodel we can afford now. No future blueprint income is assumed.
+//!
+//! The reported expiry is the 11th of the month, but may really be the end of the
+//! month; see ExpiryBasis.
+
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+use strum::{Display, EnumString};
+
+use crate::importer::kancolle_arcade_net::BlueprintExpirationDate;
+use crate::ships::{Ship, ShipMod, Ships};
+
+/// Which expiry date to forecast with
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Display, EnumString)]
+pub enum ExpiryBasis {
+    /// The reported expiry, the 11th of the month. The cautious choice.
+    #[default]
+    Reported,
+    /// The end of the month of the reported expiry
+    MonthEnd,
+}
+
+impl ExpiryBasis {
+    pub fn expires_at(&self, expiration_date: &BlueprintExpirationDate) -> DateTime<FixedOffset> {
+        match self {
+            ExpiryBasis::Reported => expiration_date.expires_at(),
+            ExpiryBasis::MonthEnd => expiration_date.expires_at_month_end(),
+        }
+    }
+}
+
+/// A remodel and its blueprint and large-scale blueprint cost
+#[derive(Debug, Clone, Copy)]
+pub struct Remodel<'a> {
+    shipmod: &'a ShipMod,
+    blueprint_cost: (u16, u8),
+}
+
+impl<'a> Remodel<'a> {
+    pub fn shipmod(&self) -> &'a ShipMod {
+        self.shipmod
+    }
+
+    /// The blueprint and large-scale blueprint cost
+    pub fn blueprint_cost(&self) -> (u16, u8) {
+        self.blueprint_cost
+    }
+
+    /// The blueprint cost
+    pub fn blueprints(&self) -> u16 {
+        self.blueprint_cost.0
+    }
+
+    /// True if we don't have the Normal card for this form
+    pub fn card_missing(&self) -> bool {
+        !has_normal_card(self.shipmod)
+    }
+}
+
+fn has_normal_card(shipmod: &ShipMod) -> bool {
+    shipmod.book().as_ref().is_some_and(|book| {
+        book.card_list
+            .first()
+            .is_some_and(|page| !page.card_img_list[0].is_empty())
+    })
+}
+
+/// The remodel a Ship is saving its blueprints for: its highest remodel whose Normal card
+/// we don't have. None if we have every remodel, or the remodel has no known cost, e.g.,
+/// newly-released ships which can't yet be remodelled.
+pub fn savings_target(ship: &Ship) -> Option<Remodel<'_>> {
+    let shipmod = ship
+        .mods()
+        .iter()
+        .rev()
+        .find(|shipmod| shipmod.remodel_level() > 0 && !has_normal_card(shipmod))?;
+    ship.shipmod_blueprint_cost(shipmod.remodel_level())
+        .map(|blueprint_cost| Remodel {
+            shipmod,
+            blueprint_cost,
+        })
+}
+
+/// Blueprints which will expire unused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpiringBatch {
+    pub expires_at: DateTime<FixedOffset>,
+    pub count: u16,
+}
+
+/// A Ship with blueprints that will expire unused
+#[derive(Debug, Clone)]
+pub struct ShipForecast<'a> {
+    ship: &'a Ship,
+    held: u16,
+    target: Option<Remodel<'a>>,
+    expiring: Vec<ExpiringBatch>,
+    spend_options: Vec<Remodel<'a>>,
+}
+
+impl<'a> ShipForecast<'a> {
+    pub fn ship(&self) -> &'a Ship {
+        self.ship
+    }
+
+    /// Blueprints held now
+    pub fn held(&self) -> u16 {
+        self.held
+    }
+
+    pub fn target(&self) -> Option<&Remodel<'a>> {
+        self.target.as_ref()
+    }
+
+    /// True if the savings target can be built now, and so will be spent first
+    pub fn target_affordable(&self) -> bool {
+        self.target
+            .is_some_and(|target| target.blueprints() <= self.held)
+    }
+
+    /// The batches which will expire unused, earliest first
+    pub fn expiring(&self) -> &[ExpiringBatch] {
+        &self.expiring
+    }
+
+    pub fn expiring_count(&self) -> u16 {
+        self.expiring.iter().map(|batch| batch.count).sum()
+    }
+
+    pub fn first_expiry(&self) -> DateTime<FixedOffset> {
+        self.expiring[0].expires_at
+    }
+
+    /// Remodels we can afford after the savings target, if affordable, has been built.
+    /// Forms whose Normal card we are missing come first, then cheapest first.
+    pub fn spend_options(&self) -> &[Remodel<'a>] {
+        &self.spend_options
+    }
+
+    /// True if building this remodel, after the savings target, would use every
+    /// expiring blueprint.
+    pub fn uses_all_expiring(&self, remodel: &Remodel) -> bool {
+        remodel.blueprints() >= self.expiring_count()
+    }
+
+    fn new(ship: &'a Ship, until: DateTime<FixedOffset>, basis: ExpiryBasis) -> Option<Self> {
+        let blueprint = ship.blueprint().as_ref()?;
+        let held = blueprint.blueprint_total_num;
+        let target = savings_target(ship);
+
+        let mut batches: Vec<ExpiringBatch> = blueprint
+            .expiration_date_list
+            .iter()
+            .map(|expiration_date| ExpiringBatch {
+                expires_at: basis.expires_at(expiration_date),
+                count: expiration_date.blueprint_num,
+            })
+            .collect();
+        batches.sort_by_key(|batch| batch.expires_at);
+
+        // Spend the target, if affordable, from the oldest batches.
+        let target_spend = match target {
+            Some(target) if target.blueprints() <= held => target.blueprints(),
+            _ => 0,
+        };
+        let mut spend = target_spend;
+        for batch in batches.iter_mut() {
+            let spent = spend.min(batch.count);
+            batch.count -= spent;
+            spend -= spent;
+        }
+        let remaining = held - target_spend;
+
+        let expiring: Vec<ExpiringBatch> = batches
+            .into_iter()
+            .filter(|batch| batch.count > 0 && batch.expires_at <= until)
+            .collect();
+        if expiring.is_empty() {
+            return None;
+        }
+
+        let mut spend_options: Vec<Remodel> = ship
+            .mods()
+            .iter()
+            .filter(|shipmod| shipmod.remodel_level() > 0)
+            // Already being built
+            .filter(|shipmod| {
+                target_spend == 0
+                    || !target.is_some_and(|target| std::ptr::eq(target.shipmod, *shipmod))
+            })
+            .filter_map(|shipmod| {
+                ship.shipmod_blueprint_cost(shipmod.remodel_level())
+                    .map(|blueprint_cost| Remodel {
+                        shipmod,
+                        blueprint_cost,
+                    })
+            })
+            .filter(|remodel| remodel.blueprints() > 0 && remodel.blueprints() <= remaining)
+            .collect();
+        spend_options.sort_by_key(|remodel| (!remodel.card_missing(), remodel.blueprints()));
+
+        Some(ShipForecast {
+            ship,
+            held,
+            target,
+            expiring,
+            spend_options,
+        })
+    }
+}
+
+/// Every Ship with blueprints expiring unused by the horizon, earliest expiry first, then
+/// in the game's order.
+pub struct BlueprintForecast<'a> {
+    forecasts: Vec<ShipForecast<'a>>,
+}
+
+impl<'a> BlueprintForecast<'a> {
+    /// `until` is the forecast horizon: blueprints expiring after it are not reported.
+    /// Requires Blueprint data, otherwise the forecast will be empty. TcBook data is
+    /// needed to tell which remodels we have, otherwise every remodel is a target.
+    pub fn new(
+        ships: &'a Ships,
+        until: DateTime<FixedOffset>,
+        basis: ExpiryBasis,
+    ) -> BlueprintForecast<'a> {
+        let mut forecasts: Vec<ShipForecast> = ships
+            .values()
+            .filter_map(|ship| ShipForecast::new(ship, until, basis))
+            .collect();
+        forecasts.sort_by(|left, right| {
+            left.first_expiry()
+                .cmp(&right.first_expiry())
+                .then_with(|| left.ship.cmp(right.ship))
+        });
+        BlueprintForecast { forecasts }
+    }
+
+    pub fn forecasts(&self) -> &[ShipForecast<'a>] {
+        &self.forecasts
+    }
+
+    /// The blueprints which will expire unused
+    pub fn expiring_count(&self) -> u16 {
+        self.forecasts
+            .iter()
+            .map(|forecast| forecast.expiring_count())
+            .sum()
+    }
+}
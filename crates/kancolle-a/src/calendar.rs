@@ -0,0 +1,224 @@
+//! iCalendar (.ics, RFC 5545) export of deadlines and event periods.
+//!
+//! Everything on kancolle-arcade.net runs on Japan time, so entries are written in
+//! Asia/Tokyo with its VTIMEZONE included, and calendar apps show them in local time.
+//! Deadlines, such as blueprint expiry and quest ends, are zero-length entries.
+
+use chrono::{DateTime, FixedOffset, Utc};
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::blueprint_forecast::ExpiryBasis;
+use crate::importer::kancolle_arcade_net::{AimeCampaign, EventHold, QuestInfo};
+use crate::ships::Ships;
+use crate::time::japan;
+
+const PRODID: &str = "-//kancolle-a//kancolle-a calendar//JA";
+const UID_DOMAIN: &str = "kancolle-a";
+
+/// An entry in the calendar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarEvent {
+    /// Stable across exports, so re-importing updates rather than duplicates
+    pub uid: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub start: DateTime<Utc>,
+    /// The same as start for deadlines
+    pub end: DateTime<Utc>,
+}
+
+/// A calendar, built up from kancolle-arcade.net data
+pub struct Calendar {
+    dtstamp: DateTime<Utc>,
+    events: Vec<CalendarEvent>,
+}
+
+impl Calendar {
+    /// `dtstamp` is when the calendar was generated, usually now.
+    pub fn new(dtstamp: DateTime<Utc>) -> Calendar {
+        Calendar {
+            dtstamp,
+            events: vec![],
+        }
+    }
+
+    pub fn events(&self) -> &[CalendarEvent] {
+        &self.events
+    }
+
+    pub fn push(&mut self, event: CalendarEvent) {
+        self.events.push(event);
+    }
+
+    /// One deadline per expiry date, listing the ships whose blueprints expire then.
+    /// Requires Blueprint data.
+    pub fn blueprint_expiries(mut self, ships: &Ships, basis: ExpiryBasis) -> Calendar {
+        let mut by_expiry: BTreeMap<DateTime<FixedOffset>, Vec<(&str, u16)>> = BTreeMap::new();
+        let mut blueprints: Vec<_> = ships
+            .values()
+            .filter_map(|ship| ship.blueprint().as_ref().map(|blueprint| (ship, blueprint)))
+            .collect();
+        blueprints.sort_by_key(|(ship, _)| *ship);
+        for (ship, blueprint) in blueprints {
+            for expiration_date in &blueprint.expiration_date_list {
+                by_expiry
+                    .entry(basis.expires_at(expiration_date))
+                    .or_default()
+                    .push((ship.name(), expiration_date.blueprint_num));
+            }
+        }
+
+        for (expires_at, ships) in by_expiry {
+            let total: u16 = ships.iter().map(|(_, count)| count).sum();
+            let description = ships
+                .iter()
+                .map(|(name, count)| format!("{name}\t{count}"))
+                .join("\n");
+            self.events.push(CalendarEvent {
+                uid: format!(
+                    "blueprint-expiry-{}@{UID_DOMAIN}",
+                    expires_at.format("%Y%m%d")
+                ),
+                summary: format!("{total} blueprints expire"),
+                description: Some(description),
+                start: expires_at.to_utc(),
+                end: expires_at.to_utc(),
+            });
+        }
+        self
+    }
+
+    /// A deadline for each quest with an end time
+    pub fn quests(mut self, quest_info: &QuestInfo) -> Calendar {
+        for quest in &quest_info.quest_list {
+            let Some(end_time) = quest.quest_end_time else {
+                continue;
+            };
+            self.events.push(CalendarEvent {
+                uid: format!(
+                    "quest-{}-{}@{UID_DOMAIN}",
+                    quest.quest_id,
+                    end_time.timestamp()
+                ),
+                summary: format!("Quest ends: {}", quest.quest_name),
+                description: Some(quest.quest_description.replace("**", "")),
+                start: end_time,
+                end: end_time,
+            });
+        }
+        self
+    }
+
+    /// The start of the current or upcoming event
+    pub fn event_hold(mut self, event_hold: &EventHold) -> Calendar {
+        self.events.push(CalendarEvent {
+            uid: format!("event-{}@{UID_DOMAIN}", event_hold.start_time.timestamp()),
+            summary: "Event starts".to_string(),
+            description: None,
+            start: event_hold.start_time,
+            end: event_hold.start_time,
+        });
+        self
+    }
+
+    /// The period of each Aime campaign
+    pub fn aime_campaigns(mut self, aime_campaigns: &[AimeCampaign]) -> Calendar {
+        for campaign in aime_campaigns {
+            self.events.push(CalendarEvent {
+                uid: format!(
+                    "aime-campaign-{}-{}@{UID_DOMAIN}",
+                    campaign.id,
+                    campaign.start_time.timestamp()
+                ),
+                summary: "Aime campaign".to_string(),
+                description: Some(campaign.aime_url.clone()),
+                start: campaign.start_time,
+                end: campaign.end_time,
+            });
+        }
+        self
+    }
+
+    /// The calendar in iCalendar format, with CRLF line endings.
+    pub fn to_ics(&self) -> String {
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            format!("PRODID:{PRODID}"),
+            "CALSCALE:GREGORIAN".to_string(),
+            // Japan has had no daylight saving since 1951
+            "BEGIN:VTIMEZONE".to_string(),
+            "TZID:Asia/Tokyo".to_string(),
+            "BEGIN:STANDARD".to_string(),
+            "DTSTART:19700101T000000".to_string(),
+            "TZOFFSETFROM:+0900".to_string(),
+            "TZOFFSETTO:+0900".to_string(),
+            "TZNAME:JST".to_string(),
+            "END:STANDARD".to_string(),
+            "END:VTIMEZONE".to_string(),
+        ];
+
+        let dtstamp = self.dtstamp.format("%Y%m%dT%H%M%SZ");
+        for event in self.events.iter().sorted_by_key(|event| event.start) {
+            lines.push("BEGIN:VEVENT".to_string());
+            lines.push(format!("UID:{}", escape_text(&event.uid)));
+            lines.push(format!("DTSTAMP:{dtstamp}"));
+            lines.push(format!(
+                "DTSTART;TZID=Asia/Tokyo:{}",
+                japan_time(event.start)
+            ));
+            lines.push(format!("DTEND;TZID=Asia/Tokyo:{}", japan_time(event.end)));
+            lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+            if let Some(description) = &event.description {
+                lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+            }
+            lines.push("END:VEVENT".to_string());
+        }
+        lines.push("END:VCALENDAR".to_string());
+
+        let mut ics = String::new();
+        for line in lines {
+            write!(ics, "{}\r\n", fold_line(&line)).unwrap();
+        }
+        ics
+    }
+}
+
+/// A local time in Japan, for use with TZID=Asia/Tokyo
+fn japan_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&japan())
+        .format("%Y%m%dT%H%M%S")
+        .to_string()
+}
+
+/// Escapes a TEXT value, per RFC 5545 3.3.11
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Folds a content line to at most 75 octets per line, per RFC 5545 3.1, without
+/// splitting a UTF-8 character.
+fn fold_line(line: &str) -> String {
+    const MAX_OCTETS: usize = 75;
+    let mut folded = String::new();
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_OCTETS {
+            folded.push_str("\r\n ");
+            // The leading space counts towards the continuation line.
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests;
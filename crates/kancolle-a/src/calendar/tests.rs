@@ -0,0 +1,81 @@
+use super::*;
+use crate::importer::kancolle_arcade_net::{
+    read_aime_campaign_info, read_event_hold, read_quest_info,
+};
+
+use chrono::TimeZone;
+use chrono_tz::Asia::Tokyo;
+use lazy_static_include::*;
+
+lazy_static_include_bytes! {
+    QUEST_INFO => "tests/fixtures/latest/Quest_info.json",
+    EVENT_HOLD => "tests/fixtures/latest/Event_hold.json",
+    AIME_CAMPAIGN_INFO => "tests/fixtures/latest/AimeCampaign_info.json",
+}
+
+#[test]
+fn test_escape_text() {
+    assert_eq!(escape_text("a,b;c\\d\r\ne\nf"), r"a\,b\;c\\d\ne\nf");
+}
+
+#[test]
+fn test_fold_line() {
+    let short = "SUMMARY:short";
+    assert_eq!(fold_line(short), short);
+
+    // 3 octets per character, so folds must not split them
+    let line = format!("SUMMARY:{}", "任務".repeat(30));
+    let folded = fold_line(&line);
+    for (index, part) in folded.split("\r\n").enumerate() {
+        assert!(part.len() <= 75);
+        if index > 0 {
+            assert!(part.starts_with(' '));
+        }
+    }
+    assert_eq!(folded.replace("\r\n ", ""), line);
+}
+
+#[test]
+fn test_japan_time() {
+    let time = Tokyo
+        .with_ymd_and_hms(2025, 6, 11, 23, 59, 59)
+        .unwrap()
+        .to_utc();
+    assert_eq!(japan_time(time), "20250611T235959");
+}
+
+#[test]
+fn test_calendar_to_ics() {
+    let dtstamp = Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap();
+    let calendar = Calendar::new(dtstamp)
+        .quests(&read_quest_info(QUEST_INFO.as_ref()).unwrap())
+        .event_hold(&read_event_hold(EVENT_HOLD.as_ref()).unwrap())
+        .aime_campaigns(&read_aime_campaign_info(AIME_CAMPAIGN_INFO.as_ref()).unwrap());
+
+    // One quest has no end time
+    assert_eq!(calendar.events().len(), 9 + 1 + 1);
+    assert!(calendar
+        .events()
+        .iter()
+        .map(|event| &event.uid)
+        .all_unique());
+
+    let ics = calendar.to_ics();
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), 11);
+    assert!(ics.contains("TZID:Asia/Tokyo\r\n"));
+    assert!(ics.contains("DTSTAMP:20250601T000000Z\r\n"));
+    // Event start, in Japan time
+    assert!(ics.contains("DTSTART;TZID=Asia/Tokyo:20250410T070000\r\n"));
+    // Aime campaign period
+    assert!(ics.contains("DTSTART;TZID=Asia/Tokyo:20250508T070000\r\n"));
+    assert!(ics.contains("DTEND;TZID=Asia/Tokyo:20250701T235900\r\n"));
+    // Daily quest end
+    assert!(ics.contains("DTEND;TZID=Asia/Tokyo:20250601T070000\r\n"));
+    // Markup removed
+    assert!(!ics.contains("**"));
+    for line in ics.split("\r\n") {
+        assert!(line.len() <= 75);
+    }
+}
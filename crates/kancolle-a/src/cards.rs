@@ -0,0 +1,119 @@
+//! Individual cards from the TcBook, one per variation of each page.
+//!
+//! A TcBook page lists its cards positionally in `cardImgList`: a row of Normal, Holo
+//! and Damaged (中破) cards, with an empty string for any card not owned. Original
+//! Illustration pages instead have one or two cards, with no Holo or Damaged versions.
+
+use serde::Serialize;
+use strum::Display;
+
+use crate::importer::kancolle_arcade_net::{
+    BookShipCardPage, BookShipCardPageSource, ImageKind, ImageRef,
+};
+
+/// Which card of a page, see Card::variation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Display)]
+pub enum CardVariation {
+    Normal,
+    Holo,
+    Damaged,
+    OriginalIllustration,
+}
+
+impl CardVariation {
+    /// The single-letter code used in reports, N, H, D or O.
+    pub fn code(&self) -> char {
+        match self {
+            CardVariation::Normal => 'N',
+            CardVariation::Holo => 'H',
+            CardVariation::Damaged => 'D',
+            CardVariation::OriginalIllustration => 'O',
+        }
+    }
+}
+
+/// A single card, which may or may not be owned
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Card<'a> {
+    ship_name: &'a str,
+    book_no: u16,
+    priority: u16,
+    source: BookShipCardPageSource,
+    variation: CardVariation,
+    image: Option<&'a str>,
+}
+
+impl<'a> Card<'a> {
+    /// The cards on the given TcBook page, for the given ShipMod.
+    pub(crate) fn from_page(
+        ship_name: &'a str,
+        book_no: u16,
+        source: BookShipCardPageSource,
+        card_page: &'a BookShipCardPage,
+    ) -> impl Iterator<Item = Card<'a>> + 'a {
+        use BookShipCardPageSource::*;
+        let original_illustration = match source {
+            OriginalIllustration1(..) | OriginalIllustration2(..) => true,
+            // Unidentified Original Illustration pages still have the wrong shape for a row.
+            _ => !card_page.variation_num_in_page.is_multiple_of(3),
+        };
+
+        card_page
+            .card_img_list
+            .iter()
+            .enumerate()
+            .map(move |(index, image)| Card {
+                ship_name,
+                book_no,
+                priority: card_page.priority,
+                source: source.clone(),
+                variation: match (original_illustration, index % 3) {
+                    (true, _) => CardVariation::OriginalIllustration,
+                    (false, 0) => CardVariation::Normal,
+                    (false, 1) => CardVariation::Holo,
+                    (false, _) => CardVariation::Damaged,
+                },
+                image: (!image.is_empty()).then_some(image.as_str()),
+            })
+    }
+
+    /// The full name of the ShipMod this card is for
+    pub fn ship_name(&self) -> &'a str {
+        self.ship_name
+    }
+
+    pub fn book_no(&self) -> u16 {
+        self.book_no
+    }
+
+    /// The TcBook page this card is on
+    pub fn priority(&self) -> u16 {
+        self.priority
+    }
+
+    pub fn source(&self) -> &BookShipCardPageSource {
+        &self.source
+    }
+
+    pub fn variation(&self) -> CardVariation {
+        self.variation
+    }
+
+    pub fn owned(&self) -> bool {
+        self.image.is_some()
+    }
+
+    /// The card image path relative to the TcBook resources, only known for owned cards.
+    pub fn image(&self) -> Option<&'a str> {
+        self.image
+    }
+
+    /// The card image, only known for owned cards.
+    pub fn image_ref(&self) -> Option<ImageRef> {
+        ImageRef::new(ImageKind::Card, self.image?)
+    }
+}
+
+#[cfg(test)]
+mod tests;
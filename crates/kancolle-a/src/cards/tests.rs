@@ -0,0 +1,66 @@
+use super::*;
+
+fn card_page(card_img_list: &[&str]) -> BookShipCardPage {
+    serde_json::from_value(serde_json::json!({
+        "priority": 1,
+        "cardImgList": card_img_list,
+        "statusImg": null,
+        "variationNumInPage": card_img_list.len(),
+        "acquireNumInPage": card_img_list.iter().filter(|image| !image.is_empty()).count(),
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_cards_from_row() {
+    let page = card_page(&["s/tc_1_a.jpg", "", "s/tc_1_c.jpg"]);
+    let cards: Vec<Card> =
+        Card::from_page("長門", 1, BookShipCardPageSource::Swimsuit, &page).collect();
+
+    assert_eq!(
+        cards
+            .iter()
+            .map(|card| (card.variation(), card.owned()))
+            .collect::<Vec<_>>(),
+        vec![
+            (CardVariation::Normal, true),
+            (CardVariation::Holo, false),
+            (CardVariation::Damaged, true),
+        ]
+    );
+    assert_eq!(cards[0].image(), Some("s/tc_1_a.jpg"));
+    assert_eq!(cards[1].image(), None);
+    assert!(cards.iter().all(|card| card.priority() == 1
+        && card.book_no() == 1
+        && card.ship_name() == "長門"
+        && card.source() == &BookShipCardPageSource::Swimsuit));
+}
+
+#[test]
+fn test_cards_from_original_illustration() {
+    let page = card_page(&["", "s/tc_205_b.jpg"]);
+    let cards: Vec<Card> = Card::from_page(
+        "春雨",
+        205,
+        BookShipCardPageSource::OriginalIllustration2(false, true),
+        &page,
+    )
+    .collect();
+    assert_eq!(
+        cards
+            .iter()
+            .map(|card| (card.variation(), card.owned()))
+            .collect::<Vec<_>>(),
+        vec![
+            (CardVariation::OriginalIllustration, false),
+            (CardVariation::OriginalIllustration, true),
+        ]
+    );
+
+    // Unidentified, but the wrong shape for a row
+    let page = card_page(&[""]);
+    let cards: Vec<Card> =
+        Card::from_page("早霜", 209, BookShipCardPageSource::Unknown, &page).collect();
+    assert_eq!(cards.len(), 1);
+    assert_eq!(cards[0].variation(), CardVariation::OriginalIllustration);
+}
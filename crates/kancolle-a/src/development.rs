@@ -0,0 +1,172 @@
+//! Planning equipment development (装備開発) across the fleet.
+//!
+//! Each Character in the Character List carries its development plans: the equipment
+//! it can develop, the ship level required, what each development costs in strategy
+//! points and material medals, and how many of its developments have been done.
+
+use serde::Serialize;
+use std::ops::Add;
+
+use crate::importer::kancolle_arcade_net::{
+    BookEquipment, Character, DevelopEquipment, PersonalBasicInfo,
+};
+use crate::ships::{ShipMod, Ships};
+
+/// Strategy points and material medals, as spent on development
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DevelopmentCost {
+    pub strategy_points: u32,
+    pub material_medals: u32,
+}
+
+impl DevelopmentCost {
+    /// What the admiral currently holds
+    pub fn held(basic_info: &PersonalBasicInfo) -> DevelopmentCost {
+        DevelopmentCost {
+            strategy_points: basic_info.strategy_point,
+            material_medals: basic_info.material_medal,
+        }
+    }
+
+    /// How much more than the given holdings this costs, zero where we have enough.
+    pub fn shortfall(&self, held: &DevelopmentCost) -> DevelopmentCost {
+        DevelopmentCost {
+            strategy_points: self.strategy_points.saturating_sub(held.strategy_points),
+            material_medals: self.material_medals.saturating_sub(held.material_medals),
+        }
+    }
+
+    pub fn affordable(&self, held: &DevelopmentCost) -> bool {
+        self.shortfall(held) == DevelopmentCost::default()
+    }
+}
+
+impl Add for DevelopmentCost {
+    type Output = DevelopmentCost;
+
+    fn add(self, other: DevelopmentCost) -> DevelopmentCost {
+        DevelopmentCost {
+            strategy_points: self.strategy_points + other.strategy_points,
+            material_medals: self.material_medals + other.material_medals,
+        }
+    }
+}
+
+/// Equipment a ShipMod can still develop
+#[derive(Debug, Clone, Copy)]
+pub struct Development<'a> {
+    shipmod: &'a ShipMod,
+    character: &'a Character,
+    equipment: &'a DevelopEquipment,
+}
+
+impl<'a> Development<'a> {
+    pub fn shipmod(&self) -> &'a ShipMod {
+        self.shipmod
+    }
+
+    pub fn equipment(&self) -> &'a DevelopEquipment {
+        self.equipment
+    }
+
+    /// The EquipBook entry for the equipment, for its name and kind
+    pub fn book_equipment(&self, equip_book: &'a [BookEquipment]) -> Option<&'a BookEquipment> {
+        self.equipment.book_equipment(equip_book)
+    }
+
+    /// True if the ship is at or above the required level
+    pub fn level_met(&self) -> bool {
+        self.character.lv >= self.equipment.require_lv
+    }
+
+    /// How many more times this equipment can be developed
+    pub fn remaining(&self) -> u16 {
+        self.equipment
+            .max_develop_count
+            .saturating_sub(self.equipment.develop_count)
+    }
+
+    /// The cost of one development
+    pub fn cost_each(&self) -> DevelopmentCost {
+        DevelopmentCost {
+            strategy_points: self.equipment.require_strategy_point as u32,
+            material_medals: self.equipment.require_material_medal as u32,
+        }
+    }
+
+    /// The cost of all the remaining developments
+    pub fn cost(&self) -> DevelopmentCost {
+        let each = self.cost_each();
+        let remaining = self.remaining() as u32;
+        DevelopmentCost {
+            strategy_points: each.strategy_points * remaining,
+            material_medals: each.material_medals * remaining,
+        }
+    }
+}
+
+/// Every development still available across the fleet, in the game's order.
+pub struct DevelopmentPlan<'a> {
+    developments: Vec<Development<'a>>,
+}
+
+impl<'a> DevelopmentPlan<'a> {
+    /// Requires Character data, otherwise the plan will be empty.
+    pub fn new(ships: &'a Ships) -> DevelopmentPlan<'a> {
+        let mut shipmods: Vec<&ShipMod> = ships.shipmod_iter().collect();
+        shipmods.sort();
+
+        let developments = shipmods
+            .into_iter()
+            .filter_map(|shipmod| {
+                shipmod
+                    .character()
+                    .as_ref()
+                    .map(|character| (shipmod, character))
+            })
+            .flat_map(|(shipmod, character)| {
+                let mut equipment: Vec<&DevelopEquipment> =
+                    character.develop_equipment_list.iter().collect();
+                equipment.sort_by_key(|equipment| (equipment.plan_kind, equipment.sort_index));
+                equipment.into_iter().map(move |equipment| Development {
+                    shipmod,
+                    character,
+                    equipment,
+                })
+            })
+            .filter(|development| development.remaining() > 0)
+            .collect();
+
+        DevelopmentPlan { developments }
+    }
+
+    pub fn developments(&self) -> &[Development<'a>] {
+        &self.developments
+    }
+
+    /// The developments whose ship meets the level requirement
+    pub fn available(&self) -> impl Iterator<Item = &Development<'a>> + '_ {
+        self.developments
+            .iter()
+            .filter(|development| development.level_met())
+    }
+
+    /// The cost of every remaining development
+    pub fn total_cost(&self) -> DevelopmentCost {
+        self.developments
+            .iter()
+            .map(|development| development.cost())
+            .fold(DevelopmentCost::default(), Add::add)
+    }
+
+    /// The cost of the remaining developments whose ship meets the level requirement
+    pub fn available_cost(&self) -> DevelopmentCost {
+        self.available()
+            .map(|development| development.cost())
+            .fold(DevelopmentCost::default(), Add::add)
+    }
+}
+
+#[cfg(test)]
+mod tests;
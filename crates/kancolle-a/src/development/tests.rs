@@ -0,0 +1,31 @@
+use super::*;
+
+#[test]
+fn test_development_cost_shortfall() {
+    let cost = DevelopmentCost {
+        strategy_points: 1200,
+        material_medals: 3,
+    } + DevelopmentCost {
+        strategy_points: 300,
+        material_medals: 0,
+    };
+    assert_eq!(cost.strategy_points, 1500);
+    assert_eq!(cost.material_medals, 3);
+
+    let held = DevelopmentCost {
+        strategy_points: 2000,
+        material_medals: 1,
+    };
+    assert_eq!(
+        cost.shortfall(&held),
+        DevelopmentCost {
+            strategy_points: 0,
+            material_medals: 2,
+        }
+    );
+    assert!(!cost.affordable(&held));
+    assert!(cost.affordable(&DevelopmentCost {
+        strategy_points: 1500,
+        material_medals: 3,
+    }));
+}
@@ -1,7 +1,9 @@
 //! Error and failure handing types
 
+use strum::Display;
 use thiserror::Error;
 
+use crate::importer::kancolle_arcade_net::{ImageKind, UnknownField, UnknownKind};
 use csv::Error as CSVError;
 use reqwest::header::InvalidHeaderValue;
 use reqwest::Error as ReqwestError;
@@ -17,6 +19,33 @@ pub enum Error {
     #[error("Authentication failed, login_code {0}")]
     AuthenticationFailed(String),
 
+    // Data inconsistencies found while assembling ships::Ships
+    #[error("Duplicate {data_source} entry for {ship_name}")]
+    DuplicateEntry {
+        ship_name: String,
+        data_source: DataSource,
+    },
+    #[error("Inconsistent {data_source} entry for {ship_name}: {reason}")]
+    InconsistentData {
+        ship_name: String,
+        data_source: DataSource,
+        reason: String,
+    },
+    #[error("Inconsistent ship {ship_name}: {reason}")]
+    InconsistentShip { ship_name: String, reason: String },
+    #[error("Unknown remodel suffix {suffix} for {ship_name}")]
+    UnknownRemodel { ship_name: String, suffix: String },
+    #[error("Duplicate entry for book number {book_no} in {data}")]
+    DuplicateBookNo { book_no: u16, data: String },
+    #[error("Unsupported {data} version {version}")]
+    UnsupportedVersion { data: String, version: u32 },
+    #[error("Snapshot {0} already exists")]
+    SnapshotExists(String),
+    #[error("No file name to store {0} data under")]
+    NoFileName(String),
+    #[error("Unknown location for {0} images")]
+    UnknownImageLocation(ImageKind),
+
     // Passthroughs from other libraries
     #[error(transparent)]
     ParseError(#[from] ParseError),
@@ -28,4 +57,46 @@ pub enum Error {
     SerdeJsonError(#[from] SerdeJsonError),
     #[error(transparent)]
     CSVError(#[from] CSVError),
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+}
+
+/// Problems found in the source data which are not severe enough to fail on.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum Warning {
+    #[error("Unknown field in {data_source} data: {field}")]
+    UnknownField {
+        data_source: DataSource,
+        field: UnknownField,
+    },
+    #[error("Unknown value in {data_source} data: {field}")]
+    UnknownValue {
+        data_source: DataSource,
+        field: UnknownField,
+    },
+}
+
+impl Warning {
+    /// The warning for an UnknownField, according to its kind
+    pub fn unknown(data_source: DataSource, field: UnknownField) -> Warning {
+        match field.kind {
+            UnknownKind::Field => Warning::UnknownField { data_source, field },
+            UnknownKind::Value => Warning::UnknownValue { data_source, field },
+        }
+    }
+}
+
+/// The data sources that are combined into a ships::Ships, for error reporting.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq)]
+pub enum DataSource {
+    /// https://wikiwiki.jp/kancolle-a/ ship tables
+    Wiki,
+    /// https://kancolle-a.sega.jp/players/kekkonkakkokari/kanmusu_list.json
+    Kekkon,
+    /// https://kancolle-arcade.net/ac/api/CharacterList/info
+    Character,
+    /// https://kancolle-arcade.net/ac/api/TcBook/info
+    Book,
+    /// https://kancolle-arcade.net/ac/api/BlueprintList/info
+    Blueprint,
 }
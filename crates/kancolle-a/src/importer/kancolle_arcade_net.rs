@@ -16,8 +16,46 @@ mod place;
 pub use place::districts::*;
 pub use place::places::*;
 
+mod equip;
+pub use equip::book::*;
+pub use equip::list::*;
+
+mod personal;
+pub use personal::basic_info::*;
+
+mod quest;
+pub use quest::info::*;
+
+mod event;
+pub use event::hold::*;
+pub use event::info::*;
+
+mod area;
+pub use area::capture_info::*;
+
+mod aime_campaign;
+pub use aime_campaign::info::*;
+
 mod kekkonkakkokari;
 pub use kekkonkakkokari::kanmusu_list::*;
 
 mod api_client;
 pub use api_client::*;
+
+mod images;
+pub use images::*;
+
+// wasm32 has no filesystem to cache images in
+#[cfg(not(target_arch = "wasm32"))]
+pub mod image_cache;
+
+// wasm32 has no filesystem to store snapshots in
+#[cfg(not(target_arch = "wasm32"))]
+pub mod snapshot;
+
+mod unknown_fields;
+pub use unknown_fields::{UnknownField, UnknownKind};
+
+pub mod card_page_inference;
+
+pub mod schema_check;
@@ -0,0 +1,56 @@
+/// Module for importer for https://kancolle-arcade.net/ac/api/AimeCampaign/info
+pub mod info {
+    use super::super::unknown_fields::{self, UnknownField, UnknownFields};
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Map, Result, Value};
+    use std::io::Read;
+
+    pub type AimeCampaignInfo = Vec<AimeCampaign>;
+
+    /// Parses an AimeCampaignInfo from the provided JSON reader.
+    /// Fails if not given a JSON array, or expected data structure does not match.
+    pub fn read_aime_campaign_info(reader: impl Read) -> Result<AimeCampaignInfo> {
+        unknown_fields::read_strict(reader)
+    }
+
+    /// Parses an AimeCampaignInfo from the provided JSON reader, also reporting any unknown fields.
+    /// Fails if not given a JSON array, or expected data structure does not match.
+    pub fn read_aime_campaign_info_lenient(
+        reader: impl Read,
+    ) -> Result<(AimeCampaignInfo, Vec<UnknownField>)> {
+        unknown_fields::read_lenient(reader)
+    }
+
+    /// A campaign run through Aime, entered by playing
+    #[derive(Debug, Deserialize, Serialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct AimeCampaign {
+        pub id: u32,
+        pub order: u16,
+        #[serde(with = "chrono::serde::ts_milliseconds")]
+        pub start_time: DateTime<Utc>,
+        #[serde(with = "chrono::serde::ts_milliseconds")]
+        pub end_time: DateTime<Utc>,
+        pub has_entered: bool,
+        pub entry_num: u16,
+        /// Credits needed per entry
+        pub need_credit: u16,
+        /// The campaign page on my-aime.net
+        pub aime_url: String,
+        /// e.g. OPEN
+        pub status: String,
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    impl UnknownFields for AimeCampaign {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
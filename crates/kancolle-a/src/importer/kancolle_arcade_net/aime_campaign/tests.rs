@@ -0,0 +1,38 @@
+use super::info::*;
+
+use chrono::TimeZone;
+use chrono_tz::Asia::Tokyo;
+use lazy_static_include::*;
+
+// https://kancolle-arcade.net/ac/api/AimeCampaign/info
+lazy_static_include_bytes! {
+    AIME_CAMPAIGN_INFO_LATEST => "tests/fixtures/latest/AimeCampaign_info.json",
+}
+
+#[test]
+fn parse_empty_aime_campaign_info_reader() {
+    read_aime_campaign_info(std::io::empty()).unwrap_err();
+}
+
+#[test]
+fn parse_fixture_aime_campaign_info_latest() {
+    let aime_campaign_info = read_aime_campaign_info(AIME_CAMPAIGN_INFO_LATEST.as_ref()).unwrap();
+    assert_eq!(aime_campaign_info.len(), 1);
+    let campaign = &aime_campaign_info[0];
+    assert_eq!(
+        campaign.start_time,
+        Tokyo
+            .with_ymd_and_hms(2025, 5, 8, 7, 0, 0)
+            .unwrap()
+            .to_utc()
+    );
+    assert_eq!(
+        campaign.end_time,
+        Tokyo
+            .with_ymd_and_hms(2025, 7, 1, 23, 59, 0)
+            .unwrap()
+            .to_utc()
+    );
+    assert!(campaign.has_entered);
+    assert_eq!(campaign.status, "OPEN");
+}
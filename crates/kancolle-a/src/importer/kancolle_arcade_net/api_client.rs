@@ -2,9 +2,10 @@
 //! https://kancolle-a.sega.jp/players/kekkonkakkokari/kanmusu_list.json
 
 use crate::{Error, Result};
+use futures_util::stream::{self, StreamExt};
 use reqwest::{
     header::{HeaderMap, USER_AGENT},
-    StatusCode,
+    Response, StatusCode,
 };
 use reqwest::{Client as ReqwestClient, ClientBuilder as ReqwestBuilder};
 use serde::{Deserialize, Serialize};
@@ -15,9 +16,14 @@ mod cookie_helper;
 
 const API_BASE: &str = "https://kancolle-arcade.net/ac/api/";
 
+/// The default limit on requests in flight at once, e.g. during fetch_snapshot.
+pub const MAX_CONCURRENT_REQUESTS: usize = 6;
+
 pub struct ClientBuilder {
     jsessionid: Option<String>,
     userpass: Option<(String, String)>,
+    api_base: String,
+    max_concurrent_requests: usize,
 }
 
 impl ClientBuilder {
@@ -25,6 +31,8 @@ impl ClientBuilder {
         ClientBuilder {
             jsessionid: None,
             userpass: None,
+            api_base: API_BASE.to_string(),
+            max_concurrent_requests: MAX_CONCURRENT_REQUESTS,
         }
     }
 
@@ -35,11 +43,15 @@ impl ClientBuilder {
         headers.insert("X-Requested-With", "XMLHttpRequest".parse()?);
         reqwest_builder = reqwest_builder.default_headers(headers);
 
-        reqwest_builder = cookie_helper::setup_cookies(self.jsessionid, reqwest_builder)?;
+        let (reqwest_builder, cookies) =
+            cookie_helper::setup_cookies(self.jsessionid, &self.api_base, reqwest_builder)?;
 
         Ok(Client {
             client: reqwest_builder.build()?,
+            cookies,
             userpass: self.userpass,
+            api_base: self.api_base,
+            max_concurrent_requests: self.max_concurrent_requests,
         })
     }
 
@@ -52,6 +64,22 @@ impl ClientBuilder {
         self.userpass = Some((username, password));
         self
     }
+
+    /// Use the given base URL instead of API_BASE, e.g. a local server for testing.
+    /// KanmusuList is not under API_BASE, and is unaffected.
+    pub fn api_base(mut self, api_base: String) -> ClientBuilder {
+        self.api_base = match api_base.ends_with('/') {
+            true => api_base,
+            false => api_base + "/",
+        };
+        self
+    }
+
+    /// Limit the requests in flight at once, default MAX_CONCURRENT_REQUESTS.
+    pub fn max_concurrent_requests(mut self, max_concurrent_requests: usize) -> ClientBuilder {
+        self.max_concurrent_requests = max_concurrent_requests.max(1);
+        self
+    }
 }
 
 impl Default for ClientBuilder {
@@ -60,6 +88,7 @@ impl Default for ClientBuilder {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ApiEndpoint {
     // Global data: no authentication needed, unaffected by auth status
     KanmusuList,
@@ -119,7 +148,72 @@ pub enum ApiEndpoint {
     Other(String),
 }
 
-fn url_for_endpoint(endpoint: &ApiEndpoint) -> String {
+impl ApiEndpoint {
+    /// The file name for this endpoint's data, as used for test fixtures and snapshots.
+    /// None for AuthLogin, which has no data to store, and for an Other path with nothing
+    /// left to name it after sanitizing.
+    pub fn file_name(&self) -> Option<String> {
+        use ApiEndpoint::*;
+        let file_name = match self {
+            KanmusuList => "kanmusu_list.json",
+
+            AimeCampaignHold => "AimeCampaign_hold.json",
+            AimeCampaignInfo => "AimeCampaign_info.json",
+            AreaCaptureInfo => "Area_captureInfo.json",
+            BlueprintListInfo => "BlueprintList_info.json",
+            CampaignHistory => "Capmpaign_history.json",
+            CampaignInfo => "Campaign_info.json",
+            CampaignPresent => "Campaign_present.json",
+            CharacterListInfo => "CharacterList_info.json",
+            CopCheckreward => "Cop_checkreward.json",
+            CopHold => "Cop_hold.json",
+            CopInfo => "Cop_info.json",
+            EpFesHold => "EpFes_hold.json",
+            EpFesProgress => "EpFes_progress.json",
+            EquipBookInfo => "EquipBook_info.json",
+            EquipListInfo => "EquipList_info.json",
+            EventHold => "Event_hold.json",
+            EventInfo => "Event_info.json",
+            ExerciseInfo => "Exercise_info.json",
+            NCampInfo => "NCamp_info.json",
+            PersonalBasicInfo => "Personal_basicInfo.json",
+            PlaceDistricts => "Place_districts.json",
+            PlacePlaces => "Place_places.json",
+            QuestInfo => "Quest_info.json",
+            RankingMonthlyCurrent => "Ranking_monthly_current.json",
+            RankingMonthlyPrev => "Ranking_monthly_prev.json",
+            RankingTotal => "Ranking_total.json",
+            RoomItemListInfo => "RoomItemList_info.json",
+            TcBookInfo => "TcBook_info.json",
+            TcErrorDispFlag => "TcError_dispFlag.json",
+
+            AuthLogin => return None,
+
+            Other(path) => return other_file_name(path),
+        };
+        Some(file_name.to_string())
+    }
+}
+
+/// The file name for an Other endpoint's path, named like the known endpoints, e.g.
+/// `Ranking/monthly/current` gives `Ranking_monthly_current.json`. Anything but ASCII
+/// letters, digits and `-` becomes `_`, so the name cannot leave the directory it is in.
+fn other_file_name(path: &str) -> Option<String> {
+    let name: String = path
+        .trim_matches('/')
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() || c == '-' {
+            true => c,
+            false => '_',
+        })
+        .collect();
+    match name.trim_matches('_').is_empty() {
+        true => None,
+        false => Some(format!("{name}.json")),
+    }
+}
+
+fn url_for_endpoint(api_base: &str, endpoint: &ApiEndpoint) -> String {
     // TODO: When these have some value... Currenty just empty JSON arrays.
     // * https://kancolle-arcade.net/ac/resources/place/exclude.json
     // * https://kancolle-arcade.net/ac/resources/place/verified.json
@@ -129,38 +223,38 @@ fn url_for_endpoint(endpoint: &ApiEndpoint) -> String {
             "https://kancolle-a.sega.jp/players/kekkonkakkokari/kanmusu_list.json".to_string()
         }
 
-        AimeCampaignHold => format!("{API_BASE}AimeCampaign/hold"),
-        AimeCampaignInfo => format!("{API_BASE}AimeCampaign/info"),
-        AreaCaptureInfo => format!("{API_BASE}Area/captureInfo"),
-        AuthLogin => format!("{API_BASE}Auth/login"),
-        BlueprintListInfo => format!("{API_BASE}BlueprintList/info"),
-        CampaignHistory => format!("{API_BASE}Campaign/history"),
-        CampaignInfo => format!("{API_BASE}Campaign/info"),
-        CampaignPresent => format!("{API_BASE}Campaign/present"),
-        CharacterListInfo => format!("{API_BASE}CharacterList/info"),
-        CopCheckreward => format!("{API_BASE}Cop/checkreward"),
-        CopHold => format!("{API_BASE}Cop/hold"),
-        CopInfo => format!("{API_BASE}Cop/info"),
-        EpFesHold => format!("{API_BASE}EpFes/hold"),
-        EpFesProgress => format!("{API_BASE}EpFes/progress"),
-        EquipBookInfo => format!("{API_BASE}EquipBook/info"),
-        EquipListInfo => format!("{API_BASE}EquipList/info"),
-        EventHold => format!("{API_BASE}Event/hold"),
-        EventInfo => format!("{API_BASE}Event/info"),
-        ExerciseInfo => format!("{API_BASE}Exercise/info"),
-        NCampInfo => format!("{API_BASE}NCamp/info"),
-        PersonalBasicInfo => format!("{API_BASE}Personal/basicInfo"),
-        PlaceDistricts => format!("{API_BASE}Place/districts"),
-        PlacePlaces => format!("{API_BASE}Place/places"),
-        QuestInfo => format!("{API_BASE}Quest/info"),
-        RankingMonthlyCurrent => format!("{API_BASE}Ranking/monthly/current"),
-        RankingMonthlyPrev => format!("{API_BASE}Ranking/monthly/prev"),
-        RankingTotal => format!("{API_BASE}Ranking/total"),
-        RoomItemListInfo => format!("{API_BASE}RoomItemList/info"),
-        TcBookInfo => format!("{API_BASE}TcBook/info"),
-        TcErrorDispFlag => format!("{API_BASE}TcError/dispFlag"),
-
-        Other(raw_path) => format!("{API_BASE}{raw_path}"),
+        AimeCampaignHold => format!("{api_base}AimeCampaign/hold"),
+        AimeCampaignInfo => format!("{api_base}AimeCampaign/info"),
+        AreaCaptureInfo => format!("{api_base}Area/captureInfo"),
+        AuthLogin => format!("{api_base}Auth/login"),
+        BlueprintListInfo => format!("{api_base}BlueprintList/info"),
+        CampaignHistory => format!("{api_base}Campaign/history"),
+        CampaignInfo => format!("{api_base}Campaign/info"),
+        CampaignPresent => format!("{api_base}Campaign/present"),
+        CharacterListInfo => format!("{api_base}CharacterList/info"),
+        CopCheckreward => format!("{api_base}Cop/checkreward"),
+        CopHold => format!("{api_base}Cop/hold"),
+        CopInfo => format!("{api_base}Cop/info"),
+        EpFesHold => format!("{api_base}EpFes/hold"),
+        EpFesProgress => format!("{api_base}EpFes/progress"),
+        EquipBookInfo => format!("{api_base}EquipBook/info"),
+        EquipListInfo => format!("{api_base}EquipList/info"),
+        EventHold => format!("{api_base}Event/hold"),
+        EventInfo => format!("{api_base}Event/info"),
+        ExerciseInfo => format!("{api_base}Exercise/info"),
+        NCampInfo => format!("{api_base}NCamp/info"),
+        PersonalBasicInfo => format!("{api_base}Personal/basicInfo"),
+        PlaceDistricts => format!("{api_base}Place/districts"),
+        PlacePlaces => format!("{api_base}Place/places"),
+        QuestInfo => format!("{api_base}Quest/info"),
+        RankingMonthlyCurrent => format!("{api_base}Ranking/monthly/current"),
+        RankingMonthlyPrev => format!("{api_base}Ranking/monthly/prev"),
+        RankingTotal => format!("{api_base}Ranking/total"),
+        RoomItemListInfo => format!("{api_base}RoomItemList/info"),
+        TcBookInfo => format!("{api_base}TcBook/info"),
+        TcErrorDispFlag => format!("{api_base}TcError/dispFlag"),
+
+        Other(raw_path) => format!("{api_base}{raw_path}"),
     }
 }
 
@@ -199,59 +293,122 @@ struct AuthLoginResponse {
     _hash_auth_key: Option<String>,
 }
 
-#[derive(Default)]
 pub struct Client {
     client: ReqwestClient,
+    cookies: cookie_helper::SessionCookies,
     userpass: Option<(String, String)>,
+    api_base: String,
+    max_concurrent_requests: usize,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Client {
+            client: ReqwestClient::default(),
+            cookies: Default::default(),
+            userpass: None,
+            api_base: API_BASE.to_string(),
+            max_concurrent_requests: MAX_CONCURRENT_REQUESTS,
+        }
+    }
 }
 
 impl Client {
     pub async fn fetch(&self, endpoint: &ApiEndpoint) -> Result<Box<dyn Read>> {
+        self.fetch_snapshot(std::slice::from_ref(endpoint))
+            .await
+            .pop()
+            .expect("fetch_snapshot returns one result per endpoint")
+    }
+
+    /// Fetch all the given endpoints concurrently, as a single snapshot of the data.
+    /// At most max_concurrent_requests are in flight at once.
+    /// Returns a result for each endpoint, in the same order as `endpoints`.
+    pub async fn fetch_snapshot(&self, endpoints: &[ApiEndpoint]) -> Vec<Result<Box<dyn Read>>> {
         // TODO: Push the async higher, and return an AsyncReader here, so we don't have to
         // pull the whole response down.
-        let mut response = self
-            .client
-            .get(url_for_endpoint(endpoint))
-            .send()
-            .await?
-            .error_for_status();
-        if let Err(error) = &response {
-            if let Some(status) = error.status() {
-                if status == StatusCode::FORBIDDEN && self.userpass.is_some() {
-                    self.authenticate(
-                        self.userpass.as_ref().unwrap().0.as_str(),
-                        self.userpass.as_ref().unwrap().1.as_str(),
-                    )
-                    .await?;
-
-                    response = self
-                        .client
-                        .get(url_for_endpoint(endpoint))
-                        .send()
-                        .await?
-                        .error_for_status();
+        let mut responses = self.get_texts(endpoints.iter()).await;
+
+        // Without a (current) session, every authenticated endpoint fails together. Log in once
+        // and then retry them together, rather than racing a separate login for each endpoint.
+        if let Some((username, password)) = self.userpass.as_ref() {
+            let forbidden: Vec<usize> = responses
+                .iter()
+                .enumerate()
+                .filter(|(_, response)| is_forbidden(response))
+                .map(|(index, _)| index)
+                .collect();
+            if let Some(&first_forbidden) = forbidden.first() {
+                match self
+                    .authenticate(username.as_str(), password.as_str())
+                    .await
+                {
+                    Ok(()) => {
+                        let retries = self
+                            .get_texts(forbidden.iter().map(|&index| &endpoints[index]))
+                            .await;
+                        for (index, response) in forbidden.into_iter().zip(retries) {
+                            responses[index] = response;
+                        }
+                    }
+                    // The other forbidden endpoints keep their original error.
+                    Err(error) => responses[first_forbidden] = Err(error),
                 }
             }
         }
 
-        let body_text = response?.text().await?;
-        Ok(Box::new(VecDeque::from(body_text.into_bytes())))
+        responses
+            .into_iter()
+            .map(|response| {
+                response.map(|body_text| -> Box<dyn Read> {
+                    Box::new(VecDeque::from(body_text.into_bytes()))
+                })
+            })
+            .collect()
+    }
+
+    /// get_text for each endpoint, bounded by max_concurrent_requests, in the given order.
+    async fn get_texts<'a>(
+        &self,
+        endpoints: impl Iterator<Item = &'a ApiEndpoint>,
+    ) -> Vec<Result<String>> {
+        stream::iter(endpoints)
+            .map(|endpoint| self.get_text(endpoint))
+            .buffered(self.max_concurrent_requests)
+            .collect()
+            .await
+    }
+
+    async fn get_text(&self, endpoint: &ApiEndpoint) -> Result<String> {
+        Ok(self.get(endpoint).await?.error_for_status()?.text().await?)
+    }
+
+    async fn get(&self, endpoint: &ApiEndpoint) -> Result<Response> {
+        let response = self
+            .cookies
+            .prepare(self.client.get(url_for_endpoint(&self.api_base, endpoint)))
+            .send()
+            .await?;
+        self.cookies.update(&response);
+        Ok(response)
     }
 
     async fn authenticate(&self, id: &str, password: &str) -> Result<()> {
         let body = AuthLoginRequest { id, password };
 
-        let body_response = self
-            .client
-            .post(url_for_endpoint(&ApiEndpoint::AuthLogin))
+        let response = self
+            .cookies
+            .prepare(self.client.post(url_for_endpoint(
+                &self.api_base,
+                &ApiEndpoint::AuthLogin,
+            )))
             // Some kind of user-agent sniffing going on, without this, _success_ produces a 500 error.
             .header(USER_AGENT, "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/129.0.0.0 Safari/537.36")
             .json(&body)
             .send()
-            .await?
-            .error_for_status()?
-            .text()
             .await?;
+        self.cookies.update(&response);
+        let body_response = response.error_for_status()?.text().await?;
 
         let auth_login_response: AuthLoginResponse = serde_json::from_str(&body_response)?;
 
@@ -262,3 +419,7 @@ impl Client {
         }
     }
 }
+
+fn is_forbidden<T>(response: &Result<T>) -> bool {
+    matches!(response, Err(Error::ReqwestError(error)) if error.status() == Some(StatusCode::FORBIDDEN))
+}
@@ -4,22 +4,37 @@
 use crate::Result;
 
 use reqwest::cookie::Jar;
-use reqwest::ClientBuilder as ReqwestBuilder;
+use reqwest::{ClientBuilder as ReqwestBuilder, RequestBuilder, Response};
 use std::sync::Arc;
 use url::Url;
 
 pub(super) fn setup_cookies(
     jsessionid: Option<String>,
+    api_base: &str,
     builder: ReqwestBuilder,
-) -> Result<ReqwestBuilder> {
-    Ok(if let Some(jsessionid) = jsessionid {
+) -> Result<(ReqwestBuilder, SessionCookies)> {
+    let builder = if let Some(jsessionid) = jsessionid {
         let cookies = Jar::default();
         cookies.add_cookie_str(
             &format!("JSESSIONID={}; Path=/; HttpOnly", jsessionid),
-            &super::API_BASE.parse::<Url>()?,
+            &api_base.parse::<Url>()?,
         );
         builder.cookie_provider(Arc::new(cookies))
     } else {
         builder.cookie_store(true)
-    })
+    };
+    Ok((builder, SessionCookies))
+}
+
+/// Per-request session cookie handling.
+/// Nothing to do here, as reqwest's cookie store does all the work.
+#[derive(Debug, Default)]
+pub(super) struct SessionCookies;
+
+impl SessionCookies {
+    pub(super) fn prepare(&self, request: RequestBuilder) -> RequestBuilder {
+        request
+    }
+
+    pub(super) fn update(&self, _response: &Response) {}
 }
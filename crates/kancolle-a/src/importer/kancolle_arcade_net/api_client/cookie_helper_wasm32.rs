@@ -1,15 +1,64 @@
 //! Helpers for configuring cookie support in wasm32 builds
 //! Necessary because reqwest's cookie support is excluded in wasm32 builds.
+//!
+//! In the browser, the Fetch API owns the cookies: we ask it to include credentials,
+//! and it will send and store the session cookie itself. The browser will not let us
+//! set or read the `Cookie` and `Set-Cookie` headers ourselves, but non-browser hosts
+//! like Node.js will, so we also track the JSESSIONID cookie by hand for them.
 
 use crate::Result;
 
-use reqwest::ClientBuilder as ReqwestBuilder;
+use reqwest::header::{COOKIE, SET_COOKIE};
+use reqwest::{ClientBuilder as ReqwestBuilder, RequestBuilder, Response};
+use std::sync::Mutex;
 
 pub(super) fn setup_cookies(
     jsessionid: Option<String>,
+    _api_base: &str,
     builder: ReqwestBuilder,
-) -> Result<ReqwestBuilder> {
-    // TODO: wasm-cookies-rs could be used in the browser
-    assert!(jsessionid.is_none());
-    Ok(builder)
+) -> Result<(ReqwestBuilder, SessionCookies)> {
+    Ok((
+        builder,
+        SessionCookies {
+            jsessionid: Mutex::new(jsessionid),
+        },
+    ))
 }
+
+/// Per-request session cookie handling.
+#[derive(Debug, Default)]
+pub(super) struct SessionCookies {
+    jsessionid: Mutex<Option<String>>,
+}
+
+impl SessionCookies {
+    pub(super) fn prepare(&self, request: RequestBuilder) -> RequestBuilder {
+        let request = request.fetch_credentials_include();
+        match self.jsessionid.lock().unwrap().as_ref() {
+            Some(jsessionid) => request.header(COOKIE, format!("JSESSIONID={jsessionid}")),
+            None => request,
+        }
+    }
+
+    pub(super) fn update(&self, response: &Response) {
+        let jsessionid = response
+            .headers()
+            .get_all(SET_COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .find_map(jsessionid_from_set_cookie);
+        if let Some(jsessionid) = jsessionid {
+            *self.jsessionid.lock().unwrap() = Some(jsessionid.to_string());
+        }
+    }
+}
+
+/// Extract the JSESSIONID value from a Set-Cookie header value, if that's what it sets.
+fn jsessionid_from_set_cookie(set_cookie: &str) -> Option<&str> {
+    let (name, value) = set_cookie.split(';').next()?.split_once('=')?;
+    (name.trim() == "JSESSIONID").then(|| value.trim())
+}
+
+#[cfg(test)]
+#[path = "cookie_helper_wasm32/tests.rs"]
+mod tests;
@@ -0,0 +1,45 @@
+use super::*;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn test_jsessionid_from_set_cookie() {
+    assert_eq!(
+        jsessionid_from_set_cookie("JSESSIONID=ABC123; Path=/ac; Secure; HttpOnly"),
+        Some("ABC123")
+    );
+    assert_eq!(
+        jsessionid_from_set_cookie(" JSESSIONID = ABC123 "),
+        Some("ABC123")
+    );
+    assert_eq!(jsessionid_from_set_cookie("JSESSIONID="), Some(""));
+    assert_eq!(jsessionid_from_set_cookie("OTHER=ABC123; Path=/"), None);
+    assert_eq!(
+        jsessionid_from_set_cookie("Path=/; JSESSIONID=ABC123"),
+        None
+    );
+    assert_eq!(jsessionid_from_set_cookie("JSESSIONID"), None);
+}
+
+fn cookie_header(cookies: &SessionCookies) -> Option<String> {
+    let request = cookies
+        .prepare(reqwest::Client::new().get("https://kancolle-arcade.net/ac/api/TcBook/info"))
+        .build()
+        .unwrap();
+    request
+        .headers()
+        .get(COOKIE)
+        .map(|value| value.to_str().unwrap().to_string())
+}
+
+#[wasm_bindgen_test]
+fn test_session_cookies_prepare() {
+    let (_, cookies) = setup_cookies(None, "", reqwest::Client::builder()).unwrap();
+    assert_eq!(cookie_header(&cookies), None);
+
+    let (_, cookies) =
+        setup_cookies(Some("ABC123".to_string()), "", reqwest::Client::builder()).unwrap();
+    assert_eq!(
+        cookie_header(&cookies).as_deref(),
+        Some("JSESSIONID=ABC123")
+    );
+}
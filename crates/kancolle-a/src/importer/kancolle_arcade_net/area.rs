@@ -0,0 +1,107 @@
+/// Module for importer for https://kancolle-arcade.net/ac/api/Area/captureInfo
+pub mod capture_info {
+    use super::super::unknown_fields::{self, UnknownField, UnknownFields};
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Map, Result, Value};
+    use std::io::Read;
+
+    pub type AreaCaptureInfo = Vec<AreaCaptureStage>;
+
+    /// Parses an AreaCaptureInfo from the provided JSON reader.
+    /// Fails if not given a JSON array, or expected data structure does not match.
+    pub fn read_area_capture_info(reader: impl Read) -> Result<AreaCaptureInfo> {
+        unknown_fields::read_strict(reader)
+    }
+
+    /// Parses an AreaCaptureInfo from the provided JSON reader, also reporting any unknown fields.
+    /// Fails if not given a JSON array, or expected data structure does not match.
+    pub fn read_area_capture_info_lenient(
+        reader: impl Read,
+    ) -> Result<(AreaCaptureInfo, Vec<UnknownField>)> {
+        unknown_fields::read_lenient(reader)
+    }
+
+    /// A sortie map (海域) stage, and the admiral's progress on it
+    #[derive(Debug, Deserialize, Serialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct AreaCaptureStage {
+        /// e.g. 1 for 1-4
+        pub area_id: u16,
+        /// e.g. 4 for 1-4
+        pub area_sub_id: u16,
+        pub pursuit_map: bool,
+        pub pursuit_map_open: bool,
+        /// e.g. MEISTER, MATERIALMEDAL, NONE
+        pub stage_clear_item_info: String,
+        pub stage_clear_item_amount: u32,
+        /// Absent on most stages, e.g. RESULTPOINT, NONE
+        pub stage_clear_item_info2: Option<String>,
+        pub stage_clear_item_amount2: u32,
+        /// CLEAR or NOTCLEAR have been seen
+        pub area_clear_state: String,
+        pub limit_sec: u32,
+        pub require_gp: u32,
+        pub sortie_limit: bool,
+        /// A sortie map stage picture, see images.rs
+        pub stage_image_name: String,
+        pub stage_mission_name: String,
+        pub stage_mission_info: String,
+        /// e.g. BUCKET, SMALLBOX, NONE
+        pub stage_drop_item_info: Vec<String>,
+        pub sortie_limit_drum: u16,
+        /// Sic
+        pub strategy_point_drum_bounus: bool,
+        pub all_lv_raid_flag: bool,
+        pub support_fleet_flag: bool,
+        /// Far in the future, or absent, for stages with no time limit
+        #[serde(default, with = "chrono::serde::ts_milliseconds_option")]
+        pub ssop_start_time: Option<DateTime<Utc>>,
+        /// Far in the future, or absent, for stages with no time limit
+        #[serde(default, with = "chrono::serde::ts_milliseconds_option")]
+        pub ssop_end_time: Option<DateTime<Utc>>,
+        /// The epoch for stages with no time limit
+        #[serde(with = "chrono::serde::ts_milliseconds")]
+        pub period_end_time: DateTime<Utc>,
+        /// Absent on stages without a boss gauge
+        pub boss_info: Option<AreaBossInfo>,
+        /// Absent on most stages
+        pub ng_unit_img: Option<String>,
+        /// Absent on stages without routing, e.g. ROUTE_A, PURSUIT
+        pub route: Option<String>,
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    impl UnknownFields for AreaCaptureStage {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+            self.boss_info
+                .unknown_fields(&format!("{path}.bossInfo"), found);
+        }
+    }
+
+    #[derive(Debug, Deserialize, Serialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct AreaBossInfo {
+        /// NORMAL or BREAK have been seen
+        pub military_gauge_status: String,
+        pub ene_military_gauge_val: u32,
+        pub military_gauge_left: u32,
+        /// e.g. FORM_1, NONE
+        pub boss_status: String,
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    impl UnknownFields for AreaBossInfo {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
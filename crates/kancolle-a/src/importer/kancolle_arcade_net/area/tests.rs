@@ -0,0 +1,32 @@
+use super::capture_info::*;
+
+use lazy_static_include::*;
+
+// https://kancolle-arcade.net/ac/api/Area/captureInfo
+lazy_static_include_bytes! {
+    AREA_CAPTURE_INFO_LATEST => "tests/fixtures/latest/Area_captureInfo.json",
+}
+
+#[test]
+fn parse_empty_area_capture_info_reader() {
+    read_area_capture_info(std::io::empty()).unwrap_err();
+}
+
+#[test]
+fn parse_fixture_area_capture_info_latest() {
+    let area_capture_info = read_area_capture_info(AREA_CAPTURE_INFO_LATEST.as_ref()).unwrap();
+    assert_eq!(area_capture_info.len(), 42);
+
+    let stage = &area_capture_info[0];
+    assert_eq!((stage.area_id, stage.area_sub_id), (1, 1));
+    assert_eq!(stage.stage_mission_name, "近海警備");
+    assert_eq!(stage.stage_image_name, "area_rprx04hjnl.png");
+    assert_eq!(stage.stage_drop_item_info.len(), 4);
+    assert!(stage.boss_info.is_none());
+    assert_eq!(stage.period_end_time.timestamp_millis(), 0);
+
+    assert!(area_capture_info
+        .iter()
+        .any(|stage| stage.boss_info.is_some()));
+    assert!(area_capture_info.iter().any(|stage| stage.route.is_some()));
+}
@@ -1,8 +1,11 @@
 //! Module for importer for https://kancolle-arcade.net/ac/api/BlueprintList/info
 
-use chrono::{DateTime, Utc};
-use serde::Deserialize;
-use serde_json::Result;
+use super::unknown_fields::{self, UnknownField, UnknownFields};
+use crate::ship_attributes::ShipType;
+use crate::time::japan;
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Result, Value};
 use std::io::Read;
 
 type BlueprintList = Vec<BlueprintShip>;
@@ -10,40 +13,92 @@ type BlueprintList = Vec<BlueprintShip>;
 /// Parses a BlueprintList from the provided JSON reader.
 /// Fails if not given a JSON array, or expected data structure does not match.
 pub(crate) fn read_blueprintlist(blueprintlist_reader: impl Read) -> Result<BlueprintList> {
-    let result: BlueprintList = serde_json::from_reader(blueprintlist_reader)?;
-    Ok(result)
+    unknown_fields::read_strict(blueprintlist_reader)
+}
+
+/// Parses a BlueprintList from the provided JSON reader, also reporting any unknown fields.
+/// Fails if not given a JSON array, or expected data structure does not match.
+pub(crate) fn read_blueprintlist_lenient(
+    blueprintlist_reader: impl Read,
+) -> Result<(BlueprintList, Vec<UnknownField>)> {
+    unknown_fields::read_lenient(blueprintlist_reader)
 }
 
 // Notes for future functions
-// * Status images (i/i_xxx.png) live in https://kancolle-arcade.net/ac/resources/chara/
-// ** Status images end with _n, _bs, _bm, or _bl. (Not sure if there's one for sunk?)
+// * Status image paths are resolved to URLs in images.rs
 // * Expiration date appears to be the 11th of the month of expiry. Not clear why.
 // ** True expiration date is 23:59 on the last date of the month.
 // ** Or I made a mistake, I guess?
+// ** BlueprintExpirationDate offers both, see expires_at and expires_at_month_end
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
-#[serde(deny_unknown_fields)]
 pub struct BlueprintShip {
     pub ship_class_id: u16,
     pub ship_class_index: u16,
     pub ship_sort_no: u16,
-    pub ship_type: String,
+    pub ship_type: ShipType,
     pub ship_name: String,
     pub status_img: String,
     pub blueprint_total_num: u16,
     pub exists_warning_for_expiration: bool,
     pub expiration_date_list: Vec<BlueprintExpirationDate>,
+    /// Any fields not listed above, see read_*_lenient
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl UnknownFields for BlueprintShip {
+    fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+        self.extra.unknown_fields(path, found);
+        self.ship_type
+            .unknown_fields(&format!("{path}.shipType"), found);
+        self.expiration_date_list
+            .unknown_fields(&format!("{path}.expirationDateList"), found);
+    }
 }
 
-#[derive(Debug, Deserialize, Clone, Copy)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
-#[serde(deny_unknown_fields)]
 pub struct BlueprintExpirationDate {
     #[serde(with = "chrono::serde::ts_milliseconds")]
     pub expiration_date: DateTime<Utc>,
     pub blueprint_num: u16,
     pub expire_this_month: bool,
+    /// Any fields not listed above, see read_*_lenient
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl BlueprintExpirationDate {
+    /// The expiry as reported, 23:59:59 Japan time on the 11th of the month of expiry.
+    pub fn expires_at(&self) -> DateTime<FixedOffset> {
+        self.expiration_date.with_timezone(&japan())
+    }
+
+    /// 23:59:59 Japan time on the last day of the month of expiry, which may be the
+    /// true expiry. See the notes above.
+    pub fn expires_at_month_end(&self) -> DateTime<FixedOffset> {
+        let expires_at = self.expires_at();
+        let (year, month) = match expires_at.month() {
+            12 => (expires_at.year() + 1, 1),
+            month => (expires_at.year(), month + 1),
+        };
+        let month_end = NaiveDate::from_ymd_opt(year, month, 1)
+            .unwrap()
+            .pred_opt()
+            .unwrap();
+        month_end
+            .and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap())
+            .and_local_timezone(japan())
+            .unwrap()
+    }
+}
+
+impl UnknownFields for BlueprintExpirationDate {
+    fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+        self.extra.unknown_fields(path, found);
+    }
 }
 
 #[cfg(test)]
@@ -4,6 +4,7 @@ use chrono::TimeZone;
 use chrono_tz::Asia::Tokyo;
 
 use super::*;
+use crate::ship_attributes::ShipType;
 
 use lazy_static_include::*;
 
@@ -53,7 +54,7 @@ fn validate_blueprint_list_common(blueprint_list: &BlueprintList) {
 
         //assert_ne!(*ship.ship_class_id(), 0); // Can be zero, is this an index into something?
         assert_ne!(ship.ship_class_index, 0);
-        assert_ne!(ship.ship_type, "");
+        assert!(!matches!(ship.ship_type, ShipType::Unknown(_)));
         assert_ne!(ship.ship_name, "");
 
         assert!(ship.status_img.starts_with(STATUS_IMAGE_PREFIX));
@@ -88,7 +89,7 @@ fn parse_fixture_blueprint_list_info_20240528() {
     assert_eq!(弥生.ship_class_id, 14);
     assert_eq!(弥生.ship_class_index, 3);
     assert_eq!(弥生.ship_sort_no, 1800);
-    assert_eq!(弥生.ship_type, "駆逐艦");
+    assert_eq!(弥生.ship_type, ShipType::Destroyer);
     assert_eq!(弥生.ship_name, "弥生");
     assert_eq!(弥生.status_img, "i/i_4ma06a97am0r_n.png");
     assert_eq!(弥生.blueprint_total_num, 2);
@@ -121,7 +122,7 @@ fn parse_fixture_blueprint_list_info_20240528() {
     assert_eq!(卯月.ship_class_id, 14);
     assert_eq!(卯月.ship_class_index, 4);
     assert_eq!(卯月.ship_sort_no, 1800);
-    assert_eq!(卯月.ship_type, "駆逐艦");
+    assert_eq!(卯月.ship_type, ShipType::Destroyer);
     assert_eq!(卯月.ship_name, "卯月");
     assert_eq!(卯月.status_img, "i/i_mj1x41twqqw6_n.png");
     assert_eq!(卯月.blueprint_total_num, 2);
@@ -137,6 +138,14 @@ fn parse_fixture_blueprint_list_info_20240528() {
     );
     assert_eq!(卯月_0.blueprint_num, 1);
     assert!(卯月_0.expire_this_month);
+    assert_eq!(
+        卯月_0.expires_at(),
+        Tokyo.with_ymd_and_hms(2024, 5, 11, 23, 59, 59).unwrap()
+    );
+    assert_eq!(
+        卯月_0.expires_at_month_end(),
+        Tokyo.with_ymd_and_hms(2024, 5, 31, 23, 59, 59).unwrap()
+    );
     let 卯月_1 = &卯月.expiration_date_list[1];
     assert_eq!(
         卯月_1.expiration_date,
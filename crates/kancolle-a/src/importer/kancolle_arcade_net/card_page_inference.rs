@@ -0,0 +1,458 @@
+//! Propose sources for TcBook card pages we don't know the source of, by pooling
+//! several players' TcBook dumps taken at different dates.
+//!
+//! The evidence used is:
+//! * Page shape: Original Illustration pages have one or two variations, where Normal
+//!   and event pages have three per row, and show the Normal page's status images even
+//!   before any card is acquired.
+//! * Page order: event pages are added in order of release, before any Original
+//!   Illustration pages, so already-known sources fill the first event pages.
+//! * Page counts: a page which appeared between two dumps was added by an event
+//!   running between them.
+//! * Acquisition: a player who acquired a card on a page between two of their dumps did
+//!   so while its event was running.
+//!
+//! Proposals are suggestions for a human to check before adding them to the card page
+//! sources data, see CardPageSources.
+
+use super::{
+    read_tclist, BookShip, BookShipCardPageSource, BookShipCardPageSourceDiscriminants,
+    CardPageSources, TcBook,
+};
+use chrono::{Datelike, NaiveDate};
+use serde_json::Result;
+use std::collections::BTreeMap;
+use std::io::Read;
+use strum::{Display, EnumString};
+
+/// One player's TcBook, as dumped on a given date.
+#[derive(Debug, Clone)]
+pub struct BookSnapshot {
+    /// Any label which is the same for all of one player's snapshots
+    pub player: String,
+    pub date: NaiveDate,
+    pub tcbook: TcBook,
+}
+
+impl BookSnapshot {
+    /// Parses a TcBook from the provided JSON reader.
+    pub fn from_reader(player: String, date: NaiveDate, reader: impl Read) -> Result<BookSnapshot> {
+        Ok(BookSnapshot {
+            player,
+            date,
+            tcbook: read_tclist(reader)?,
+        })
+    }
+}
+
+/// A period during which cards of a given source could be obtained.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventRun {
+    pub source: BookShipCardPageSourceDiscriminants,
+    pub start: NaiveDate,
+    /// Inclusive
+    pub end: NaiveDate,
+}
+
+/// A year and month
+type Month = (i32, u32);
+
+/// Seasonal events: the first run, and the months they usually run in each year.
+/// See the BookShipCardPageSource docs.
+///
+/// Each entry cites the announcements at https://kancolle-a.sega.jp/players/information/
+/// for its first run and usual months.
+///
+/// PacificSaury and Fishing pages come from the same 鎮守府秋刀魚祭り, so have identical
+/// windows. Dates alone can never tell them apart, and both are proposed as candidates.
+const SEASONAL_EVENTS: &[(BookShipCardPageSourceDiscriminants, Month, &[u32])] = {
+    use BookShipCardPageSourceDiscriminants::*;
+    &[
+        // 190722_1.html, 2207_join_Johnston_swim.html, 2208_join_kazagumo_swim.html
+        (Swimsuit, (2019, 7), &[7, 8]),
+        // 191203_1.html, 2212_xmas.html
+        (Christmas, (2019, 12), &[12]),
+        // 201013_1.html, 2210_halloween.html
+        (Halloween, (2020, 10), &[10]),
+        // 210205_1.html, 2402_valentine.html
+        (Valentine, (2021, 2), &[2]),
+        // 211005_1.html, 2209_sauryfestival.html, 2310_sauryfestival.html
+        (PacificSaury, (2021, 10), &[9, 10]),
+        (Fishing, (2021, 10), &[9, 10]),
+        // 211230_2.html, 2212_haregimode.html
+        (SundayBest, (2022, 1), &[12, 1]),
+        // 2205_rainy_season.html, 2306_rainy_season.html
+        (RainySeason, (2022, 5), &[5, 6]),
+        // 2309_yukata_season.html
+        (Yukata, (2023, 9), &[8, 9]),
+    ]
+};
+
+/// Once-off events, as (start, end) months, each citing its announcements at
+/// https://kancolle-a.sega.jp/players/information/
+const ONCE_OFF_EVENTS: &[(BookShipCardPageSourceDiscriminants, Month, Month)] = {
+    use BookShipCardPageSourceDiscriminants::*;
+    &[
+        // 190508_1.html
+        (DecisiveBattle, (2019, 5), (2019, 5)),
+        // 2401_seaarea_event14_detail_report.html
+        (DecisiveBattle, (2024, 1), (2024, 2)),
+        // 2409_join_zuikaku_kai_2_engano.html, 2410_seaarea_event15_start.html
+        (DecisiveBattle, (2024, 9), (2024, 10)),
+    ]
+};
+
+fn first_day(year: i32, month: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, 1).unwrap()
+}
+
+fn last_day(year: i32, month: u32) -> NaiveDate {
+    match month {
+        12 => first_day(year + 1, 1),
+        _ => first_day(year, month + 1),
+    }
+    .pred_opt()
+    .unwrap()
+}
+
+/// Our best knowledge of when each event source has run, up to the given date.
+/// Seasonal events are assumed to have run every year since their first run.
+pub fn default_event_runs(until: NaiveDate) -> Vec<EventRun> {
+    let mut runs: Vec<EventRun> = ONCE_OFF_EVENTS
+        .iter()
+        .map(|&(source, start, end)| EventRun {
+            source,
+            start: first_day(start.0, start.1),
+            end: last_day(end.0, end.1),
+        })
+        .collect();
+
+    for &(source, (first_year, first_month), months) in SEASONAL_EVENTS {
+        let first_run = first_day(first_year, first_month);
+        let (start_month, end_month) = (months[0], months[months.len() - 1]);
+        for year in first_year - 1..=until.year() {
+            let start = first_day(year, start_month);
+            let end = match end_month < start_month {
+                true => last_day(year + 1, end_month),
+                false => last_day(year, end_month),
+            };
+            if end >= first_run && start <= until {
+                runs.push(EventRun {
+                    source,
+                    start: start.max(first_run),
+                    end,
+                });
+            }
+        }
+    }
+
+    runs.sort_by_key(|run| run.start);
+    runs
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display, EnumString)]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+/// A proposed source for a TcBook page
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceProposal {
+    pub book_no: u16,
+    pub ship_name: String,
+    pub page: u16,
+    /// The possible sources, most likely first. Empty if nothing we know of fits.
+    /// Original Illustration pages are proposed without knowing which row they are for.
+    pub candidates: Vec<BookShipCardPageSourceDiscriminants>,
+    pub confidence: Confidence,
+    pub reason: String,
+}
+
+/// A period in which something happened: after `start`, up to and including `end`.
+#[derive(Debug, Clone, Copy)]
+struct Window {
+    start: Option<NaiveDate>,
+    end: NaiveDate,
+}
+
+impl Window {
+    fn overlaps(&self, run: &EventRun) -> bool {
+        run.start <= self.end && self.start.is_none_or(|start| run.end > start)
+    }
+}
+
+impl std::fmt::Display for Window {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.start {
+            Some(start) => write!(f, "{start}..{}", self.end),
+            None => write!(f, "by {}", self.end),
+        }
+    }
+}
+
+fn is_original_illustration(source: &BookShipCardPageSource) -> bool {
+    matches!(
+        source,
+        BookShipCardPageSource::OriginalIllustration1(..)
+            | BookShipCardPageSource::OriginalIllustration2(..)
+    )
+}
+
+/// Original Illustration pages have fewer variations than a row of a normal page.
+fn original_illustration_kind(
+    book_ship: &BookShip,
+    page: usize,
+) -> Option<BookShipCardPageSourceDiscriminants> {
+    match book_ship.card_list[page].variation_num_in_page {
+        1 => Some(BookShipCardPageSourceDiscriminants::OriginalIllustration1),
+        2 => Some(BookShipCardPageSourceDiscriminants::OriginalIllustration2),
+        _ => None,
+    }
+}
+
+/// The indexes of the event pages, i.e. not Normal or Original Illustration pages.
+fn event_pages(book_ship: &BookShip) -> Vec<usize> {
+    (1..book_ship.card_list.len())
+        .filter(|&page| original_illustration_kind(book_ship, page).is_none())
+        .collect()
+}
+
+/// All the observations of a single TcBook entry, in date order
+struct BookHistory<'a> {
+    observations: Vec<(&'a str, NaiveDate, &'a BookShip)>,
+}
+
+impl BookHistory<'_> {
+    fn latest(&self) -> &BookShip {
+        self.observations.last().unwrap().2
+    }
+
+    /// When the given event page (by index into event_pages) first appeared.
+    fn appeared(&self, event_index: usize) -> Window {
+        let present = |book_ship: &BookShip| event_pages(book_ship).len() > event_index;
+        let end = self
+            .observations
+            .iter()
+            .find(|(_, _, book_ship)| present(book_ship))
+            .map(|(_, date, _)| *date)
+            .unwrap();
+        let start = self
+            .observations
+            .iter()
+            .filter(|(_, date, book_ship)| *date < end && !present(book_ship))
+            .map(|(_, date, _)| *date)
+            .next_back();
+        Window { start, end }
+    }
+
+    /// For each player who acquired a card on the given event page, when they did.
+    fn acquired(&self, event_index: usize) -> Vec<Window> {
+        let acquired = |book_ship: &BookShip| {
+            event_pages(book_ship)
+                .get(event_index)
+                .map(|&page| book_ship.card_list[page].acquire_num_in_page > 0)
+        };
+
+        let mut players: Vec<&str> = self
+            .observations
+            .iter()
+            .map(|(player, _, _)| *player)
+            .collect();
+        players.sort();
+        players.dedup();
+
+        players
+            .into_iter()
+            .filter_map(|player| {
+                let mine = || {
+                    self.observations
+                        .iter()
+                        .filter(move |(owner, _, _)| *owner == player)
+                };
+                let end = mine()
+                    .find(|(_, _, book_ship)| acquired(book_ship) == Some(true))
+                    .map(|(_, date, _)| *date)?;
+                let start = mine()
+                    .filter(|(_, date, book_ship)| *date < end && acquired(book_ship) != Some(true))
+                    .map(|(_, date, _)| *date)
+                    .next_back();
+                Some(Window { start, end })
+            })
+            .collect()
+    }
+}
+
+fn infer_book(
+    history: &BookHistory,
+    card_page_sources: &CardPageSources,
+    event_runs: &[EventRun],
+) -> Vec<SourceProposal> {
+    use BookShipCardPageSourceDiscriminants as Source;
+
+    let latest = history.latest();
+    let known = card_page_sources.get(latest.book_no);
+    if known.is_some_and(|known| {
+        known.sources.len() + 1 == latest.card_list.len()
+            && !known.sources.contains(&BookShipCardPageSource::Unknown)
+    }) {
+        return vec![];
+    }
+
+    let proposal = |page: usize, candidates, confidence, reason: String| SourceProposal {
+        book_no: latest.book_no,
+        ship_name: latest.ship_name.clone(),
+        page: page as u16,
+        candidates,
+        confidence,
+        reason,
+    };
+    let mut proposals = vec![];
+
+    for page in 1..latest.card_list.len() {
+        let Some(kind) = original_illustration_kind(latest, page) else {
+            continue;
+        };
+        let card_page = &latest.card_list[page];
+        let (confidence, reason) = if card_page.status_img.is_some()
+            && card_page.status_img == latest.card_list[0].status_img
+        {
+            (
+                Confidence::High,
+                format!(
+                    "{} variations, with the Normal page's status images",
+                    card_page.variation_num_in_page
+                ),
+            )
+        } else {
+            (
+                Confidence::Medium,
+                format!("{} variations", card_page.variation_num_in_page),
+            )
+        };
+        proposals.push(proposal(page, vec![kind], confidence, reason));
+    }
+
+    // Known event sources fill the first event pages, in order.
+    let known_events: Vec<&BookShipCardPageSource> = known
+        .map(|known| {
+            known
+                .sources
+                .iter()
+                .filter(|source| !is_original_illustration(source))
+                .collect()
+        })
+        .unwrap_or_default();
+    let event_pages = event_pages(latest);
+    let known_events = match known_events.len() <= event_pages.len() {
+        true => known_events,
+        // The table doesn't fit this book at all, so work everything out again.
+        false => vec![],
+    };
+    let taken: Vec<Source> = known_events
+        .iter()
+        .filter(|&&source| source != &BookShipCardPageSource::Unknown)
+        .map(|&source| source.into())
+        .collect();
+
+    for (event_index, &page) in event_pages.iter().enumerate() {
+        if let Some(&source) = known_events.get(event_index) {
+            if source != &BookShipCardPageSource::Unknown {
+                let confidence = match known_events.len() == event_pages.len() {
+                    true => Confidence::High,
+                    false => Confidence::Medium,
+                };
+                proposals.push(proposal(
+                    page,
+                    vec![source.into()],
+                    confidence,
+                    format!(
+                        "event page {} of {}, with {} known",
+                        event_index + 1,
+                        event_pages.len(),
+                        known_events.len()
+                    ),
+                ));
+                continue;
+            }
+        }
+
+        let appeared = history.appeared(event_index);
+        let acquired = history.acquired(event_index);
+
+        // Most recent run before the page appeared first
+        let mut candidates: Vec<(NaiveDate, Source)> = vec![];
+        for run in event_runs {
+            if taken.contains(&run.source)
+                || !appeared.overlaps(run)
+                || !acquired.iter().all(|window| window.overlaps(run))
+            {
+                continue;
+            }
+            match candidates
+                .iter_mut()
+                .find(|(_, source)| *source == run.source)
+            {
+                Some(candidate) => candidate.0 = candidate.0.max(run.start),
+                None => candidates.push((run.start, run.source)),
+            }
+        }
+        candidates.sort_by_key(|&(start, _)| std::cmp::Reverse(start));
+        let candidates: Vec<Source> = candidates.into_iter().map(|(_, source)| source).collect();
+
+        let bounded_acquisition = acquired.iter().any(|window| window.start.is_some());
+        let confidence = match (candidates.len(), appeared.start, bounded_acquisition) {
+            (1, _, true) => Confidence::High,
+            (1, Some(_), false) => Confidence::Medium,
+            _ => Confidence::Low,
+        };
+
+        let mut reason = format!("appeared {appeared}");
+        for window in acquired.iter() {
+            reason += &format!(", acquired {window}");
+        }
+        proposals.push(proposal(page, candidates, confidence, reason));
+    }
+
+    proposals.sort_by_key(|proposal| proposal.page);
+    proposals
+}
+
+/// Propose sources for every TcBook page the given CardPageSources can't identify,
+/// from the given snapshots of one or more players' TcBooks.
+///
+/// `event_runs` is usually `default_event_runs`, but may be extended with better
+/// knowledge of when events ran.
+pub fn infer_card_page_sources(
+    snapshots: &[BookSnapshot],
+    card_page_sources: &CardPageSources,
+    event_runs: &[EventRun],
+) -> Vec<SourceProposal> {
+    let mut histories: BTreeMap<u16, BookHistory> = BTreeMap::new();
+    for snapshot in snapshots {
+        for book_ship in snapshot.tcbook.iter() {
+            if book_ship.card_list.is_empty() {
+                continue;
+            }
+            histories
+                .entry(book_ship.book_no)
+                .or_insert_with(|| BookHistory {
+                    observations: vec![],
+                })
+                .observations
+                .push((&snapshot.player, snapshot.date, book_ship));
+        }
+    }
+
+    histories
+        .values_mut()
+        .flat_map(|history| {
+            history.observations.sort_by_key(|(_, date, _)| *date);
+            infer_book(history, card_page_sources, event_runs)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,226 @@
+use super::*;
+use BookShipCardPageSourceDiscriminants as Source;
+
+const STATUS: &str = r#"["i/i_abc_n.png"]"#;
+
+/// A TcBook entry for book 9999 with the given (variations, acquired, status images) pages
+fn book_ship(pages: &[(u16, u16, Option<&str>)]) -> BookShip {
+    let card_list: Vec<String> = pages
+        .iter()
+        .enumerate()
+        .map(|(priority, (variations, acquired, status))| {
+            format!(
+                r#"{{"priority": {priority}, "cardImgList": [], "statusImg": {}, "variationNumInPage": {variations}, "acquireNumInPage": {acquired}}}"#,
+                status.unwrap_or("null")
+            )
+        })
+        .collect();
+    serde_json::from_str(&format!(
+        r#"{{"bookNo": 9999, "shipClass": "", "shipClassIndex": -1, "shipType": "", "shipModelNum": "", "shipName": "テスト", "cardIndexImg": "", "cardList": [{}], "variationNum": 0, "acquireNum": 0, "lv": 1, "isMarried": null, "marriedImg": null}}"#,
+        card_list.join(",")
+    ))
+    .unwrap()
+}
+
+fn snapshot(player: &str, date: &str, pages: &[(u16, u16, Option<&str>)]) -> BookSnapshot {
+    BookSnapshot {
+        player: player.to_string(),
+        date: date.parse().unwrap(),
+        tcbook: vec![book_ship(pages)],
+    }
+}
+
+fn until_2024() -> Vec<EventRun> {
+    default_event_runs(NaiveDate::from_ymd_opt(2024, 12, 31).unwrap())
+}
+
+#[test]
+fn test_default_event_runs() {
+    let runs = until_2024();
+
+    let sunday_best: Vec<(NaiveDate, NaiveDate)> = runs
+        .iter()
+        .filter(|run| run.source == Source::SundayBest)
+        .map(|run| (run.start, run.end))
+        .collect();
+    assert_eq!(
+        sunday_best.first(),
+        Some(&(
+            NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2022, 1, 31).unwrap()
+        ))
+    );
+    assert_eq!(
+        sunday_best.last(),
+        Some(&(
+            NaiveDate::from_ymd_opt(2024, 12, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap()
+        ))
+    );
+    assert!(runs.is_sorted_by_key(|run| run.start));
+    assert!(!runs
+        .iter()
+        .any(|run| run.source == Source::Yukata && run.start.year() < 2023));
+}
+
+#[test]
+fn test_original_illustration_pages() {
+    let snapshots = [snapshot(
+        "a",
+        "2024-06-01",
+        &[
+            (3, 1, Some(STATUS)),
+            (3, 0, None),
+            (1, 0, Some(STATUS)),
+            (2, 0, None),
+        ],
+    )];
+
+    let proposals = infer_card_page_sources(&snapshots, &CardPageSources::default(), &until_2024());
+    let summary: Vec<(u16, Vec<Source>, Confidence)> = proposals
+        .into_iter()
+        .map(|proposal| (proposal.page, proposal.candidates, proposal.confidence))
+        .collect();
+    assert_eq!(summary.len(), 3);
+    assert_eq!(summary[0].0, 1);
+    assert_eq!(summary[0].2, Confidence::Low);
+    assert_eq!(
+        summary[1..],
+        [
+            (2, vec![Source::OriginalIllustration1], Confidence::High),
+            (3, vec![Source::OriginalIllustration2], Confidence::Medium),
+        ]
+    );
+}
+
+#[test]
+fn test_acquisition_narrows_candidates() {
+    let mut snapshots = vec![
+        snapshot("a", "2024-04-20", &[(3, 1, Some(STATUS))]),
+        snapshot("a", "2024-07-15", &[(3, 1, Some(STATUS)), (3, 0, None)]),
+    ];
+
+    let proposals = infer_card_page_sources(&snapshots, &CardPageSources::default(), &until_2024());
+    assert_eq!(proposals.len(), 1);
+    assert_eq!(
+        proposals[0].candidates,
+        vec![Source::Swimsuit, Source::RainySeason]
+    );
+    assert_eq!(proposals[0].confidence, Confidence::Low);
+    assert_eq!(proposals[0].reason, "appeared 2024-04-20..2024-07-15");
+
+    snapshots.push(snapshot(
+        "b",
+        "2024-07-01",
+        &[(3, 1, Some(STATUS)), (3, 0, None)],
+    ));
+    snapshots.push(snapshot(
+        "b",
+        "2024-07-14",
+        &[(3, 1, Some(STATUS)), (3, 2, Some(STATUS))],
+    ));
+
+    let proposals = infer_card_page_sources(&snapshots, &CardPageSources::default(), &until_2024());
+    assert_eq!(proposals.len(), 1);
+    assert_eq!(proposals[0].candidates, vec![Source::Swimsuit]);
+    assert_eq!(proposals[0].confidence, Confidence::High);
+    assert_eq!(
+        proposals[0].reason,
+        "appeared 2024-04-20..2024-07-01, acquired 2024-07-01..2024-07-14"
+    );
+}
+
+#[test]
+fn test_known_sources() {
+    let sources = CardPageSources::from_reader(
+        r#"{"version": 1, "books": [{"bookNo": 9999, "ships": "テスト", "sources": ["Swimsuit"]}]}"#
+            .as_bytes(),
+    )
+    .unwrap();
+
+    // Fully known
+    let snapshots = [snapshot(
+        "a",
+        "2024-10-01",
+        &[(3, 1, Some(STATUS)), (3, 0, None)],
+    )];
+    assert_eq!(
+        infer_card_page_sources(&snapshots, &sources, &until_2024()),
+        vec![]
+    );
+
+    // A new page: Swimsuit takes the first event page, and can't be the new one.
+    let snapshots = [
+        snapshot("a", "2024-08-31", &[(3, 1, Some(STATUS)), (3, 0, None)]),
+        snapshot(
+            "a",
+            "2024-10-01",
+            &[(3, 1, Some(STATUS)), (3, 0, None), (3, 0, None)],
+        ),
+    ];
+    let proposals = infer_card_page_sources(&snapshots, &sources, &until_2024());
+    assert_eq!(proposals.len(), 2);
+    assert_eq!(proposals[0].candidates, vec![Source::Swimsuit]);
+    assert_eq!(proposals[0].confidence, Confidence::Medium);
+    assert_eq!(
+        proposals[1].candidates,
+        vec![
+            Source::Halloween,
+            Source::DecisiveBattle,
+            Source::PacificSaury,
+            Source::Fishing,
+            Source::Yukata
+        ]
+    );
+    assert_eq!(proposals[1].confidence, Confidence::Low);
+}
+
+#[test]
+fn test_fixtures() {
+    let snapshots: Vec<BookSnapshot> = [
+        "2024-05-28",
+        "2024-05-30",
+        "2024-06-09",
+        "2024-06-10",
+        "2024-06-20",
+        "2024-06-23",
+        "2024-10-06",
+    ]
+    .into_iter()
+    .map(|date| {
+        let path = format!(
+            "{}/tests/fixtures/{date}/TcBook_info.json",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        BookSnapshot::from_reader(
+            "fixtures".to_string(),
+            date.parse().unwrap(),
+            std::fs::File::open(path).unwrap(),
+        )
+        .unwrap()
+    })
+    .collect();
+
+    let sources = CardPageSources::bundled();
+    let events = default_event_runs(NaiveDate::from_ymd_opt(2024, 10, 6).unwrap());
+    let proposals = infer_card_page_sources(&snapshots, sources, &events);
+
+    let latest = &snapshots.last().unwrap().tcbook;
+    for proposal in proposals.iter() {
+        // Books the table fully knows are never proposed for
+        let book_ship = latest
+            .iter()
+            .find(|book_ship| book_ship.book_no == proposal.book_no)
+            .unwrap();
+        if let Some(known) = sources.get(proposal.book_no) {
+            assert!(
+                known.sources.len() + 1 != book_ship.card_list.len()
+                    || known.sources.contains(&BookShipCardPageSource::Unknown),
+                "{proposal:?}"
+            );
+        }
+        if proposal.confidence == Confidence::High {
+            assert_eq!(proposal.candidates.len(), 1, "{proposal:?}");
+        }
+    }
+}
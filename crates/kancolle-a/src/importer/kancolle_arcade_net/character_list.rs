@@ -1,7 +1,9 @@
 //! Module for importer for https://kancolle-arcade.net/ac/api/CharacterList/info
 
-use serde::Deserialize;
-use serde_json::Result;
+use super::unknown_fields::{self, UnknownField, UnknownFields};
+use crate::ship_attributes::{DamageStatus, ShipType};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Result, Value};
 use std::io::Read;
 
 type CharacterList = Vec<Character>;
@@ -9,17 +11,23 @@ type CharacterList = Vec<Character>;
 /// Parses a CharacterList from the provided JSON reader.
 /// Fails if not given a JSON array, or expected data structure does not match.
 pub(crate) fn read_characterlist(characterlist_reader: impl Read) -> Result<CharacterList> {
-    let result: CharacterList = serde_json::from_reader(characterlist_reader)?;
-    Ok(result)
+    unknown_fields::read_strict(characterlist_reader)
 }
 
-#[derive(Debug, Deserialize, Clone)]
+/// Parses a CharacterList from the provided JSON reader, also reporting any unknown fields.
+/// Fails if not given a JSON array, or expected data structure does not match.
+pub(crate) fn read_characterlist_lenient(
+    characterlist_reader: impl Read,
+) -> Result<(CharacterList, Vec<UnknownField>)> {
+    unknown_fields::read_lenient(characterlist_reader)
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
-#[serde(deny_unknown_fields)]
 pub struct Character {
     pub book_no: u16,
     pub lv: u16,
-    pub ship_type: String,
+    pub ship_type: ShipType,
     pub ship_sort_no: u16,
     pub remodel_lv: u16,
     pub ship_name: String,
@@ -31,23 +39,203 @@ pub struct Character {
     pub exp_percent: u16,
     pub max_hp: u16,
     pub real_hp: u16,
-    pub damage_status: String, // How do I enum this?
+    pub damage_status: DamageStatus,
+    /// How many of slots are usable, see usable_slots
     pub slot_num: u16,
-    pub slot_equip_name: Vec<String>,
-    pub slot_amount: Vec<u16>,
-    pub slot_disp: Vec<String>, // How do I enum this?
-    pub slot_img: Vec<String>,
-    pub slot_extension: Vec<bool>,
+    /// From the parallel `slot*` arrays of the source data
+    #[serde(flatten, with = "equip_slots")]
+    pub slots: Vec<EquipSlot>,
     pub blueprint_total_num: u16,
     pub married: bool,
-    pub disp_sort_no: u64, // This is really an encoded structure...
+    /// The in-game sort order, see DispSortNo
+    pub disp_sort_no: u64,
     pub develop_equipment_list: Vec<DevelopEquipment>,
     pub ship_model_num: String,
+    /// Any fields not listed above, see read_*_lenient
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Character {
+    pub fn disp_sort(&self) -> DispSortNo {
+        DispSortNo::from(self.disp_sort_no)
+    }
+
+    /// The slots this ship can use. Any further slots are always empty.
+    pub fn usable_slots(&self) -> &[EquipSlot] {
+        &self.slots[..(self.slot_num as usize).min(self.slots.len())]
+    }
+
+    /// The slots with something equipped
+    pub fn equipped_slots(&self) -> impl Iterator<Item = &EquipSlot> + '_ {
+        self.slots.iter().filter(|slot| slot.is_equipped())
+    }
+}
+
+/// An equipment slot of a Character
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EquipSlot {
+    /// Empty if nothing is equipped
+    pub equip_name: String,
+    /// The aircraft capacity of the slot, shown whether or not it holds aircraft
+    pub amount: u16,
+    pub disp: SlotDisp,
+    /// Empty if nothing is equipped, see images.rs
+    pub img: String,
+    /// True for the reinforcement expansion slot
+    pub extension: bool,
+}
+
+impl EquipSlot {
+    pub fn is_equipped(&self) -> bool {
+        !self.equip_name.is_empty()
+    }
+}
+
+/// How the game displays an equipment slot, i.e. whether it shows an aircraft count
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SlotDisp {
+    /// A slot which cannot hold aircraft, or is unusable
+    None,
+    /// A slot which can hold aircraft, but doesn't
+    NotEquippedAircraft,
+    /// A slot holding aircraft
+    EquippedAircraft,
+    /// A value this crate doesn't know about yet
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+impl SlotDisp {
+    /// The value from the source data, if this crate doesn't know it
+    pub fn unknown_value(&self) -> Option<&str> {
+        match self {
+            SlotDisp::Unknown(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// (De)serialize Character::slots from and to the parallel arrays of the source data.
+mod equip_slots {
+    use super::{EquipSlot, SlotDisp};
+    use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Slots {
+        slot_equip_name: Vec<String>,
+        slot_amount: Vec<u16>,
+        slot_disp: Vec<SlotDisp>,
+        slot_img: Vec<String>,
+        slot_extension: Vec<bool>,
+    }
+
+    pub fn serialize<S>(slots: &[EquipSlot], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Slots {
+            slot_equip_name: slots.iter().map(|slot| slot.equip_name.clone()).collect(),
+            slot_amount: slots.iter().map(|slot| slot.amount).collect(),
+            slot_disp: slots.iter().map(|slot| slot.disp.clone()).collect(),
+            slot_img: slots.iter().map(|slot| slot.img.clone()).collect(),
+            slot_extension: slots.iter().map(|slot| slot.extension).collect(),
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<EquipSlot>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let slots = Slots::deserialize(deserializer)?;
+        let count = slots.slot_equip_name.len();
+        if [
+            slots.slot_amount.len(),
+            slots.slot_disp.len(),
+            slots.slot_img.len(),
+            slots.slot_extension.len(),
+        ]
+        .iter()
+        .any(|&len| len != count)
+        {
+            return Err(D::Error::custom("slot arrays differ in length"));
+        }
+        Ok(itertools::izip!(
+            slots.slot_equip_name,
+            slots.slot_amount,
+            slots.slot_disp,
+            slots.slot_img,
+            slots.slot_extension
+        )
+        .map(|(equip_name, amount, disp, img, extension)| EquipSlot {
+            equip_name,
+            amount,
+            disp,
+            img,
+            extension,
+        })
+        .collect())
+    }
+}
+
+/// The decoded `dispSortNo` of a Character, by which the game orders characters:
+/// in TcBook order of the base ship, then by remodel level.
+///
+/// Encoded in decimal as `BBB_CCC_III_RR`: the TcBook number of the base ship, the
+/// `shipClassId` (as in the Blueprint List), the index in the class, and `remodelLv`.
+/// Fields are in that order, so the derived Ord matches the encoded number.
+/// The ship type is not part of it, see `shipSortNo` for that.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DispSortNo {
+    pub base_book_no: u16,
+    pub ship_class_id: u16,
+    /// Present even where the Character List lacks shipClassIndex
+    pub ship_class_index: u16,
+    pub remodel_lv: u16,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl From<u64> for DispSortNo {
+    fn from(disp_sort_no: u64) -> Self {
+        DispSortNo {
+            base_book_no: (disp_sort_no / 100_000_000) as u16,
+            ship_class_id: (disp_sort_no / 100_000 % 1000) as u16,
+            ship_class_index: (disp_sort_no / 100 % 1000) as u16,
+            remodel_lv: (disp_sort_no % 100) as u16,
+        }
+    }
+}
+
+impl From<DispSortNo> for u64 {
+    fn from(disp_sort: DispSortNo) -> Self {
+        disp_sort.base_book_no as u64 * 100_000_000
+            + disp_sort.ship_class_id as u64 * 100_000
+            + disp_sort.ship_class_index as u64 * 100
+            + disp_sort.remodel_lv as u64
+    }
+}
+
+impl UnknownFields for Character {
+    fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+        self.extra.unknown_fields(path, found);
+        self.ship_type
+            .unknown_fields(&format!("{path}.shipType"), found);
+        self.damage_status
+            .unknown_fields(&format!("{path}.damageStatus"), found);
+        for (index, slot) in self.slots.iter().enumerate() {
+            slot.disp
+                .unknown_fields(&format!("{path}.slotDisp[{index}]"), found);
+        }
+        self.develop_equipment_list
+            .unknown_fields(&format!("{path}.developEquipmentList"), found);
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
-#[serde(deny_unknown_fields)]
 pub struct DevelopEquipment {
     pub plan_kind: u16,
     pub sort_index: u16,
@@ -57,6 +245,15 @@ pub struct DevelopEquipment {
     pub develop_count: u16,
     pub max_develop_count: u16,
     pub develop_equip_img: String,
+    /// Any fields not listed above, see read_*_lenient
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl UnknownFields for DevelopEquipment {
+    fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+        self.extra.unknown_fields(path, found);
+    }
 }
 
 #[cfg(test)]
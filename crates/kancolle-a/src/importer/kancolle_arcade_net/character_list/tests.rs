@@ -1,5 +1,5 @@
 use super::*;
-use itertools::izip;
+use crate::importer::kancolle_arcade_net::UnknownKind;
 
 use lazy_static_include::*;
 
@@ -31,61 +31,42 @@ fn validate_character_list_common(character_list: &CharacterList) {
         let _ship_name = &ship.ship_name;
         eprintln!("Ship {_ship_name}");
         // Minimum of four, but some ships have more
-        assert_eq!(ship.slot_equip_name.len(), ship.slot_amount.len());
-        assert_eq!(ship.slot_equip_name.len(), ship.slot_disp.len());
-        assert_eq!(ship.slot_equip_name.len(), ship.slot_img.len());
-        assert_eq!(ship.slot_equip_name.len(), ship.slot_extension.len());
-
-        for (equip, amount, display, image, extension) in izip!(
-            ship.slot_equip_name[(ship.slot_num as usize)..].iter(),
-            ship.slot_amount[(ship.slot_num as usize)..].iter(),
-            ship.slot_disp[(ship.slot_num as usize)..].iter(),
-            ship.slot_img[(ship.slot_num as usize)..].iter(),
-            ship.slot_extension[(ship.slot_num as usize)..].iter()
-        ) {
-            assert_eq!(equip, "");
-            assert_eq!(*amount, 0);
-            assert_eq!(display, "NONE");
-            assert_eq!(image, "");
-            assert!(!extension);
+        assert!(ship.slots.len() >= 4);
+        assert_eq!(ship.usable_slots().len(), ship.slot_num as usize);
+
+        for slot in ship.slots[(ship.slot_num as usize)..].iter() {
+            assert_eq!(slot.equip_name, "");
+            assert_eq!(slot.amount, 0);
+            assert_eq!(slot.disp, SlotDisp::None);
+            assert_eq!(slot.img, "");
+            assert!(!slot.extension);
         }
-        for (equip, amount, display, image, extension) in izip!(
-            ship.slot_equip_name[..(ship.slot_num as usize)].iter(),
-            ship.slot_amount[..(ship.slot_num as usize)].iter(),
-            ship.slot_disp[..(ship.slot_num as usize)].iter(),
-            ship.slot_img[..(ship.slot_num as usize)].iter(),
-            ship.slot_extension[..(ship.slot_num as usize)].iter()
-        ) {
-            if equip.is_empty() {
-                assert_eq!(image, "");
-                assert!(!extension);
-                if *amount == 0 {
+        for slot in ship.usable_slots().iter() {
+            if !slot.is_equipped() {
+                assert_eq!(slot.img, "");
+                assert!(!slot.extension);
+                if slot.amount == 0 {
                     // TODO: Why can this be NOT_EQUIPPED_AIRCRAFT? See 鳳翔改.
                     // Wiki shows it should be 14-16-12, so data issue? Invisible damage?
                     // Might be a weirdness due to initial loadout zeroing out the slots?
-                    assert!(display == "NONE" || display == "NOT_EQUIPPED_AIRCRAFT");
+                    assert_ne!(slot.disp, SlotDisp::EquippedAircraft);
                 } else {
-                    assert_eq!(display, "NOT_EQUIPPED_AIRCRAFT");
+                    assert_eq!(slot.disp, SlotDisp::NotEquippedAircraft);
                 }
             } else {
-                assert_ne!(image, "");
+                assert_ne!(slot.img, "");
                 // TODO: What is this for? New in VERSION E REVISION 2
-                assert!(!extension);
-                if *amount == 0 {
+                assert!(!slot.extension);
+                if slot.amount == 0 {
                     // TODO: Why can this be NOT_EQUIPPED_AIRCRAFT? See 熊野改.
                     // Wiki shows it should be 5-6-5-6, so data issue? In this case she is damaged, but
                     // the other slots are unaffected.
                     // Might be a weirdness due to initial loadout zeroing out the slots?
                     // TODO: 明石 has 0-count aircraft-capable mounts. And I happen to have used them in my data,
                     // so we can have EQUIPPED_AIRCRAFT here too.
-                    assert!(
-                        display == "NONE"
-                            || display == "NOT_EQUIPPED_AIRCRAFT"
-                            || display == "EQUIPPED_AIRCRAFT"
-                    );
                 } else {
                     // Depends on whether the equipped item is an aircraft
-                    assert!(display == "EQUIPPED_AIRCRAFT" || display == "NOT_EQUIPPED_AIRCRAFT");
+                    assert_ne!(slot.disp, SlotDisp::None);
                 }
             }
         }
@@ -97,7 +78,7 @@ fn validate_character_list_common(character_list: &CharacterList) {
         let _ship_name = &ship.ship_name;
         eprintln!("Ship {_ship_name}");
 
-        assert_ne!(ship.ship_type, "");
+        assert!(!matches!(ship.ship_type, ShipType::Unknown(_)));
         assert_ne!(ship.ship_name, "");
 
         assert!(ship.status_img.starts_with(STATUS_IMAGE_PREFIX));
@@ -112,31 +93,24 @@ fn validate_character_list_common(character_list: &CharacterList) {
         assert!(ship.tc_img.starts_with(&card_image_prefix));
         assert!(ship.tc_img.ends_with(CARD_IMAGE_SUFFIX));
 
-        for image in ship.slot_img.iter().filter(|img| !img.is_empty()) {
-            assert!(image.starts_with(EQUIP_IMAGE_PREFIX));
-        }
-
-        for extension in ship.slot_extension.iter() {
-            assert!(!extension);
+        for slot in ship.equipped_slots() {
+            assert!(slot.img.starts_with(EQUIP_IMAGE_PREFIX));
         }
 
         // Breakdown of dispSortNo
-        let disp_sort_no = ship.disp_sort_no;
-        assert_eq!(ship.remodel_lv, (disp_sort_no % 100) as u16);
-        let disp_sort_no = disp_sort_no / 100;
+        let disp_sort = ship.disp_sort();
+        assert_eq!(u64::from(disp_sort), ship.disp_sort_no);
+        assert_eq!(ship.remodel_lv, disp_sort.remodel_lv);
         // dispSortNo is actually more reliable here, as various ships lack this index in their data.
         if let Some(ship_class_index) = ship.ship_class_index {
-            assert_eq!(ship_class_index, (disp_sort_no % 1000) as u16);
+            assert_eq!(ship_class_index, disp_sort.ship_class_index);
         } else {
-            assert_ne!((disp_sort_no % 1000), 0);
+            assert_ne!(disp_sort.ship_class_index, 0);
         };
-        let disp_sort_no = disp_sort_no / 1000;
-        // Not sure what's next. Probably the same as Blueprint shipClassId.
-        // TODO: Validate this in Ship tests.
-        let disp_sort_no = disp_sort_no / 1000;
-        // Useful fact but annoying to test: This is the base ship's book number.
+        // The ship class id is checked against the Blueprint List in the Ship tests.
+        // This is the base ship's book number.
         if ship.remodel_lv == 0 {
-            assert_eq!(ship.book_no, disp_sort_no as u16);
+            assert_eq!(ship.book_no, disp_sort.base_book_no);
         }
     }
 }
@@ -147,4 +121,72 @@ fn parse_fixture_character_list_info_20241031() {
 
     assert_eq!(character_list.len(), 393);
     validate_character_list_common(&character_list);
+
+    // The list is in the in-game order
+    assert!(character_list
+        .iter()
+        .map(|ship| ship.disp_sort())
+        .is_sorted());
+}
+
+#[test]
+fn disp_sort_no_decode() {
+    // 千歳航改二
+    let disp_sort = DispSortNo::from(4900500105);
+    assert_eq!(
+        disp_sort,
+        DispSortNo {
+            base_book_no: 49,
+            ship_class_id: 5,
+            ship_class_index: 1,
+            remodel_lv: 5,
+        }
+    );
+    assert_eq!(u64::from(disp_sort), 4900500105);
+    assert!(DispSortNo::from(100300100) < disp_sort);
+}
+
+#[test]
+fn character_slots_round_trip() {
+    let character_list = read_characterlist(CHARLIST_2024_10_31.as_ref()).unwrap();
+    let json = serde_json::to_string(&character_list).unwrap();
+    assert!(json.contains("\"slotEquipName\":["));
+    assert!(!json.contains("\"slots\""));
+
+    let round_trip = read_characterlist(json.as_bytes()).unwrap();
+    for (left, right) in character_list.iter().zip(round_trip.iter()) {
+        assert_eq!(left.slots, right.slots);
+    }
+
+    let mismatched = String::from_utf8(CHARLIST_2024_10_31.to_vec())
+        .unwrap()
+        .replacen("\"slotExtension\": [", "\"slotExtension\": [false,", 1);
+    read_characterlist(mismatched.as_bytes()).unwrap_err();
+}
+
+#[test]
+fn character_unknown_slot_disp() {
+    let modified = String::from_utf8(CHARLIST_2024_10_31.to_vec())
+        .unwrap()
+        .replacen("\"slotDisp\": [", "\"slotDisp\": [\"NEW_DISP\",", 1)
+        .replacen("\"slotEquipName\": [", "\"slotEquipName\": [\"\",", 1)
+        .replacen("\"slotAmount\": [", "\"slotAmount\": [0,", 1)
+        .replacen("\"slotImg\": [", "\"slotImg\": [\"\",", 1)
+        .replacen("\"slotExtension\": [", "\"slotExtension\": [false,", 1);
+
+    let err = read_characterlist(modified.as_bytes()).unwrap_err();
+    assert!(err.to_string().contains("NEW_DISP"));
+
+    let (character_list, unknown_fields) = read_characterlist_lenient(modified.as_bytes()).unwrap();
+    assert_eq!(
+        character_list[0].slots[0].disp,
+        SlotDisp::Unknown("NEW_DISP".to_string())
+    );
+    assert_eq!(unknown_fields.len(), 1);
+    assert_eq!(unknown_fields[0].path, "[0].slotDisp[0]");
+    assert_eq!(unknown_fields[0].kind, UnknownKind::Value);
+
+    // Unknown values are written back out as they were found
+    let json = serde_json::to_string(&character_list).unwrap();
+    assert!(json.contains("\"slotDisp\":[\"NEW_DISP\","));
 }
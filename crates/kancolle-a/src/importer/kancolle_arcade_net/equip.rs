@@ -0,0 +1,161 @@
+use super::{DevelopEquipment, EquipSlot};
+
+/// Module for importer for https://kancolle-arcade.net/ac/api/EquipBook/info
+pub mod book {
+    use super::super::unknown_fields::{self, UnknownField, UnknownFields};
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Map, Result, Value};
+    use std::io::Read;
+
+    pub type EquipBook = Vec<BookEquipment>;
+
+    /// Parses an EquipBook from the provided JSON reader.
+    /// Fails if not given a JSON array, or expected data structure does not match.
+    pub fn read_equipbook(reader: impl Read) -> Result<EquipBook> {
+        unknown_fields::read_strict(reader)
+    }
+
+    /// Parses an EquipBook from the provided JSON reader, also reporting any unknown fields.
+    /// Fails if not given a JSON array, or expected data structure does not match.
+    pub fn read_equipbook_lenient(reader: impl Read) -> Result<(EquipBook, Vec<UnknownField>)> {
+        unknown_fields::read_lenient(reader)
+    }
+
+    /// An entry in the equipment picture book. Entries the player has not yet
+    /// seen have only a book number.
+    #[derive(Debug, Deserialize, Serialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct BookEquipment {
+        pub book_no: u16,
+        /// e.g. 艦上戦闘機
+        pub equip_kind: String,
+        pub equip_name: String,
+        /// See images.rs
+        pub equip_img: String,
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    impl BookEquipment {
+        pub fn is_known(&self) -> bool {
+            !self.equip_name.is_empty()
+        }
+    }
+
+    impl UnknownFields for BookEquipment {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+        }
+    }
+}
+
+/// Module for importer for https://kancolle-arcade.net/ac/api/EquipList/info
+pub mod list {
+    use super::super::unknown_fields::{self, UnknownField, UnknownFields};
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Map, Result, Value};
+    use std::io::Read;
+
+    /// Parses an EquipList from the provided JSON reader.
+    /// Fails if not given a JSON object, or expected data structure does not match.
+    pub fn read_equiplist(reader: impl Read) -> Result<EquipList> {
+        unknown_fields::read_strict(reader)
+    }
+
+    /// Parses an EquipList from the provided JSON reader, also reporting any unknown fields.
+    /// Fails if not given a JSON object, or expected data structure does not match.
+    pub fn read_equiplist_lenient(reader: impl Read) -> Result<(EquipList, Vec<UnknownField>)> {
+        unknown_fields::read_lenient(reader)
+    }
+
+    /// The equipment the player holds, including what is equipped.
+    #[derive(Debug, Deserialize, Serialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct EquipList {
+        /// How much equipment can be held
+        pub max_slot_num: u16,
+        pub equip_list: Vec<ListEquipment>,
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    impl UnknownFields for EquipList {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+            self.equip_list
+                .unknown_fields(&format!("{path}.equipList"), found);
+        }
+    }
+
+    #[derive(Debug, Deserialize, Serialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ListEquipment {
+        /// A broad grouping of EquipBook kinds, as far as we can tell:
+        /// 1: main guns, 2: secondary and AA guns, 3: torpedoes, 4: carrier fighters,
+        /// 5: carrier bombers, 6: other aircraft, 7: ASW, 8: everything else.
+        #[serde(rename = "type")]
+        pub equip_type: u16,
+        pub equipment_id: u16,
+        pub name: String,
+        /// How many are held
+        pub num: u16,
+        /// An equipment slot icon, see images.rs
+        pub img: String,
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    impl UnknownFields for ListEquipment {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+        }
+    }
+}
+
+// The API only gives equipment names for Character slots, so that's what we match on.
+impl EquipSlot {
+    /// The EquipBook entry for the equipment in this slot
+    pub fn book_equipment<'a>(
+        &self,
+        equip_book: &'a [book::BookEquipment],
+    ) -> Option<&'a book::BookEquipment> {
+        if !self.is_equipped() {
+            return None;
+        }
+        equip_book
+            .iter()
+            .find(|equipment| equipment.equip_name == self.equip_name)
+    }
+
+    /// The EquipList entry for the equipment in this slot, i.e. how many are held
+    pub fn list_equipment<'a>(
+        &self,
+        equip_list: &'a list::EquipList,
+    ) -> Option<&'a list::ListEquipment> {
+        if !self.is_equipped() {
+            return None;
+        }
+        equip_list
+            .equip_list
+            .iter()
+            .find(|equipment| equipment.name == self.equip_name)
+    }
+}
+
+impl DevelopEquipment {
+    /// The EquipBook entry for the equipment developed, matched by image
+    pub fn book_equipment<'a>(
+        &self,
+        equip_book: &'a [book::BookEquipment],
+    ) -> Option<&'a book::BookEquipment> {
+        equip_book
+            .iter()
+            .find(|equipment| equipment.equip_img == self.develop_equip_img)
+    }
+}
+
+#[cfg(test)]
+mod tests;
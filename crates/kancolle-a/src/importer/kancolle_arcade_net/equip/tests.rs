@@ -0,0 +1,122 @@
+use super::super::read_characterlist;
+use super::book::*;
+use super::list::*;
+use crate::ship_attributes::ShipType;
+use itertools::Itertools;
+
+use lazy_static_include::*;
+
+// https://kancolle-arcade.net/ac/api/EquipBook/info
+// https://kancolle-arcade.net/ac/api/EquipList/info
+lazy_static_include_bytes! {
+    EQUIPBOOK_LATEST => "tests/fixtures/latest/EquipBook_info.json",
+    EQUIPLIST_LATEST => "tests/fixtures/latest/EquipList_info.json",
+    CHARLIST_LATEST => "tests/fixtures/latest/CharacterList_info.json",
+}
+
+#[test]
+fn parse_empty_equip_book_reader() {
+    read_equipbook(std::io::empty()).unwrap_err();
+}
+
+#[test]
+fn parse_empty_equip_book_vector() {
+    let equip_book = read_equipbook("[]".as_bytes()).unwrap();
+    assert_eq!(equip_book.len(), 0);
+}
+
+#[test]
+fn parse_fixture_equip_book_info_latest() {
+    let equip_book = read_equipbook(EQUIPBOOK_LATEST.as_ref()).unwrap();
+    assert_eq!(equip_book.len(), 282);
+
+    // Not in order, and with gaps, but unique
+    assert!(equip_book
+        .iter()
+        .map(|equipment| equipment.book_no)
+        .all_unique());
+
+    for equipment in equip_book.iter() {
+        if equipment.is_known() {
+            assert_ne!(equipment.equip_kind, "");
+            assert!(equipment
+                .equip_img
+                .starts_with(&format!("e/equip_{}_", equipment.book_no)));
+        } else {
+            assert_eq!(equipment.equip_kind, "");
+            assert_eq!(equipment.equip_img, "");
+        }
+    }
+}
+
+#[test]
+fn parse_fixture_equip_list_info_latest() {
+    let equip_list = read_equiplist(EQUIPLIST_LATEST.as_ref()).unwrap();
+    assert_eq!(equip_list.max_slot_num, 590);
+    assert_eq!(equip_list.equip_list.len(), 164);
+
+    for equipment in equip_list.equip_list.iter() {
+        assert!((1..=8).contains(&equipment.equip_type));
+        assert!(equipment.num > 0);
+        assert!(equipment.img.starts_with("equip_icon_"));
+    }
+}
+
+#[test]
+fn equip_slot_links() {
+    let equip_book = read_equipbook(EQUIPBOOK_LATEST.as_ref()).unwrap();
+    let equip_list = read_equiplist(EQUIPLIST_LATEST.as_ref()).unwrap();
+    let character_list = read_characterlist(CHARLIST_LATEST.as_ref()).unwrap();
+
+    // Everything equipped is in both the EquipBook and the EquipList
+    for slot in character_list
+        .iter()
+        .flat_map(|character| character.equipped_slots())
+    {
+        let book_equipment = slot.book_equipment(&equip_book).unwrap();
+        let list_equipment = slot.list_equipment(&equip_list).unwrap();
+        assert_eq!(book_equipment.equip_name, list_equipment.name);
+    }
+
+    let 龍驤改二 = character_list
+        .iter()
+        .find(|character| character.ship_name == "龍驤改二")
+        .unwrap();
+    let slot = &龍驤改二.slots[0];
+    assert_eq!(slot.equip_name, "試製烈風 後期型");
+    assert_eq!(
+        slot.book_equipment(&equip_book).unwrap().equip_kind,
+        "艦上戦闘機"
+    );
+    assert_eq!(slot.list_equipment(&equip_list).unwrap().equip_type, 4);
+
+    // Loadout audit: equipped carriers with no fighters
+    let no_fighters: Vec<&str> = character_list
+        .iter()
+        .filter(|character| {
+            matches!(
+                character.ship_type,
+                ShipType::LightCarrier | ShipType::StandardCarrier | ShipType::ArmoredCarrier
+            )
+        })
+        .filter(|character| character.equipped_slots().next().is_some())
+        .filter(|character| {
+            !character.equipped_slots().any(|slot| {
+                slot.book_equipment(&equip_book)
+                    .is_some_and(|equipment| equipment.equip_kind == "艦上戦闘機")
+            })
+        })
+        .map(|character| character.ship_name.as_str())
+        .collect();
+    assert!(no_fighters.contains(&"蒼龍改二"));
+    assert!(!no_fighters.contains(&"龍驤改二"));
+
+    // Empty slots link to nothing
+    let 蒼龍改二 = character_list
+        .iter()
+        .find(|character| character.ship_name == "蒼龍改二")
+        .unwrap();
+    assert!(!蒼龍改二.slots[1].is_equipped());
+    assert!(蒼龍改二.slots[1].book_equipment(&equip_book).is_none());
+    assert!(蒼龍改二.slots[1].list_equipment(&equip_list).is_none());
+}
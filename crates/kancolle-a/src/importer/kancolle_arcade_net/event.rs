@@ -0,0 +1,151 @@
+/// Module for importer for https://kancolle-arcade.net/ac/api/Event/hold
+pub mod hold {
+    use super::super::unknown_fields::{self, UnknownField, UnknownFields};
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Map, Result, Value};
+    use std::io::Read;
+
+    /// Parses an EventHold from the provided JSON reader.
+    /// Fails if not given a JSON object, or expected data structure does not match.
+    pub fn read_event_hold(reader: impl Read) -> Result<EventHold> {
+        unknown_fields::read_strict(reader)
+    }
+
+    /// Parses an EventHold from the provided JSON reader, also reporting any unknown fields.
+    /// Fails if not given a JSON object, or expected data structure does not match.
+    pub fn read_event_hold_lenient(reader: impl Read) -> Result<(EventHold, Vec<UnknownField>)> {
+        unknown_fields::read_lenient(reader)
+    }
+
+    /// The current or upcoming event (期間限定海域)
+    #[derive(Debug, Deserialize, Serialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct EventHold {
+        // TODO: Only seen PERIOD0 so far.
+        pub hold_status: String,
+        #[serde(with = "chrono::serde::ts_milliseconds")]
+        pub start_time: DateTime<Utc>,
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    impl UnknownFields for EventHold {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+        }
+    }
+}
+
+/// Module for importer for https://kancolle-arcade.net/ac/api/Event/info
+pub mod info {
+    use super::super::unknown_fields::{self, UnknownField, UnknownFields};
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Map, Result, Value};
+    use std::io::Read;
+
+    pub type EventInfo = Vec<EventStage>;
+
+    /// Parses an EventInfo from the provided JSON reader.
+    /// Fails if not given a JSON array, or expected data structure does not match.
+    pub fn read_event_info(reader: impl Read) -> Result<EventInfo> {
+        unknown_fields::read_strict(reader)
+    }
+
+    /// Parses an EventInfo from the provided JSON reader, also reporting any unknown fields.
+    /// Fails if not given a JSON array, or expected data structure does not match.
+    pub fn read_event_info_lenient(reader: impl Read) -> Result<(EventInfo, Vec<UnknownField>)> {
+        unknown_fields::read_lenient(reader)
+    }
+
+    /// A stage of the current event (期間限定海域), at one difficulty level
+    #[derive(Debug, Deserialize, Serialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct EventStage {
+        pub area_id: u16,
+        pub area_sub_id: u16,
+        pub area_spe_id: u16,
+        /// KOU (甲), OTU (乙) or HEI (丙)
+        pub level: String,
+        /// NORMAL or PERIOD_LAST have been seen
+        pub area_kind: String,
+        /// A sortie map stage picture, see images.rs
+        pub stage_image_name: String,
+        /// ？ for stages not yet open
+        pub stage_mission_name: String,
+        /// ？ for stages not yet open
+        pub stage_mission_info: String,
+        pub require_gp: u32,
+        pub limit_sec: u32,
+        pub reward_list: Vec<EventStageReward>,
+        /// e.g. SMALLBOX, UNKNOWN, NONE
+        pub stage_drop_item_info: Vec<String>,
+        pub sortie_limit: bool,
+        /// CLEAR, NOTCLEAR or NOOPEN have been seen
+        pub area_clear_state: String,
+        /// NORMAL or BREAK have been seen
+        pub military_gauge_status: String,
+        pub ene_military_gauge_val: u32,
+        pub military_gauge_left: u32,
+        /// The boss shown on the gauge, e.g. base_shuusekichi_hime, or empty
+        pub ene_military_gauge2d: String,
+        // TODO: Only seen NONE so far.
+        pub military2nd_gauge_status: String,
+        pub ene_military2nd_gauge_val: u32,
+        pub military2nd_gauge_left: u32,
+        pub ene_military2nd_gauge2d: String,
+        pub loop_count: u32,
+        pub period: u32,
+        pub all_lv_raid_flag: bool,
+        /// -1 has been seen
+        pub group_id: i32,
+        pub ufleet: bool,
+        pub tp_gauge: bool,
+        /// Sic
+        pub suportfleet: bool,
+        /// Absent on some stages, and only seen empty so far
+        pub gimmick_img1: Option<String>,
+        pub gimmick_img2: Option<String>,
+        pub gimmick_img3: Option<String>,
+        pub ffleet: bool,
+        /// Absent on most stages
+        pub sortie_limit_img: Option<String>,
+        /// Absent on most stages
+        pub ng_unit_img: Option<String>,
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    impl UnknownFields for EventStage {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+            self.reward_list
+                .unknown_fields(&format!("{path}.rewardList"), found);
+        }
+    }
+
+    #[derive(Debug, Deserialize, Serialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct EventStageReward {
+        /// FIRST or SECOND have been seen, absent for stages not yet open
+        pub reward_type: Option<String>,
+        pub data_id: u32,
+        /// e.g. RESULT_POINT, ROOM_ITEM_COIN, STRATEGY_POINT, NONE
+        pub kind: String,
+        pub value: u32,
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    impl UnknownFields for EventStageReward {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,52 @@
+use super::hold::*;
+use super::info::*;
+
+use chrono::TimeZone;
+use chrono_tz::Asia::Tokyo;
+use lazy_static_include::*;
+
+// https://kancolle-arcade.net/ac/api/Event/hold
+// https://kancolle-arcade.net/ac/api/Event/info
+lazy_static_include_bytes! {
+    EVENT_HOLD_LATEST => "tests/fixtures/latest/Event_hold.json",
+    EVENT_INFO_LATEST => "tests/fixtures/latest/Event_info.json",
+}
+
+#[test]
+fn parse_empty_event_hold_reader() {
+    read_event_hold(std::io::empty()).unwrap_err();
+}
+
+#[test]
+fn parse_fixture_event_hold_latest() {
+    let event_hold = read_event_hold(EVENT_HOLD_LATEST.as_ref()).unwrap();
+    assert_eq!(event_hold.hold_status, "PERIOD0");
+    assert_eq!(
+        event_hold.start_time,
+        Tokyo
+            .with_ymd_and_hms(2025, 4, 10, 7, 0, 0)
+            .unwrap()
+            .to_utc()
+    );
+}
+
+#[test]
+fn parse_empty_event_info_reader() {
+    read_event_info(std::io::empty()).unwrap_err();
+}
+
+#[test]
+fn parse_fixture_event_info_latest() {
+    let event_info = read_event_info(EVENT_INFO_LATEST.as_ref()).unwrap();
+    assert_eq!(event_info.len(), 9);
+
+    let stage = &event_info[0];
+    assert_eq!((stage.area_id, stage.area_sub_id), (1018, 1));
+    assert_eq!(stage.level, "HEI");
+    assert_eq!(stage.stage_image_name, "area_v5y7ayf25sf.png");
+    assert_eq!(stage.reward_list.len(), 3);
+    assert_eq!(stage.reward_list[1].kind, "RESULT_POINT");
+    assert!(stage.ng_unit_img.is_none());
+
+    assert!(event_info.iter().any(|stage| stage.ng_unit_img.is_some()));
+}
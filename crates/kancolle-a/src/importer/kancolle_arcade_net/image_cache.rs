@@ -0,0 +1,204 @@
+//! Module for a local mirror of images from https://kancolle-arcade.net/ac/resources/
+//!
+//! Images are stored under the cache directory at their ImageRef::resource_path, so the
+//! directory can be served as a replacement base URL. A `manifest.json` in the cache
+//! directory records what has been fetched, so existing images are not fetched again.
+//! Images without a resource_path, as their kind's directory is not known, cannot be cached.
+
+use super::{ImageKind, ImageRef, RESOURCES_BASE};
+use crate::{Error, Result};
+use chrono::{DateTime, Utc};
+use reqwest::Client as ReqwestClient;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The manifest file name, in the cache directory
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// The version of the manifest format we understand
+const MANIFEST_VERSION: u32 = 1;
+
+/// A fetched image, as recorded in the manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageCacheEntry {
+    pub kind: ImageKind,
+    /// Size in bytes
+    pub size: u64,
+    pub fetched: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImageCacheManifest {
+    version: u32,
+    /// Keyed by ImageRef::resource_path
+    images: BTreeMap<String, ImageCacheEntry>,
+}
+
+impl Default for ImageCacheManifest {
+    fn default() -> Self {
+        ImageCacheManifest {
+            version: MANIFEST_VERSION,
+            images: BTreeMap::new(),
+        }
+    }
+}
+
+/// What ImageCache::fetch_all did
+#[derive(Debug, Default)]
+pub struct ImageFetchReport {
+    pub fetched: Vec<ImageRef>,
+    /// Already in the cache
+    pub skipped: Vec<ImageRef>,
+    pub failed: Vec<(ImageRef, Error)>,
+}
+
+pub struct ImageCacheBuilder {
+    directory: PathBuf,
+    base_url: String,
+}
+
+impl ImageCacheBuilder {
+    /// An ImageCache in the given directory, which will be created if needed.
+    pub fn new(directory: impl Into<PathBuf>) -> ImageCacheBuilder {
+        ImageCacheBuilder {
+            directory: directory.into(),
+            base_url: RESOURCES_BASE.to_string(),
+        }
+    }
+
+    /// Fetch from the given base URL instead of RESOURCES_BASE, e.g. a local server for testing.
+    pub fn base_url(mut self, base_url: String) -> ImageCacheBuilder {
+        self.base_url = match base_url.ends_with('/') {
+            true => base_url,
+            false => base_url + "/",
+        };
+        self
+    }
+
+    /// Fails if there is an existing manifest we cannot read.
+    pub fn build(self) -> Result<ImageCache> {
+        fs::create_dir_all(&self.directory)?;
+
+        let manifest_path = self.directory.join(MANIFEST_FILE);
+        let manifest = match manifest_path.exists() {
+            true => {
+                let manifest: ImageCacheManifest =
+                    serde_json::from_reader(fs::File::open(&manifest_path)?)?;
+                if manifest.version != MANIFEST_VERSION {
+                    return Err(Error::UnsupportedVersion {
+                        data: "image cache manifest".to_string(),
+                        version: manifest.version,
+                    });
+                }
+                manifest
+            }
+            false => ImageCacheManifest::default(),
+        };
+
+        Ok(ImageCache {
+            directory: self.directory,
+            base_url: self.base_url,
+            client: ReqwestClient::new(),
+            manifest,
+        })
+    }
+}
+
+pub struct ImageCache {
+    directory: PathBuf,
+    base_url: String,
+    client: ReqwestClient,
+    manifest: ImageCacheManifest,
+}
+
+impl ImageCache {
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Where the given image is, or would be, stored. None if it has no resource_path.
+    /// Always below directory, as ImageRef rejects paths which would leave it.
+    pub fn local_path(&self, image: &ImageRef) -> Option<PathBuf> {
+        Some(self.directory.join(image.resource_path()?))
+    }
+
+    /// True if the given image has been fetched, and is still on disk.
+    pub fn contains(&self, image: &ImageRef) -> bool {
+        self.entry(image).is_some()
+            && self
+                .local_path(image)
+                .is_some_and(|local_path| local_path.exists())
+    }
+
+    /// The manifest entry for the given image, if it has been fetched
+    pub fn entry(&self, image: &ImageRef) -> Option<&ImageCacheEntry> {
+        self.manifest.images.get(&image.resource_path()?)
+    }
+
+    /// Fetch the given image unless we already have it.
+    /// Returns true if the image was fetched.
+    /// Fails for images without a resource_path.
+    /// Does not save the manifest, see save_manifest.
+    pub async fn fetch(&mut self, image: &ImageRef) -> Result<bool> {
+        let (Some(resource_path), Some(local_path)) =
+            (image.resource_path(), self.local_path(image))
+        else {
+            return Err(Error::UnknownImageLocation(image.kind()));
+        };
+        if self.contains(image) {
+            return Ok(false);
+        }
+
+        let bytes = self
+            .client
+            .get(format!("{}{resource_path}", self.base_url))
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+
+        if let Some(parent) = local_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&local_path, &bytes).await?;
+
+        self.manifest.images.insert(
+            resource_path,
+            ImageCacheEntry {
+                kind: image.kind(),
+                size: bytes.len() as u64,
+                fetched: Utc::now(),
+            },
+        );
+        Ok(true)
+    }
+
+    /// Fetch all the given images we don't already have, one at a time, then save the
+    /// manifest. Failures to fetch individual images are reported rather than stopping.
+    pub async fn fetch_all(
+        &mut self,
+        images: impl IntoIterator<Item = ImageRef>,
+    ) -> Result<ImageFetchReport> {
+        let mut report = ImageFetchReport::default();
+        for image in images {
+            match self.fetch(&image).await {
+                Ok(true) => report.fetched.push(image),
+                Ok(false) => report.skipped.push(image),
+                Err(error) => report.failed.push((image, error)),
+            }
+        }
+        self.save_manifest()?;
+        Ok(report)
+    }
+
+    pub fn save_manifest(&self) -> Result<()> {
+        let manifest = serde_json::to_string_pretty(&self.manifest)?;
+        fs::write(self.directory.join(MANIFEST_FILE), manifest)?;
+        Ok(())
+    }
+}
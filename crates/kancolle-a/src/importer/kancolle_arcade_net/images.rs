@@ -0,0 +1,252 @@
+//! Module for the images referenced by https://kancolle-arcade.net/ac/api/ responses
+//!
+//! The API gives image paths relative to a per-kind directory under
+//! https://kancolle-arcade.net/ac/resources/, e.g. `s/tc_1_xxx.jpg` for a card in the
+//! TcBook, which lives in https://kancolle-arcade.net/ac/resources/pictureBook/.
+
+use super::{
+    AreaCaptureStage, BlueprintShip, BookEquipment, BookShip, BookShipCardPage, Character,
+    DevelopEquipment, EquipSlot, EventStage, ListEquipment,
+};
+use crate::ship_attributes::DamageStatus;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path};
+use strum::Display;
+
+/// Where the images live on https://kancolle-arcade.net/
+pub const RESOURCES_BASE: &str = "https://kancolle-arcade.net/ac/resources/";
+
+/// What an image is of, which determines where it lives
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Display,
+)]
+pub enum ImageKind {
+    /// A card, `s/tc_NO_xxx.jpg`
+    Card,
+    /// A married card, also `s/tc_NO_xxx.jpg`
+    Married,
+    /// A status icon, `i/i_xxx_S.png`, where S shows the damage status, see ImageRef::damage_status
+    Status,
+    /// An equipment picture, `e/equip_NO_xxx.png`
+    Equipment,
+    /// An equipment slot icon, `equip_icon_KIND_xxx.png`
+    EquipmentIcon,
+    /// A sortie map stage picture, `area_xxx.png`
+    AreaStage,
+}
+
+impl ImageKind {
+    /// The directory under RESOURCES_BASE for this kind of image.
+    /// None where we have not confirmed where the site serves this kind from.
+    pub fn directory(&self) -> Option<&'static str> {
+        match self {
+            ImageKind::Card | ImageKind::Married | ImageKind::Equipment => Some("pictureBook/"),
+            ImageKind::Status => Some("chara/"),
+            ImageKind::EquipmentIcon | ImageKind::AreaStage => None,
+        }
+    }
+}
+
+/// True if the path is relative, and stays below where it is relative to.
+/// Backslashes are rejected as they are separators on Windows.
+fn is_contained(path: &str) -> bool {
+    !path.contains('\\')
+        && Path::new(path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+/// The status icon suffixes, in DamageStatus order
+const STATUS_SUFFIXES: [(&str, DamageStatus); 4] = [
+    ("_n.png", DamageStatus::Normal),
+    ("_bs.png", DamageStatus::LightDamage),
+    ("_bm.png", DamageStatus::ModerateDamage),
+    ("_bl.png", DamageStatus::HeavyDamage),
+];
+
+/// An image from the API, as its kind and the path the API gave
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(try_from = "ImageRefFields")]
+pub struct ImageRef {
+    kind: ImageKind,
+    path: String,
+}
+
+/// ImageRef's fields, so deserializing goes through ImageRef::new's checks
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImageRefFields {
+    kind: ImageKind,
+    path: String,
+}
+
+impl TryFrom<ImageRefFields> for ImageRef {
+    type Error = String;
+
+    fn try_from(fields: ImageRefFields) -> Result<Self, Self::Error> {
+        ImageRef::new(fields.kind, &fields.path)
+            .ok_or_else(|| format!("invalid image path \"{}\"", fields.path))
+    }
+}
+
+impl ImageRef {
+    /// None for the empty path the API uses for images not available to the player.
+    /// Also None for a path which would leave its kind's directory, e.g. `../x.png` or
+    /// `/x.png`, as the path is used both in URLs and in local mirrors like ImageCache.
+    pub fn new(kind: ImageKind, path: &str) -> Option<ImageRef> {
+        match path.is_empty() || !is_contained(path) {
+            true => None,
+            false => Some(ImageRef {
+                kind,
+                path: path.to_string(),
+            }),
+        }
+    }
+
+    pub fn kind(&self) -> ImageKind {
+        self.kind
+    }
+
+    /// The path as given by the API
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The path relative to RESOURCES_BASE, or any mirror of it.
+    /// None if the kind's directory is not known, see ImageKind::directory.
+    pub fn resource_path(&self) -> Option<String> {
+        Some(format!("{}{}", self.kind.directory()?, self.path))
+    }
+
+    /// The URL of this image on https://kancolle-arcade.net/
+    pub fn url(&self) -> Option<String> {
+        self.url_with_base(RESOURCES_BASE)
+    }
+
+    /// The URL of this image under the given base, which should end with a `/`.
+    pub fn url_with_base(&self, base: &str) -> Option<String> {
+        Some(format!("{base}{}", self.resource_path()?))
+    }
+
+    /// The damage status shown by a status icon
+    pub fn damage_status(&self) -> Option<DamageStatus> {
+        if self.kind != ImageKind::Status {
+            return None;
+        }
+        STATUS_SUFFIXES
+            .iter()
+            .find(|(suffix, _)| self.path.ends_with(suffix))
+            .map(|(_, damage_status)| damage_status.clone())
+    }
+
+    /// The status icon for the same ship in the given damage status
+    pub fn with_damage_status(&self, damage_status: DamageStatus) -> Option<ImageRef> {
+        if self.kind != ImageKind::Status {
+            return None;
+        }
+        let stem = STATUS_SUFFIXES
+            .iter()
+            .find_map(|(suffix, _)| self.path.strip_suffix(suffix))?;
+        let (suffix, _) = STATUS_SUFFIXES
+            .iter()
+            .find(|(_, status)| *status == damage_status)?;
+        ImageRef::new(ImageKind::Status, &format!("{stem}{suffix}"))
+    }
+}
+
+impl BookShip {
+    /// The card shown for this entry in the TcBook index
+    pub fn card_index_image(&self) -> Option<ImageRef> {
+        ImageRef::new(ImageKind::Card, &self.card_index_img)
+    }
+
+    pub fn married_images(&self) -> Vec<ImageRef> {
+        self.married_img
+            .iter()
+            .flatten()
+            .filter_map(|path| ImageRef::new(ImageKind::Married, path))
+            .collect()
+    }
+}
+
+impl BookShipCardPage {
+    /// The images of the owned cards on this page
+    pub fn card_images(&self) -> Vec<ImageRef> {
+        self.card_img_list
+            .iter()
+            .filter_map(|path| ImageRef::new(ImageKind::Card, path))
+            .collect()
+    }
+
+    pub fn status_images(&self) -> Vec<ImageRef> {
+        self.status_img
+            .iter()
+            .flatten()
+            .filter_map(|path| ImageRef::new(ImageKind::Status, path))
+            .collect()
+    }
+}
+
+impl Character {
+    /// The status icon for this character's current damage status
+    pub fn status_image(&self) -> Option<ImageRef> {
+        ImageRef::new(ImageKind::Status, &self.status_img)
+    }
+
+    pub fn card_image(&self) -> Option<ImageRef> {
+        ImageRef::new(ImageKind::Card, &self.tc_img)
+    }
+
+    /// The icons for each equipment slot, None for empty slots
+    pub fn slot_images(&self) -> Vec<Option<ImageRef>> {
+        self.slots.iter().map(|slot| slot.icon_image()).collect()
+    }
+}
+
+impl EquipSlot {
+    /// The icon for the kind of equipment in this slot
+    pub fn icon_image(&self) -> Option<ImageRef> {
+        ImageRef::new(ImageKind::EquipmentIcon, &self.img)
+    }
+}
+
+impl BookEquipment {
+    /// None for entries the player has not yet seen
+    pub fn equipment_image(&self) -> Option<ImageRef> {
+        ImageRef::new(ImageKind::Equipment, &self.equip_img)
+    }
+}
+
+impl ListEquipment {
+    pub fn icon_image(&self) -> Option<ImageRef> {
+        ImageRef::new(ImageKind::EquipmentIcon, &self.img)
+    }
+}
+
+impl AreaCaptureStage {
+    pub fn stage_image(&self) -> Option<ImageRef> {
+        ImageRef::new(ImageKind::AreaStage, &self.stage_image_name)
+    }
+}
+
+impl EventStage {
+    pub fn stage_image(&self) -> Option<ImageRef> {
+        ImageRef::new(ImageKind::AreaStage, &self.stage_image_name)
+    }
+}
+
+impl DevelopEquipment {
+    pub fn equipment_image(&self) -> Option<ImageRef> {
+        ImageRef::new(ImageKind::Equipment, &self.develop_equip_img)
+    }
+}
+
+impl BlueprintShip {
+    pub fn status_image(&self) -> Option<ImageRef> {
+        ImageRef::new(ImageKind::Status, &self.status_img)
+    }
+}
+
+#[cfg(test)]
+mod tests;
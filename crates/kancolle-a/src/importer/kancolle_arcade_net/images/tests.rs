@@ -0,0 +1,116 @@
+use super::*;
+use crate::importer::kancolle_arcade_net::{
+    read_area_capture_info, read_equipbook, read_equiplist, read_event_info,
+};
+
+use lazy_static_include::*;
+
+lazy_static_include_bytes! {
+    AREA_CAPTURE_INFO_LATEST => "tests/fixtures/latest/Area_captureInfo.json",
+    EQUIP_BOOK_LATEST => "tests/fixtures/latest/EquipBook_info.json",
+    EQUIP_LIST_LATEST => "tests/fixtures/latest/EquipList_info.json",
+    EVENT_INFO_LATEST => "tests/fixtures/latest/Event_info.json",
+}
+
+#[test]
+fn test_image_urls() {
+    let card = ImageRef::new(ImageKind::Card, "s/tc_1_d7ju63kolamj.jpg").unwrap();
+    assert_eq!(
+        card.url().unwrap(),
+        "https://kancolle-arcade.net/ac/resources/pictureBook/s/tc_1_d7ju63kolamj.jpg"
+    );
+    assert_eq!(
+        card.url_with_base("http://localhost:8080/").unwrap(),
+        "http://localhost:8080/pictureBook/s/tc_1_d7ju63kolamj.jpg"
+    );
+    assert_eq!(card.damage_status(), None);
+
+    let equipment = ImageRef::new(ImageKind::Equipment, "e/equip_26_girzaumtr1o.png").unwrap();
+    assert_eq!(
+        equipment.resource_path().unwrap(),
+        "pictureBook/e/equip_26_girzaumtr1o.png"
+    );
+
+    // Not yet confirmed where these are served from
+    let icon = ImageRef::new(ImageKind::EquipmentIcon, "equip_icon_6_a2e6kq8r27wg.png").unwrap();
+    assert_eq!(icon.resource_path(), None);
+    assert_eq!(icon.url(), None);
+    let stage = ImageRef::new(ImageKind::AreaStage, "area_rprx04hjnl.png").unwrap();
+    assert_eq!(stage.url_with_base("http://localhost:8080/"), None);
+
+    assert_eq!(ImageRef::new(ImageKind::Card, ""), None);
+}
+
+#[test]
+fn test_image_paths_contained() {
+    for path in [
+        "../tc_1_d7ju63kolamj.jpg",
+        "s/../../tc_1_d7ju63kolamj.jpg",
+        "/etc/passwd",
+        "s\\..\\..\\tc_1_d7ju63kolamj.jpg",
+    ] {
+        assert_eq!(ImageRef::new(ImageKind::Card, path), None, "{path}");
+    }
+    assert!(ImageRef::new(ImageKind::Card, "./s/tc_1_d7ju63kolamj.jpg").is_some());
+
+    // Deserializing checks the path too
+    let card = ImageRef::new(ImageKind::Card, "s/tc_1_d7ju63kolamj.jpg").unwrap();
+    let json = serde_json::to_string(&card).unwrap();
+    assert_eq!(serde_json::from_str::<ImageRef>(&json).unwrap(), card);
+    let json = json.replace("s/tc_1", "../tc_1");
+    assert!(serde_json::from_str::<ImageRef>(&json).is_err());
+}
+
+#[test]
+fn test_status_images() {
+    let status = ImageRef::new(ImageKind::Status, "i/i_d7ju63kolamj_n.png").unwrap();
+    assert_eq!(
+        status.url().unwrap(),
+        "https://kancolle-arcade.net/ac/resources/chara/i/i_d7ju63kolamj_n.png"
+    );
+    assert_eq!(status.damage_status(), Some(DamageStatus::Normal));
+
+    let damaged = status
+        .with_damage_status(DamageStatus::ModerateDamage)
+        .unwrap();
+    assert_eq!(damaged.path(), "i/i_d7ju63kolamj_bm.png");
+    assert_eq!(damaged.damage_status(), Some(DamageStatus::ModerateDamage));
+    assert_eq!(
+        damaged.with_damage_status(DamageStatus::Normal),
+        Some(status)
+    );
+
+    let card = ImageRef::new(ImageKind::Card, "s/tc_1_d7ju63kolamj.jpg").unwrap();
+    assert_eq!(card.with_damage_status(DamageStatus::HeavyDamage), None);
+}
+
+#[test]
+fn test_fixture_images() {
+    let equip_book = read_equipbook(EQUIP_BOOK_LATEST.as_ref()).unwrap();
+    let equipment = equip_book[0].equipment_image().unwrap();
+    assert_eq!(equipment.kind(), ImageKind::Equipment);
+    assert!(equipment.path().starts_with("e/equip_"));
+    // Unseen entries have no image
+    assert!(equip_book
+        .iter()
+        .filter(|equipment| !equipment.is_known())
+        .all(|equipment| equipment.equipment_image().is_none()));
+
+    let equip_list = read_equiplist(EQUIP_LIST_LATEST.as_ref()).unwrap();
+    assert_eq!(
+        equip_list.equip_list[0].icon_image(),
+        ImageRef::new(ImageKind::EquipmentIcon, "equip_icon_6_a2e6kq8r27wg.png")
+    );
+
+    let area_capture_info = read_area_capture_info(AREA_CAPTURE_INFO_LATEST.as_ref()).unwrap();
+    assert_eq!(
+        area_capture_info[0].stage_image(),
+        ImageRef::new(ImageKind::AreaStage, "area_rprx04hjnl.png")
+    );
+
+    let event_info = read_event_info(EVENT_INFO_LATEST.as_ref()).unwrap();
+    assert_eq!(
+        event_info[0].stage_image(),
+        ImageRef::new(ImageKind::AreaStage, "area_v5y7ayf25sf.png")
+    );
+}
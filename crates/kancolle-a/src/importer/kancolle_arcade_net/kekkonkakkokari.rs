@@ -1,28 +1,41 @@
 /// Module for importer for https://kancolle-a.sega.jp/players/kekkonkakkokari/kanmusu_list.json
 pub mod kanmusu_list {
+    use super::super::unknown_fields::{self, UnknownField, UnknownFields};
     use chrono::NaiveDate;
-    use serde::Deserialize;
-    use serde_json::Result;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Map, Result, Value};
     use std::io::Read;
 
+    #[cfg(not(target_arch = "wasm32"))]
     use lazy_static_include::*;
 
+    #[cfg(not(target_arch = "wasm32"))]
     lazy_static_include_bytes! {
         pub(crate) KANMUSU => "src/importer/kancolle_arcade_net/kekkonkakkokari/kanmusu_list.json",
     }
 
+    // wasm32 has no filesystem to lazy-load from in debug builds, so always embed.
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) static KANMUSU: &[u8] = include_bytes!("kekkonkakkokari/kanmusu_list.json");
+
     // ケッコンカッコカリ, aka 結婚（仮）
     pub(crate) type KekkonKakkoKariList = Vec<KekkonKakkoKari>;
 
     /// Parses a PlacePlaces from the provided JSON reader.
     /// Fails if not given a JSON array, or expected data structure does not match.
     pub(crate) fn read_kekkonkakkokarilist(reader: impl Read) -> Result<KekkonKakkoKariList> {
-        let result: KekkonKakkoKariList = serde_json::from_reader(reader)?;
-        Ok(result)
+        unknown_fields::read_strict(reader)
     }
 
-    #[derive(Debug, Deserialize, Clone)]
-    #[serde(deny_unknown_fields)]
+    /// Parses a KekkonKakkoKariList from the provided JSON reader, also reporting any unknown fields.
+    /// Fails if not given a JSON array, or expected data structure does not match.
+    pub(crate) fn read_kekkonkakkokarilist_lenient(
+        reader: impl Read,
+    ) -> Result<(KekkonKakkoKariList, Vec<UnknownField>)> {
+        unknown_fields::read_lenient(reader)
+    }
+
+    #[derive(Debug, Deserialize, Serialize, Clone)]
     pub struct KekkonKakkoKari {
         pub id: u32,
         pub web_id: u32,
@@ -32,22 +45,31 @@ pub mod kanmusu_list {
         pub category: String,
         #[serde(with = "kekkonkakkokari_date_format")]
         pub start_time: NaiveDate, // Technically 7am JST on this day, AFAIK.
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    impl UnknownFields for KekkonKakkoKari {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+        }
     }
 
     mod kekkonkakkokari_date_format {
         // https://serde.rs/custom-date-format.html
         use chrono::NaiveDate;
-        use serde::{self, Deserialize, Deserializer /* , Serializer*/};
+        use serde::{self, Deserialize, Deserializer, Serializer};
 
         const FORMAT: &str = "%Y/%m/%d";
 
-        // pub fn serialize<S>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error>
-        // where
-        //     S: Serializer,
-        // {
-        //     let s = format!("{}", date.format(FORMAT));
-        //     serializer.serialize_str(&s)
-        // }
+        pub fn serialize<S>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let s = format!("{}", date.format(FORMAT));
+            serializer.serialize_str(&s)
+        }
 
         pub fn deserialize<'de, D>(deserializer: D) -> Result<NaiveDate, D::Error>
         where
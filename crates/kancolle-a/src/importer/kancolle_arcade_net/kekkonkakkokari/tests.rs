@@ -40,3 +40,14 @@ fn parse_fixture_kekkonkakkokari_info_20240623() {
         NaiveDate::from_ymd_opt(2024, 6, 13).unwrap()
     );
 }
+
+#[test]
+fn serialize_kekkonkakkokari_roundtrip() {
+    let kekkonkakkokari = read_kekkonkakkokarilist(KANMUSU_2024_06_23.as_ref()).unwrap();
+    let json = serde_json::to_string(&kekkonkakkokari[0..1]).unwrap();
+    assert!(json.contains(r#""start_time":"2018/02/16""#));
+
+    let roundtrip = read_kekkonkakkokarilist(json.as_bytes()).unwrap();
+    assert_eq!(roundtrip[0].start_time, kekkonkakkokari[0].start_time);
+    assert_eq!(roundtrip[0].name, kekkonkakkokari[0].name);
+}
@@ -0,0 +1,70 @@
+/// Module for importer for https://kancolle-arcade.net/ac/api/Personal/basicInfo
+pub mod basic_info {
+    use super::super::unknown_fields::{self, UnknownField, UnknownFields};
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Map, Result, Value};
+    use std::io::Read;
+
+    /// Parses a PersonalBasicInfo from the provided JSON reader.
+    /// Fails if not given a JSON object, or expected data structure does not match.
+    pub fn read_personal_basic_info(reader: impl Read) -> Result<PersonalBasicInfo> {
+        unknown_fields::read_strict(reader)
+    }
+
+    /// Parses a PersonalBasicInfo from the provided JSON reader, also reporting any unknown fields.
+    /// Fails if not given a JSON object, or expected data structure does not match.
+    pub fn read_personal_basic_info_lenient(
+        reader: impl Read,
+    ) -> Result<(PersonalBasicInfo, Vec<UnknownField>)> {
+        unknown_fields::read_lenient(reader)
+    }
+
+    /// The admiral's level and holdings
+    #[derive(Debug, Deserialize, Serialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct PersonalBasicInfo {
+        pub admiral_name: String,
+        pub fuel: u32,
+        pub ammo: u32,
+        pub steel: u32,
+        pub bauxite: u32,
+        /// 高速修復材
+        pub bucket: u32,
+        pub level: u16,
+        /// 家具コイン
+        pub room_item_coin: u32,
+        /// 勲章
+        pub material_medal: u32,
+        /// "--" when unranked
+        pub result_point: String,
+        /// "--" when unranked
+        pub rank: String,
+        pub title_id: u32,
+        /// The cap on each of fuel, ammo, steel and bauxite
+        pub material_max: u32,
+        /// 戦略ポイント
+        pub strategy_point: u32,
+        /// 甲種勲章
+        pub kou_medal: u32,
+        // TODO: Only seen empty so far.
+        pub kou_medal_hist: Vec<Value>,
+        /// 改装設計図 Mk.B
+        pub blue_print_mk_b: u32,
+        /// -1 seen, meaning unknown
+        pub saiun_num: i32,
+        pub le_trophy1: u32,
+        pub le_trophy2: u32,
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    impl UnknownFields for PersonalBasicInfo {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,23 @@
+use super::basic_info::*;
+
+use lazy_static_include::*;
+
+// https://kancolle-arcade.net/ac/api/Personal/basicInfo
+lazy_static_include_bytes! {
+    BASIC_INFO_LATEST => "tests/fixtures/latest/Personal_basicInfo.json",
+}
+
+#[test]
+fn parse_empty_personal_basic_info_reader() {
+    read_personal_basic_info(std::io::empty()).unwrap_err();
+}
+
+#[test]
+fn parse_fixture_personal_basic_info_latest() {
+    let basic_info = read_personal_basic_info(BASIC_INFO_LATEST.as_ref()).unwrap();
+    assert_eq!(basic_info.level, 101);
+    assert_eq!(basic_info.strategy_point, 28498);
+    assert_eq!(basic_info.material_medal, 45);
+    assert!(basic_info.fuel <= basic_info.material_max);
+    assert_eq!(basic_info.rank, "--");
+}
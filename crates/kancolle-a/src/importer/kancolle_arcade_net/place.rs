@@ -1,7 +1,8 @@
 /// Module for importer for https://kancolle-arcade.net/ac/api/Place/districts
 pub mod districts {
-    use serde::Deserialize;
-    use serde_json::Result;
+    use super::super::unknown_fields::{self, UnknownField, UnknownFields};
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Map, Result, Value};
     use std::io::Read;
 
     pub type PlaceDistricts = Vec<PlaceTopRegion>;
@@ -9,13 +10,19 @@ pub mod districts {
     /// Parses a PlaceDistricts from the provided JSON reader.
     /// Fails if not given a JSON array, or expected data structure does not match.
     pub fn read_place_districts(reader: impl Read) -> Result<PlaceDistricts> {
-        let result: PlaceDistricts = serde_json::from_reader(reader)?;
-        Ok(result)
+        unknown_fields::read_strict(reader)
     }
 
-    #[derive(Debug, Deserialize)]
+    /// Parses a PlaceDistricts from the provided JSON reader, also reporting any unknown fields.
+    /// Fails if not given a JSON array, or expected data structure does not match.
+    pub fn read_place_districts_lenient(
+        reader: impl Read,
+    ) -> Result<(PlaceDistricts, Vec<UnknownField>)> {
+        unknown_fields::read_lenient(reader)
+    }
+
+    #[derive(Debug, Deserialize, Serialize)]
     #[serde(rename_all = "camelCase")]
-    #[serde(deny_unknown_fields)]
     pub struct PlaceTopRegion {
         // If this is actually a standard list, I can't find the source.
         // But these specific divisions (e.g., merged HOKKAIDO_TOHOKU) show
@@ -26,23 +33,43 @@ pub mod districts {
         pub top_region_enum: String,
         pub name: String,
         pub prefecture_beans: Vec<PlacePrefectureBean>,
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
     }
 
-    #[derive(Debug, Deserialize)]
+    impl UnknownFields for PlaceTopRegion {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+            self.prefecture_beans
+                .unknown_fields(&format!("{path}.prefectureBeans"), found);
+        }
+    }
+
+    #[derive(Debug, Deserialize, Serialize)]
     #[serde(rename_all = "camelCase")]
-    #[serde(deny_unknown_fields)]
     pub struct PlacePrefectureBean {
         pub region_enum: String,
         pub name: String,
         /// JIS X 0401 都道府県コード: 01..47 (Also ISO 3166-2:JP)
         pub jis_code: u8,
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    impl UnknownFields for PlacePrefectureBean {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+        }
     }
 }
 
 /// Module for importer for https://kancolle-arcade.net/ac/api/Place/places
 pub mod places {
-    use serde::Deserialize;
-    use serde_json::Result;
+    use super::super::unknown_fields::{self, UnknownField, UnknownFields};
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Map, Result, Value};
     use std::io::Read;
 
     pub type PlacePlaces = Vec<Place>;
@@ -50,15 +77,21 @@ pub mod places {
     /// Parses a PlacePlaces from the provided JSON reader.
     /// Fails if not given a JSON array, or expected data structure does not match.
     pub fn read_place_places(reader: impl Read) -> Result<PlacePlaces> {
-        let result: PlacePlaces = serde_json::from_reader(reader)?;
-        Ok(result)
+        unknown_fields::read_strict(reader)
+    }
+
+    /// Parses a PlacePlaces from the provided JSON reader, also reporting any unknown fields.
+    /// Fails if not given a JSON array, or expected data structure does not match.
+    pub fn read_place_places_lenient(
+        reader: impl Read,
+    ) -> Result<(PlacePlaces, Vec<UnknownField>)> {
+        unknown_fields::read_lenient(reader)
     }
 
     // TODO: This struct should also be used for placesFromHere handling, but there's
     // a few differences that need to be handled.
-    #[derive(Debug, Deserialize)]
+    #[derive(Debug, Deserialize, Serialize)]
     #[serde(rename_all = "camelCase")]
-    #[serde(deny_unknown_fields)]
     pub struct Place {
         pub id: u32,
         pub distance: String, // No data in places output.
@@ -75,6 +108,15 @@ pub mod places {
         pub latitude: String,  // Float-in-string.
         pub longitude: String, // Float-in-string.
         pub zoom_level: u8,    // Google Maps API zoom level.
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    impl UnknownFields for Place {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+        }
     }
 }
 
@@ -0,0 +1,119 @@
+/// Module for importer for https://kancolle-arcade.net/ac/api/Quest/info
+pub mod info {
+    use super::super::unknown_fields::{self, UnknownField, UnknownFields};
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Serialize};
+    use serde_json::{Map, Result, Value};
+    use std::io::Read;
+
+    /// Parses a QuestInfo from the provided JSON reader.
+    /// Fails if not given a JSON object, or expected data structure does not match.
+    pub fn read_quest_info(reader: impl Read) -> Result<QuestInfo> {
+        unknown_fields::read_strict(reader)
+    }
+
+    /// Parses a QuestInfo from the provided JSON reader, also reporting any unknown fields.
+    /// Fails if not given a JSON object, or expected data structure does not match.
+    pub fn read_quest_info_lenient(reader: impl Read) -> Result<(QuestInfo, Vec<UnknownField>)> {
+        unknown_fields::read_lenient(reader)
+    }
+
+    /// The admiral's current quests (任務)
+    #[derive(Debug, Deserialize, Serialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct QuestInfo {
+        // TODO: Only seen false so far.
+        pub limit: bool,
+        pub quest_list: Vec<Quest>,
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    impl UnknownFields for QuestInfo {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+            self.quest_list
+                .unknown_fields(&format!("{path}.questList"), found);
+        }
+    }
+
+    #[derive(Debug, Deserialize, Serialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Quest {
+        pub quest_name: String,
+        /// 0 daily, 1 weekly, 2 one-off have been seen
+        pub quest_category: u16,
+        pub quest_kind: u16,
+        /// Marked up with **bold**, and may contain "\r\n"
+        pub quest_description: String,
+        pub quest_group: u16,
+        pub quest_sort_index: u16,
+        pub quest_id: u32,
+        /// The target for quest_current_value
+        pub quest_norma: u32,
+        pub quest_reward_list: Vec<QuestReward>,
+        pub quest_current_value: u32,
+        pub quest_contract_status: u16,
+        pub quest_progress_status: u16,
+        /// Absent for quests with no time limit
+        #[serde(default, with = "chrono::serde::ts_milliseconds_option")]
+        pub quest_start_time: Option<DateTime<Utc>>,
+        /// Absent for quests with no time limit
+        #[serde(default, with = "chrono::serde::ts_milliseconds_option")]
+        pub quest_end_time: Option<DateTime<Utc>>,
+        pub quest_detail_message_list: Vec<QuestDetailMessage>,
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    impl UnknownFields for Quest {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+            self.quest_reward_list
+                .unknown_fields(&format!("{path}.questRewardList"), found);
+            self.quest_detail_message_list
+                .unknown_fields(&format!("{path}.questDetailMessageList"), found);
+        }
+    }
+
+    #[derive(Debug, Deserialize, Serialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct QuestReward {
+        /// e.g. STRATEGY_POINT, ROOM_ITEM_COIN, EQUIPMENT
+        pub reward_kind: String,
+        pub reward_value: u32,
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    impl UnknownFields for QuestReward {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+        }
+    }
+
+    /// Progress on one part of a Quest, e.g. the sea areas to sortie to
+    #[derive(Debug, Deserialize, Serialize, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct QuestDetailMessage {
+        pub group_no: u16,
+        pub quest_current_value: u32,
+        pub quest_norma: u32,
+        pub info_list: Vec<String>,
+        /// Any fields not listed above, see read_*_lenient
+        #[serde(flatten)]
+        pub extra: Map<String, Value>,
+    }
+
+    impl UnknownFields for QuestDetailMessage {
+        fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+            self.extra.unknown_fields(path, found);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
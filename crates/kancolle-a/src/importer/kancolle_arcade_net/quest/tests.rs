@@ -0,0 +1,45 @@
+use super::info::*;
+
+use chrono::TimeZone;
+use chrono_tz::Asia::Tokyo;
+use lazy_static_include::*;
+
+// https://kancolle-arcade.net/ac/api/Quest/info
+lazy_static_include_bytes! {
+    QUEST_INFO_LATEST => "tests/fixtures/latest/Quest_info.json",
+}
+
+#[test]
+fn parse_empty_quest_info_reader() {
+    read_quest_info(std::io::empty()).unwrap_err();
+}
+
+#[test]
+fn parse_fixture_quest_info_latest() {
+    let quest_info = read_quest_info(QUEST_INFO_LATEST.as_ref()).unwrap();
+    assert!(!quest_info.limit);
+    assert_eq!(quest_info.quest_list.len(), 10);
+
+    let quest = &quest_info.quest_list[0];
+    assert_eq!(quest.quest_name, "敵艦隊を撃退せよ！");
+    assert_eq!(quest.quest_id, 37942);
+    assert_eq!(quest.quest_norma, 3);
+    assert_eq!(quest.quest_reward_list[0].reward_kind, "STRATEGY_POINT");
+    assert_eq!(quest.quest_reward_list[0].reward_value, 5);
+    // Daily quests end at 7am Japan time
+    assert_eq!(
+        quest.quest_end_time.unwrap(),
+        Tokyo
+            .with_ymd_and_hms(2025, 6, 1, 7, 0, 0)
+            .unwrap()
+            .to_utc()
+    );
+
+    let quest = &quest_info.quest_list[1];
+    assert_eq!(quest.quest_detail_message_list[0].info_list.len(), 5);
+
+    // No time limit
+    let quest = &quest_info.quest_list[9];
+    assert!(quest.quest_start_time.is_none());
+    assert!(quest.quest_end_time.is_none());
+}
@@ -0,0 +1,361 @@
+//! Compare API responses against the data we already know how to handle.
+//!
+//! Used to find out what changed after a game update: new or missing fields, fields
+//! which changed type, new values for fields holding one of a fixed set of strings, and
+//! TcBook pages we don't know the source of.
+
+use super::{
+    read_aime_campaign_info_lenient, read_area_capture_info_lenient, read_blueprintlist_lenient,
+    read_characterlist_lenient, read_equipbook_lenient, read_equiplist_lenient,
+    read_event_hold_lenient, read_event_info_lenient, read_kekkonkakkokarilist_lenient,
+    read_personal_basic_info_lenient, read_place_districts_lenient, read_place_places_lenient,
+    read_quest_info_lenient, read_tclist_lenient, ApiEndpoint, BookShipCardPageSource,
+    CardPageSources, TcBook, UnknownKind,
+};
+use serde_json::{Result, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use strum::Display;
+
+/// The type of a JSON value, as far as schema changes are concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum JsonType {
+    Null,
+    Bool,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl From<&Value> for JsonType {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Null => JsonType::Null,
+            Value::Bool(_) => JsonType::Bool,
+            Value::Number(_) => JsonType::Number,
+            Value::String(_) => JsonType::String,
+            Value::Array(_) => JsonType::Array,
+            Value::Object(_) => JsonType::Object,
+        }
+    }
+}
+
+/// A difference between the known and live data for an endpoint.
+///
+/// Paths merge all array elements, e.g. `[].cardList[].priority`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaChange {
+    /// A field not seen in the known data, with an example value.
+    NewField { path: String, value: Value },
+    /// A field in the known data which is no longer present.
+    MissingField { path: String },
+    /// A field which now holds a different type of value.
+    TypeChanged {
+        path: String,
+        known: Vec<JsonType>,
+        found: JsonType,
+    },
+    /// A new value for a field which holds one of a fixed set of strings.
+    NewValue { path: String, value: String },
+    /// The live data no longer parses into this crate's structs.
+    ParseFailed { reason: String },
+    /// A TcBook page we don't know the source of; see BookShipCardPageSource.
+    UnknownCardPageSource {
+        book_no: u16,
+        ship_name: String,
+        page: usize,
+    },
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use SchemaChange::*;
+        match self {
+            NewField { path, value } => write!(f, "+ {path}: {value}"),
+            MissingField { path } => write!(f, "- {path}"),
+            TypeChanged { path, known, found } => {
+                let known = known
+                    .iter()
+                    .map(JsonType::to_string)
+                    .collect::<Vec<_>>()
+                    .join("|");
+                write!(f, "~ {path}: {known} -> {found}")
+            }
+            NewValue { path, value } => write!(f, "+ {path} = \"{value}\""),
+            ParseFailed { reason } => write!(f, "! {reason}"),
+            UnknownCardPageSource {
+                book_no,
+                ship_name,
+                page,
+            } => write!(
+                f,
+                "? TcBook {book_no} {ship_name} page {page}: unknown source"
+            ),
+        }
+    }
+}
+
+/// Fields which hold one of a fixed set of strings, so new values are worth reporting.
+fn enum_paths(endpoint: &ApiEndpoint) -> &'static [&'static str] {
+    use ApiEndpoint::*;
+    match endpoint {
+        CharacterListInfo => &["[].damageStatus", "[].slotDisp[]"],
+        AreaCaptureInfo => &["[].stageDropItemInfo[]"],
+        _ => &[],
+    }
+}
+
+/// All the paths in a JSON document, with what was seen at each of them.
+#[derive(Default)]
+struct Shape {
+    types: BTreeMap<String, BTreeSet<JsonType>>,
+    examples: BTreeMap<String, Value>,
+    strings: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Shape {
+    fn new(value: &Value, enum_paths: &[&str]) -> Self {
+        let mut shape = Shape::default();
+        shape.visit(String::new(), value, enum_paths);
+        shape
+    }
+
+    fn visit(&mut self, path: String, value: &Value, enum_paths: &[&str]) {
+        match value {
+            Value::Array(items) => {
+                for item in items {
+                    self.visit(format!("{path}[]"), item, enum_paths);
+                }
+            }
+            Value::Object(fields) => {
+                for (key, field) in fields {
+                    self.visit(format!("{path}.{key}"), field, enum_paths);
+                }
+            }
+            Value::String(string) if enum_paths.contains(&path.as_str()) => {
+                self.strings
+                    .entry(path.clone())
+                    .or_default()
+                    .insert(string.clone());
+            }
+            _ => (),
+        }
+        self.types
+            .entry(path.clone())
+            .or_default()
+            .insert(value.into());
+        self.examples.entry(path).or_insert_with(|| value.clone());
+    }
+
+    fn contains(&self, path: &str) -> bool {
+        self.types.contains_key(path)
+    }
+}
+
+/// The path containing the given path.
+fn parent(path: &str) -> &str {
+    match path.strip_suffix("[]") {
+        Some(parent) => parent,
+        None => &path[..path.rfind('.').unwrap_or(0)],
+    }
+}
+
+/// Turn a specific path like `[3].cardList[1]` into a shape path like `[].cardList[]`.
+fn shape_path(path: &str) -> String {
+    let mut result = String::with_capacity(path.len());
+    let mut in_index = false;
+    for c in path.chars() {
+        match c {
+            '[' => in_index = true,
+            ']' => {
+                in_index = false;
+                result.push_str("[]");
+            }
+            _ if in_index => (),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+fn shape_changes(known: &Shape, live: &Shape) -> Vec<SchemaChange> {
+    let mut changes = vec![];
+
+    // Only report the outermost new or missing field. Array elements are not fields:
+    // an array which was empty in one set of data tells us nothing about its contents.
+    for (path, live_types) in &live.types {
+        let Some(known_types) = known.types.get(path) else {
+            if !path.ends_with("[]") && known.contains(parent(path)) {
+                changes.push(SchemaChange::NewField {
+                    path: path.clone(),
+                    value: live.examples[path].clone(),
+                });
+            }
+            continue;
+        };
+
+        // Null is taken to mean an optional value, which could be any type.
+        let known_types: Vec<JsonType> = known_types
+            .iter()
+            .copied()
+            .filter(|&json_type| json_type != JsonType::Null)
+            .collect();
+        if !known_types.is_empty() {
+            for &found in live_types {
+                if found != JsonType::Null && !known_types.contains(&found) {
+                    changes.push(SchemaChange::TypeChanged {
+                        path: path.clone(),
+                        known: known_types.clone(),
+                        found,
+                    });
+                }
+            }
+        }
+    }
+
+    for path in known.types.keys() {
+        if !live.contains(path) && !path.ends_with("[]") && live.contains(parent(path)) {
+            changes.push(SchemaChange::MissingField { path: path.clone() });
+        }
+    }
+
+    for (path, live_strings) in &live.strings {
+        let known_strings = known.strings.get(path);
+        for string in live_strings {
+            if !known_strings.is_some_and(|known_strings| known_strings.contains(string)) {
+                changes.push(SchemaChange::NewValue {
+                    path: path.clone(),
+                    value: string.clone(),
+                });
+            }
+        }
+    }
+
+    changes
+}
+
+fn unknown_card_page_sources(
+    tcbook: &TcBook,
+    card_page_sources: &CardPageSources,
+) -> Vec<SchemaChange> {
+    tcbook
+        .iter()
+        .flat_map(|ship| {
+            (0..ship.card_list.len())
+                .filter(|&page| {
+                    ship.source_with(card_page_sources, page as u16)
+                        == BookShipCardPageSource::Unknown
+                })
+                .map(|page| SchemaChange::UnknownCardPageSource {
+                    book_no: ship.book_no,
+                    ship_name: ship.ship_name.clone(),
+                    page,
+                })
+        })
+        .collect()
+}
+
+/// Check the live data against this crate's structs for the endpoint, if it has any.
+fn struct_changes(
+    endpoint: &ApiEndpoint,
+    known: &str,
+    live: &str,
+    card_page_sources: &CardPageSources,
+) -> Vec<SchemaChange> {
+    use ApiEndpoint::*;
+    let mut changes = vec![];
+    let unknown_fields = match endpoint {
+        TcBookInfo => read_tclist_lenient(live.as_bytes()).map(|(tcbook, unknown_fields)| {
+            // Only report pages which are new since the known data.
+            let known_unknowns = read_tclist_lenient(known.as_bytes())
+                .map(|(known_tcbook, _)| {
+                    unknown_card_page_sources(&known_tcbook, card_page_sources)
+                })
+                .unwrap_or_default();
+            changes.extend(
+                unknown_card_page_sources(&tcbook, card_page_sources)
+                    .into_iter()
+                    .filter(|change| !known_unknowns.contains(change)),
+            );
+            unknown_fields
+        }),
+        BlueprintListInfo => read_blueprintlist_lenient(live.as_bytes()).map(|(_, fields)| fields),
+        CharacterListInfo => read_characterlist_lenient(live.as_bytes()).map(|(_, fields)| fields),
+        EquipBookInfo => read_equipbook_lenient(live.as_bytes()).map(|(_, fields)| fields),
+        EquipListInfo => read_equiplist_lenient(live.as_bytes()).map(|(_, fields)| fields),
+        PersonalBasicInfo => {
+            read_personal_basic_info_lenient(live.as_bytes()).map(|(_, fields)| fields)
+        }
+        QuestInfo => read_quest_info_lenient(live.as_bytes()).map(|(_, fields)| fields),
+        EventHold => read_event_hold_lenient(live.as_bytes()).map(|(_, fields)| fields),
+        EventInfo => read_event_info_lenient(live.as_bytes()).map(|(_, fields)| fields),
+        AreaCaptureInfo => {
+            read_area_capture_info_lenient(live.as_bytes()).map(|(_, fields)| fields)
+        }
+        AimeCampaignInfo => {
+            read_aime_campaign_info_lenient(live.as_bytes()).map(|(_, fields)| fields)
+        }
+        KanmusuList => read_kekkonkakkokarilist_lenient(live.as_bytes()).map(|(_, fields)| fields),
+        PlaceDistricts => read_place_districts_lenient(live.as_bytes()).map(|(_, fields)| fields),
+        PlacePlaces => read_place_places_lenient(live.as_bytes()).map(|(_, fields)| fields),
+        _ => return changes,
+    };
+
+    match unknown_fields {
+        Ok(unknown_fields) => changes.extend(unknown_fields.into_iter().map(|field| {
+            match (field.kind, field.value) {
+                (UnknownKind::Value, Value::String(value)) => SchemaChange::NewValue {
+                    path: shape_path(&field.path),
+                    value,
+                },
+                (_, value) => SchemaChange::NewField {
+                    path: shape_path(&field.path),
+                    value,
+                },
+            }
+        })),
+        Err(error) => changes.push(SchemaChange::ParseFailed {
+            reason: error.to_string(),
+        }),
+    }
+
+    changes
+}
+
+/// Compares the live JSON text of an endpoint against known JSON text, e.g. a fixture
+/// from the last time we looked. TcBook pages are identified per card_page_sources.
+///
+/// Fails only if either text is not JSON at all.
+pub fn schema_changes(
+    endpoint: &ApiEndpoint,
+    known: &str,
+    live: &str,
+    card_page_sources: &CardPageSources,
+) -> Result<Vec<SchemaChange>> {
+    let enum_paths = enum_paths(endpoint);
+    let known_shape = Shape::new(&serde_json::from_str(known)?, enum_paths);
+    let live_shape = Shape::new(&serde_json::from_str(live)?, enum_paths);
+
+    let mut changes = shape_changes(&known_shape, &live_shape);
+
+    // Fields and values missing from our structs are usually also new since the known data.
+    for change in struct_changes(endpoint, known, live, card_page_sources) {
+        let duplicate = match &change {
+            SchemaChange::NewField { path, .. } => changes.iter().any(|existing| {
+                matches!(existing, SchemaChange::NewField { path: existing, .. } if existing == path)
+            }),
+            SchemaChange::NewValue { .. } => changes.contains(&change),
+            _ => false,
+        };
+        if !duplicate {
+            changes.push(change);
+        }
+    }
+
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests;
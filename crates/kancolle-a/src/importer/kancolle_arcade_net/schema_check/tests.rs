@@ -0,0 +1,166 @@
+use super::*;
+
+use lazy_static_include::*;
+
+lazy_static_include_str! {
+    TCBOOK_LATEST => "tests/fixtures/latest/TcBook_info.json",
+    CHARLIST_LATEST => "tests/fixtures/latest/CharacterList_info.json",
+    AREA_CAPTURE_LATEST => "tests/fixtures/latest/Area_captureInfo.json",
+}
+
+#[test]
+fn test_shape_path() {
+    assert_eq!(shape_path(""), "");
+    assert_eq!(shape_path("[3].cardList[12].x"), "[].cardList[].x");
+    assert_eq!(parent("[].cardList[].x"), "[].cardList[]");
+    assert_eq!(parent("[].cardList[]"), "[].cardList");
+    assert_eq!(parent(".key"), "");
+}
+
+#[test]
+fn test_schema_unchanged() {
+    for (endpoint, data) in [
+        (ApiEndpoint::TcBookInfo, &*TCBOOK_LATEST),
+        (ApiEndpoint::CharacterListInfo, &*CHARLIST_LATEST),
+        (ApiEndpoint::AreaCaptureInfo, &*AREA_CAPTURE_LATEST),
+    ] {
+        assert_eq!(
+            schema_changes(&endpoint, data, data, CardPageSources::bundled()).unwrap(),
+            vec![]
+        );
+    }
+}
+
+#[test]
+fn test_schema_changes() {
+    let known = r#"[{"a": 1, "b": "x", "c": null, "d": [], "damageStatus": "NORMAL"}]"#;
+    let live = r#"[{"a": "1", "c": 2, "d": [{"e": 1}], "f": true, "damageStatus": "X_BREAK"}]"#;
+
+    // Not CharacterListInfo, so no struct checks or enum values.
+    assert_eq!(
+        schema_changes(
+            &ApiEndpoint::ExerciseInfo,
+            known,
+            live,
+            CardPageSources::bundled()
+        )
+        .unwrap(),
+        vec![
+            SchemaChange::TypeChanged {
+                path: "[].a".to_string(),
+                known: vec![JsonType::Number],
+                found: JsonType::String,
+            },
+            SchemaChange::NewField {
+                path: "[].f".to_string(),
+                value: Value::Bool(true),
+            },
+            SchemaChange::MissingField {
+                path: "[].b".to_string(),
+            },
+        ]
+    );
+
+    let changes = schema_changes(
+        &ApiEndpoint::CharacterListInfo,
+        known,
+        live,
+        CardPageSources::bundled(),
+    )
+    .unwrap();
+    assert!(changes.contains(&SchemaChange::NewValue {
+        path: "[].damageStatus".to_string(),
+        value: "X_BREAK".to_string(),
+    }));
+    assert!(matches!(
+        changes.last().unwrap(),
+        SchemaChange::ParseFailed { .. }
+    ));
+}
+
+#[test]
+fn test_schema_struct_changes() {
+    let live = TCBOOK_LATEST.replacen(r#""bookNo": 1,"#, r#""bookNo": 1, "newField": 2,"#, 1);
+    let changes = schema_changes(
+        &ApiEndpoint::TcBookInfo,
+        &TCBOOK_LATEST,
+        &live,
+        CardPageSources::bundled(),
+    )
+    .unwrap();
+    // Reported once, although both the known data and the structs lack it.
+    assert_eq!(
+        changes,
+        vec![SchemaChange::NewField {
+            path: "[].newField".to_string(),
+            value: 2.into(),
+        }]
+    );
+    assert_eq!(changes[0].to_string(), "+ [].newField: 2");
+}
+
+#[test]
+fn test_schema_struct_new_value() {
+    let live = TCBOOK_LATEST.replacen(r#""shipType": "戦艦""#, r#""shipType": "宇宙戦艦""#, 1);
+    assert_ne!(live, *TCBOOK_LATEST);
+    let changes = schema_changes(
+        &ApiEndpoint::TcBookInfo,
+        &TCBOOK_LATEST,
+        &live,
+        CardPageSources::bundled(),
+    )
+    .unwrap();
+    // Named, rather than failing to parse
+    assert_eq!(
+        changes,
+        vec![SchemaChange::NewValue {
+            path: "[].shipType".to_string(),
+            value: "宇宙戦艦".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_schema_unknown_card_page_source() {
+    // A new page for 赤城, which the bundled CardPageSources doesn't know yet.
+    let mut live: Value = serde_json::from_str(&TCBOOK_LATEST).unwrap();
+    let 赤城 = live
+        .as_array_mut()
+        .unwrap()
+        .iter_mut()
+        .find(|ship| ship["bookNo"] == 6)
+        .unwrap();
+    let card_list = 赤城["cardList"].as_array_mut().unwrap();
+    card_list.push(card_list.last().unwrap().clone());
+    let pages = card_list.len();
+    let live = live.to_string();
+
+    let changes = schema_changes(
+        &ApiEndpoint::TcBookInfo,
+        &TCBOOK_LATEST,
+        &live,
+        CardPageSources::bundled(),
+    )
+    .unwrap();
+    assert_eq!(changes.len(), pages - 1);
+    assert!(changes.iter().all(|change| matches!(
+        change,
+        SchemaChange::UnknownCardPageSource { book_no: 6, .. }
+    )));
+
+    // Not reported once the CardPageSources given know the page.
+    let sources = vec!["\"Christmas\""; pages - 1].join(", ");
+    let overlay = format!(
+        r#"{{"version": 1, "books": [{{"bookNo": 6, "ships": "赤城, 赤城改", "sources": [{sources}]}}]}}"#
+    );
+    let mut card_page_sources = CardPageSources::bundled().clone();
+    card_page_sources.overlay(CardPageSources::from_reader(overlay.as_bytes()).unwrap());
+    let changes = schema_changes(
+        &ApiEndpoint::TcBookInfo,
+        &TCBOOK_LATEST,
+        &live,
+        &card_page_sources,
+    )
+    .unwrap();
+    assert_eq!(changes, vec![]);
+}
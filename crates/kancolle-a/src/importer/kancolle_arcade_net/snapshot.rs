@@ -0,0 +1,220 @@
+//! Module for a local store of snapshots of https://kancolle-arcade.net/ac/api/ data
+//!
+//! Each snapshot is a directory in the store, named for the time it was taken in UTC,
+//! e.g. `2025-06-01T071530Z`, holding each endpoint's data under ApiEndpoint::file_name.
+//! Directories named for just a date, like the dated test fixtures, are also snapshots,
+//! taken at midnight UTC on that date. Other directories are ignored.
+
+use super::{ApiEndpoint, Client};
+use crate::ships::ShipsBuilder;
+use crate::{Error, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// The format of snapshot directory names
+const SNAPSHOT_FORMAT: &str = "%Y-%m-%dT%H%M%SZ";
+
+/// The format of date-only snapshot directory names
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// A snapshot in the store
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    taken_at: DateTime<Utc>,
+    directory: PathBuf,
+}
+
+impl Snapshot {
+    pub fn taken_at(&self) -> DateTime<Utc> {
+        self.taken_at
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// True if the snapshot has data for the endpoint
+    pub fn contains(&self, endpoint: &ApiEndpoint) -> bool {
+        endpoint
+            .file_name()
+            .is_some_and(|file_name| self.directory.join(file_name).is_file())
+    }
+
+    /// The endpoint's data, if the snapshot has it
+    pub fn reader(&self, endpoint: &ApiEndpoint) -> Result<Option<Box<dyn Read>>> {
+        let Some(file_name) = endpoint.file_name() else {
+            return Ok(None);
+        };
+        match File::open(self.directory.join(file_name)) {
+            Ok(file) => Ok(Some(Box::new(BufReader::new(file)))),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Sets the builder's TcBook, BlueprintList, CharacterList and kekkon list data from
+    /// the snapshot, for those it has. Data the snapshot lacks is left as it was.
+    pub fn apply(&self, mut builder: ShipsBuilder) -> Result<ShipsBuilder> {
+        if let Some(reader) = self.reader(&ApiEndpoint::TcBookInfo)? {
+            builder = builder.book_from_reader(reader);
+        }
+        if let Some(reader) = self.reader(&ApiEndpoint::BlueprintListInfo)? {
+            builder = builder.blueprint_from_reader(reader);
+        }
+        if let Some(reader) = self.reader(&ApiEndpoint::CharacterListInfo)? {
+            builder = builder.character_from_reader(reader);
+        }
+        if let Some(reader) = self.reader(&ApiEndpoint::KanmusuList)? {
+            builder = builder.kekkon_from_reader(reader);
+        }
+        Ok(builder)
+    }
+}
+
+/// What SnapshotStore::fetch did
+#[derive(Debug)]
+pub struct SnapshotFetchReport {
+    /// None if every endpoint failed
+    pub snapshot: Option<Snapshot>,
+    pub failed: Vec<(ApiEndpoint, Error)>,
+}
+
+pub struct SnapshotStore {
+    directory: PathBuf,
+}
+
+impl SnapshotStore {
+    /// The store need not exist yet, it is created by the first save.
+    pub fn new(directory: impl Into<PathBuf>) -> SnapshotStore {
+        SnapshotStore {
+            directory: directory.into(),
+        }
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Every snapshot in the store, oldest first.
+    pub fn snapshots(&self) -> Result<Vec<Snapshot>> {
+        if !self.directory.is_dir() {
+            return Ok(vec![]);
+        }
+        let mut snapshots = vec![];
+        for entry in fs::read_dir(&self.directory)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let Some(taken_at) = entry.file_name().to_str().and_then(parse_snapshot_name) else {
+                continue;
+            };
+            snapshots.push(Snapshot {
+                taken_at,
+                directory: entry.path(),
+            });
+        }
+        snapshots.sort_by_key(|snapshot| snapshot.taken_at);
+        Ok(snapshots)
+    }
+
+    pub fn latest(&self) -> Result<Option<Snapshot>> {
+        Ok(self.snapshots()?.pop())
+    }
+
+    /// The latest snapshot taken at or before the given time
+    pub fn at(&self, time: DateTime<Utc>) -> Result<Option<Snapshot>> {
+        Ok(self
+            .snapshots()?
+            .into_iter()
+            .rev()
+            .find(|snapshot| snapshot.taken_at <= time))
+    }
+
+    /// Saves the data as a snapshot taken at the given time, to the second.
+    /// The snapshot only appears in the store once all the data is written.
+    /// Fails without saving anything if an endpoint has no ApiEndpoint::file_name.
+    pub fn save(
+        &self,
+        taken_at: DateTime<Utc>,
+        data: Vec<(ApiEndpoint, Box<dyn Read>)>,
+    ) -> Result<Snapshot> {
+        let name = taken_at.format(SNAPSHOT_FORMAT).to_string();
+        let directory = self.directory.join(&name);
+        if directory.exists() {
+            return Err(Error::SnapshotExists(name));
+        }
+        let data = data
+            .into_iter()
+            .map(|(endpoint, reader)| match endpoint.file_name() {
+                Some(file_name) => Ok((file_name, reader)),
+                None => Err(Error::NoFileName(format!("{endpoint:?}"))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let partial = self.directory.join(format!(".{name}.partial"));
+        if partial.exists() {
+            fs::remove_dir_all(&partial)?;
+        }
+        fs::create_dir_all(&partial)?;
+        for (file_name, mut reader) in data {
+            let mut file = File::create(partial.join(file_name))?;
+            std::io::copy(&mut reader, &mut file)?;
+        }
+        fs::rename(&partial, &directory)?;
+
+        Ok(Snapshot {
+            // Truncated to the second, as it would be when listed
+            taken_at: parse_snapshot_name(&name).unwrap(),
+            directory,
+        })
+    }
+
+    /// Fetches the endpoints and saves whatever succeeded as a snapshot, taken now.
+    /// Endpoints with no ApiEndpoint::file_name are reported as failed, and not fetched.
+    pub async fn fetch(
+        &self,
+        client: &Client,
+        endpoints: &[ApiEndpoint],
+    ) -> Result<SnapshotFetchReport> {
+        let (endpoints, unnamed): (Vec<_>, Vec<_>) = endpoints
+            .iter()
+            .cloned()
+            .partition(|endpoint| endpoint.file_name().is_some());
+        let mut failed: Vec<_> = unnamed
+            .into_iter()
+            .map(|endpoint| {
+                let error = Error::NoFileName(format!("{endpoint:?}"));
+                (endpoint, error)
+            })
+            .collect();
+
+        let taken_at = Utc::now();
+        let responses = client.fetch_snapshot(&endpoints).await;
+
+        let mut data = vec![];
+        for (endpoint, response) in endpoints.into_iter().zip(responses) {
+            match response {
+                Ok(reader) => data.push((endpoint, reader)),
+                Err(error) => failed.push((endpoint, error)),
+            }
+        }
+
+        let snapshot = match data.is_empty() {
+            true => None,
+            false => Some(self.save(taken_at, data)?),
+        };
+        Ok(SnapshotFetchReport { snapshot, failed })
+    }
+}
+
+fn parse_snapshot_name(name: &str) -> Option<DateTime<Utc>> {
+    if let Ok(taken_at) = NaiveDateTime::parse_from_str(name, SNAPSHOT_FORMAT) {
+        return Some(taken_at.and_utc());
+    }
+    NaiveDate::parse_from_str(name, DATE_FORMAT)
+        .ok()
+        .map(|date| date.and_hms_opt(0, 0, 0).unwrap().and_utc())
+}
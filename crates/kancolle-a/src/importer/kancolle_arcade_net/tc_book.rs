@@ -1,7 +1,9 @@
 //! Module for importer for https://kancolle-arcade.net/ac/api/TcBook/info
 
-use serde::Deserialize;
-use serde_json::Result;
+use super::unknown_fields::{self, UnknownField, UnknownFields};
+use crate::ship_attributes::ShipType;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Result, Value};
 use std::sync::OnceLock;
 use std::{collections::HashMap, io::Read};
 use strum::{AsRefStr, Display, EnumDiscriminants, EnumString, VariantNames};
@@ -11,18 +13,24 @@ pub type TcBook = Vec<BookShip>;
 /// Parses a TcBook from the provided JSON reader.
 /// Fails if not given a JSON array, or expected data structure does not match.
 pub(crate) fn read_tclist(tcbook_reader: impl Read) -> Result<TcBook> {
-    let result: TcBook = serde_json::from_reader(tcbook_reader)?;
-    Ok(result)
+    unknown_fields::read_strict(tcbook_reader)
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+/// Parses a TcBook from the provided JSON reader, also reporting any unknown fields.
+/// Fails if not given a JSON array, or expected data structure does not match.
+pub(crate) fn read_tclist_lenient(tcbook_reader: impl Read) -> Result<(TcBook, Vec<UnknownField>)> {
+    unknown_fields::read_lenient(tcbook_reader)
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
-#[serde(deny_unknown_fields)]
 pub struct BookShip {
     pub book_no: u16,
     pub ship_class: Option<String>,
     pub ship_class_index: Option<i16>,
-    pub ship_type: String,
+    /// Empty in the source data for ships not yet acquired
+    #[serde(with = "optional_ship_type")]
+    pub ship_type: Option<ShipType>,
     pub ship_model_num: String,
     pub ship_name: String,
     pub card_index_img: String,
@@ -32,14 +40,26 @@ pub struct BookShip {
     pub lv: u16,
     pub is_married: Option<Vec<bool>>,
     pub married_img: Option<Vec<String>>,
+    /// Any fields not listed above, see read_*_lenient
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl UnknownFields for BookShip {
+    fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+        self.extra.unknown_fields(path, found);
+        self.ship_type
+            .unknown_fields(&format!("{path}.shipType"), found);
+        self.card_list
+            .unknown_fields(&format!("{path}.cardList"), found);
+    }
 }
 
 // Notes for future functions
-// * Card images (s/tc_NO_xxx.jpg) and equipment images (e/equip_NO_xxx.png) live in https://kancolle-arcade.net/ac/resources/pictureBook/
-// * Status images (i/i_xxx.png) live in https://kancolle-arcade.net/ac/resources/chara/
+// * Card, equipment and status image paths are resolved to URLs in images.rs
 // ** Status images end with _n, _bs, _bm, or _bl. (Not sure if there's one for sunk?)
 
-#[derive(Debug, PartialEq, Eq, Clone, EnumDiscriminants)]
+#[derive(Debug, PartialEq, Eq, Clone, EnumDiscriminants, Deserialize, Serialize)]
 #[strum_discriminants(derive(EnumString, Display, AsRefStr, VariantNames))]
 pub enum BookShipCardPageSource {
     Unknown, // Fallback
@@ -64,343 +84,205 @@ pub enum BookShipCardPageSource {
     OriginalIllustration2(bool, bool),
 }
 
-static BOOK_SHIP_SOURCES: OnceLock<HashMap<u16, Vec<BookShipCardPageSource>>> = OnceLock::new();
-
-fn init_book_ship_sources() {
-    use BookShipCardPageSource::*;
-    BOOK_SHIP_SOURCES.get_or_init(|| {
-        let mut sources = HashMap::new();
-
-        // TODO: A clean way to panic on double-entry. (Or even better data format.)
-        // 陸奥, 陸奥改: https://kancolle-a.sega.jp/players/information/2309_yukata_season.html
-        sources.insert(2, vec![Yukata]);
-        // 雪風改: https://kancolle-a.sega.jp/players/information/200811_1.html
-        // XXX: Needs special handling! 改-only. Special 夏のお嬢さんmode.
-        sources.insert(5, vec![Swimsuit]);
-        // 赤城, 赤城改: https://kancolle-a.sega.jp/players/information/211230_2.html
-        sources.insert(6, vec![SundayBest]);
-        // 加賀, 加賀改:
-        // * https://kancolle-a.sega.jp/players/information/211230_2.html
-        // * (OriginalIllustration) (改) https://kancolle-a.sega.jp/players/information/2206_seaarea_event12_detail.html
-        sources.insert(7, vec![SundayBest, OriginalIllustration1(true)]);
-        // 島風, 島風改:
-        // * (DecisiveBattle) https://kancolle-a.sega.jp/players/information/190508_1.html
-        // * (OriginalIllustration)
-        // ** (改) https://kancolle-a.sega.jp/players/information/200901_2.html
-        // ** (改) https://kancolle-a.sega.jp/players/information/210409_1.html
-        sources.insert(10, vec![DecisiveBattle, OriginalIllustration2(true, true)]);
-        // 敷波, 敷波改: (改) https://kancolle-a.sega.jp/players/information/211005_1.html
-        sources.insert(18, vec![OriginalIllustration1(true)]);
-        // 大井: https://kancolle-a.sega.jp/players/information/2306_rainy_season.html
-        sources.insert(19, vec![RainySeason]);
-        // 鳳翔, 鳳翔改: (改) https://kancolle-a.sega.jp/players/information/2303_seaarea_event13_detail.html
-        sources.insert(25, vec![OriginalIllustration1(true)]);
-        // 扶桑, 扶桑改: https://kancolle-a.sega.jp/players/information/2406_rainy_season.html
-        sources.insert(26, vec![RainySeason]);
-        // 球磨, 球磨改: https://kancolle-a.sega.jp/players/information/2212_xmas.html
-        sources.insert(39, vec![Christmas]);
-        // 多摩, 多摩改: https://kancolle-a.sega.jp/players/information/211005_1.html
-        sources.insert(40, vec![PacificSaury]);
-        // 由良, 由良改: https://kancolle-a.sega.jp/players/information/2309_yukata_season.html
-        sources.insert(45, vec![Yukata]);
-        // 川内, 川内改: (改) https://kancolle-a.sega.jp/players/information/2302_card_tsuika.html
-        sources.insert(46, vec![OriginalIllustration1(true)]);
-        // 那珂, 那珂改: (改) https://kancolle-a.sega.jp/players/information/190914_1.html
-        sources.insert(48, vec![OriginalIllustration1(true)]);
-        // 最上:
-        // * https://kancolle-a.sega.jp/players/information/2401_seaarea_event14_detail_report.html (スリガオ海峡突入mode)
-        // * https://kancolle-a.sega.jp/players/information/2306_rainy_season.html
-        sources.insert(51, vec![DecisiveBattle, RainySeason]);
-        // 加古, 加古改: https://kancolle-a.sega.jp/players/information/2406_rainy_season.html
-        sources.insert(53, vec![RainySeason]);
-        // 朧, 朧改:
-        // * https://kancolle-a.sega.jp/players/information/2209_sauryfestival.html
-        // * (OriginalIllustration) (改) https://kancolle-a.sega.jp/players/information/2404_spring_seaarea_detail.html
-        sources.insert(67, vec![Fishing, OriginalIllustration1(true)]);
-        // 曙, 曙改:
-        // * https://kancolle-a.sega.jp/players/information/2209_sauryfestival.html
-        // * https://kancolle-a.sega.jp/players/information/211005_1.html
-        // * (OriginalIllustration)
-        // ** (改) https://kancolle-a.sega.jp/players/information/211027_1.html
-        // ** (改) https://kancolle-a.sega.jp/players/information/210409_1.html
-        sources.insert(
-            68,
-            vec![PacificSaury, Fishing, OriginalIllustration2(true, true)],
-        );
-        // 漣, 漣改:
-        // * https://kancolle-a.sega.jp/players/information/2209_sauryfestival.html
-        // * (OriginalIllustration) (改) https://kancolle-a.sega.jp/players/information/2404_spring_seaarea_detail.html
-        sources.insert(69, vec![PacificSaury, OriginalIllustration1(true)]);
-        // 潮, 潮改:
-        // * https://kancolle-a.sega.jp/players/information/2201_valentine.html
-        // * https://kancolle-a.sega.jp/players/information/2209_sauryfestival.html
-        // * (OriginalIllustration) (改) https://kancolle-a.sega.jp/players/information/211027_1.html
-        sources.insert(
-            70,
-            vec![Valentine, PacificSaury, OriginalIllustration1(true)],
-        );
-        // 暁, 暁改: (改) https://kancolle-a.sega.jp/players/information/210409_1.html
-        sources.insert(71, vec![OriginalIllustration1(true)]);
-        // 雷, 雷改: https://kancolle-a.sega.jp/players/information/2309_yukata_season.html
-        sources.insert(73, vec![Yukata]);
-        // 電, 電改: https://kancolle-a.sega.jp/players/information/2309_yukata_season.html
-        sources.insert(74, vec![Yukata]);
-        // 白露, 白露改: (改) https://kancolle-a.sega.jp/players/information/180316_1.html
-        sources.insert(79, vec![OriginalIllustration1(true)]);
-        // 村雨:
-        // * (OriginalIllustration)
-        // ** (改) https://kancolle-a.sega.jp/players/information/2206_seaarea_event12_detail.html
-        // ** (改) https://kancolle-a.sega.jp/players/information/180316_1.html
-        sources.insert(81, vec![OriginalIllustration2(true, true)]);
-        // 夕立, 夕立改:
-        // * https://kancolle-a.sega.jp/players/information/201026_1.html
-        // * (OriginalIllustration) (改) https://kancolle-a.sega.jp/players/information/190425_2.html
-        sources.insert(82, vec![Halloween, OriginalIllustration1(true)]);
-        // 朝潮, 朝潮改: https://kancolle-a.sega.jp/players/information/201013_1.html
-        sources.insert(85, vec![Halloween]);
-        // 大潮, 大潮改: https://kancolle-a.sega.jp/players/information/2310_sauryfestival.html
-        sources.insert(86, vec![Fishing]);
-        // 満潮, 満潮改: https://kancolle-a.sega.jp/players/information/211005_1.html
-        sources.insert(87, vec![PacificSaury]);
-        // 祥鳳, 祥鳳改:
-        // * https://kancolle-a.sega.jp/players/information/2206_rainy_season_addition.html
-        // * https://kancolle-a.sega.jp/players/information/2209_sauryfestival.html
-        sources.insert(94, vec![RainySeason, PacificSaury]);
-        // 大井改: https://kancolle-a.sega.jp/players/information/2306_rainy_season.html
-        sources.insert(97, vec![RainySeason]);
-        // 最上改:
-        // * https://kancolle-a.sega.jp/players/information/2401_seaarea_event14_detail_report.html (スリガオ海峡突入mode)
-        // * https://kancolle-a.sega.jp/players/information/2306_rainy_season.html
-        sources.insert(101, vec![DecisiveBattle, RainySeason]);
-        // 伊勢改: https://kancolle-a.sega.jp/players/information/170420_1.html
-        sources.insert(102, vec![OriginalIllustration1(false)]);
-        // 日向改: https://kancolle-a.sega.jp/players/information/170420_1.html
-        sources.insert(103, vec![OriginalIllustration1(false)]);
-        // 翔鶴, 翔鶴改: https://kancolle-a.sega.jp/players/information/211214_1.html
-        sources.insert(106, vec![Christmas]);
-        // 瑞鶴: https://kancolle-a.sega.jp/players/information/211214_1.html
-        sources.insert(107, vec![Christmas]);
-        // 瑞鶴改: https://kancolle-a.sega.jp/players/information/211214_1.html
-        sources.insert(108, vec![Christmas]);
-        // 夕張, 夕張改: https://kancolle-a.sega.jp/players/information/2205_rainy_season.html
-        sources.insert(111, vec![RainySeason]);
-        // 大井改二: https://kancolle-a.sega.jp/players/information/2306_rainy_season.html
-        sources.insert(114, vec![RainySeason]);
-        // 鈴谷: https://kancolle-a.sega.jp/players/information/191203_1.html
-        sources.insert(124, vec![Christmas]);
-        // 熊野: https://kancolle-a.sega.jp/players/information/191203_1.html
-        sources.insert(125, vec![Christmas]);
-        // 鈴谷改: https://kancolle-a.sega.jp/players/information/191203_1.html
-        sources.insert(129, vec![Christmas]);
-        // 熊野改: https://kancolle-a.sega.jp/players/information/191203_1.html
-        sources.insert(130, vec![Christmas]);
-        // 大和:
-        // * (SundayBest) https://kancolle-a.sega.jp/players/information/211230_2.html
-        // * (Swimsuit) https://kancolle-a.sega.jp/players/information/190813_1.html
-        sources.insert(131, vec![SundayBest, Swimsuit]);
-        // 夕雲, 夕雲改:
-        // * https://kancolle-a.sega.jp/players/information/2310_sauryfestival.html
-        // * (OriginalIllustration) (改) https://kancolle-a.sega.jp/players/information/190307_2.html
-        sources.insert(133, vec![PacificSaury, OriginalIllustration1(true)]);
-        // 巻雲, 巻雲改: (改) https://kancolle-a.sega.jp/players/information/190307_2.html
-        sources.insert(134, vec![OriginalIllustration1(true)]);
-        // 長波, 長波改: (改) https://kancolle-a.sega.jp/players/information/190307_2.html
-        sources.insert(135, vec![OriginalIllustration1(true)]);
-        // 大和改:
-        // * (Swimsuit) https://kancolle-a.sega.jp/players/information/190813_1.html
-        // * (SundayBest) https://kancolle-a.sega.jp/players/information/211230_2.html
-        sources.insert(136, vec![SundayBest, Swimsuit]);
-        // 衣笠改二: https://kancolle-a.sega.jp/players/information/210205_1.html
-        sources.insert(142, vec![Valentine]);
-        // 夕立改二:
-        // * https://kancolle-a.sega.jp/players/information/201027_1.html
-        // * https://kancolle-a.sega.jp/players/information/2205_rainy_season.html
-        // * (OriginalIllustration)
-        // ** https://kancolle-a.sega.jp/players/information/180316_1.html
-        // ** https://kancolle-a.sega.jp/players/information/171124_3.html
-        sources.insert(
-            144,
-            vec![RainySeason, Halloween, OriginalIllustration2(false, false)],
-        );
-        // 時雨改二:
-        // * https://kancolle-a.sega.jp/players/information/2401_seaarea_event14_detail_report.html (スリガオ海峡突入mode)
-        // * https://kancolle-a.sega.jp/players/information/190805_1.html
-        // * https://kancolle-a.sega.jp/players/information/211005_1.html
-        // * (OriginalIllustration)
-        // ** https://kancolle-a.sega.jp/players/information/210409_1.html
-        // ** https://kancolle-a.sega.jp/players/information/180316_1.html
-        sources.insert(
-            145,
-            vec![
-                DecisiveBattle,
-                Swimsuit,
-                PacificSaury,
-                OriginalIllustration2(false, false),
-            ],
-        );
-        // 榛名改二: https://kancolle-a.sega.jp/players/information/190722_1.html
-        sources.insert(151, vec![Swimsuit]);
-        // 卯月, 卯月改:
-        // * https://kancolle-a.sega.jp/players/information/210205_1.html
-        // * (OriginalIllustration) (改) https://kancolle-a.sega.jp/players/information/190425_2.html
-        sources.insert(165, vec![Valentine, OriginalIllustration1(true)]);
-        // 磯風, 磯風改: https://kancolle-a.sega.jp/players/information/211005_1.html
-        sources.insert(167, vec![PacificSaury]);
-        // 浦風, 浦風改: https://kancolle-a.sega.jp/players/information/2309_yukata_season.html
-        sources.insert(168, vec![Yukata]);
-        // 浜風, 浜風改: https://kancolle-a.sega.jp/players/information/2309_yukata_season.html
-        sources.insert(170, vec![Yukata]);
-        // 天津風, 天津風改: (改) https://kancolle-a.sega.jp/players/information/200901_2.html
-        sources.insert(181, vec![OriginalIllustration1(true)]);
-        // 大淀, 大淀改:
-        // * https://kancolle-a.sega.jp/players/information/210907_1.html
-        // * (OriginalIllustration) https://kancolle-a.sega.jp/players/information/190508_1.html
-        sources.insert(183, vec![Swimsuit, OriginalIllustration1(false)]);
-        // 大鯨: https://kancolle-a.sega.jp/players/information/2209_sauryfestival.html
-        sources.insert(184, vec![PacificSaury]);
-        // 龍鳳: https://kancolle-a.sega.jp/players/information/2209_sauryfestival.html
-        sources.insert(185, vec![PacificSaury]);
-        // 明石改: https://kancolle-a.sega.jp/players/information/190914_1.html
-        sources.insert(187, vec![OriginalIllustration1(false)]);
-        // 春雨, 春雨改:
-        // * (OriginalIllustration)
-        // ** https://kancolle-a.sega.jp/players/information/180316_1.html
-        // ** (改) https://kancolle-a.sega.jp/players/information/200623_1.html
-        sources.insert(205, vec![OriginalIllustration2(false, true)]);
-        // 潮改二:
-        // * https://kancolle-a.sega.jp/players/information/2201_valentine.html
-        // * https://kancolle-a.sega.jp/players/information/2209_sauryfestival.html
-        sources.insert(207, vec![Valentine, PacificSaury]);
-        // 早霜, 早霜改:
-        // * https://kancolle-a.sega.jp/players/information/2406_rainy_season.html
-        // * (OriginalIllustration) (改) https://kancolle-a.sega.jp/players/information/190307_2.html
-        sources.insert(209, vec![RainySeason, OriginalIllustration1(true)]);
-        // 清霜, 清霜改:
-        // * https://kancolle-a.sega.jp/players/information/2306_rainy_season.html
-        // * (OriginalIllustration) (改) https://kancolle-a.sega.jp/players/information/190307_2.html
-        sources.insert(210, vec![RainySeason, OriginalIllustration1(true)]);
-        // 扶桑改二: https://kancolle-a.sega.jp/players/information/2406_rainy_season.html
-        sources.insert(211, vec![RainySeason]);
-        // 朝雲, 朝雲改: https://kancolle-a.sega.jp/players/information/2401_seaarea_event14_detail_report.html (スリガオ海峡突入mode)
-        sources.insert(213, vec![DecisiveBattle]);
-        // 山雲, 山雲改: https://kancolle-a.sega.jp/players/information/2401_seaarea_event14_detail_report.html (スリガオ海峡突入mode)
-        sources.insert(214, vec![DecisiveBattle]);
-        // 野分, 野分改: https://kancolle-a.sega.jp/players/information/2210_halloween.html
-        sources.insert(215, vec![Halloween]);
-        // 秋月, 秋月改: https://kancolle-a.sega.jp/players/information/2309_yukata_season.html
-        sources.insert(221, vec![Yukata]);
-        // 初月, 初月改: https://kancolle-a.sega.jp/players/information/2310_sauryfestival.html
-        sources.insert(223, vec![PacificSaury]);
-        // 高波, 高波改: https://kancolle-a.sega.jp/players/information/2206_rainy_season_addition.html
-        sources.insert(224, vec![RainySeason]);
-        // U-511, U-511改: https://kancolle-a.sega.jp/players/information/210914_1.html (里帰り水着mode)
-        sources.insert(231, vec![Swimsuit]);
-        // 呂500: https://kancolle-a.sega.jp/players/information/210914_1.html
-        sources.insert(236, vec![Swimsuit]);
-        // Warspite, Warspite改:
-        // * https://kancolle-a.sega.jp/players/information/2212_haregimode.html
-        // * (OriginalIllustration)
-        // ** https://kancolle-a.sega.jp/players/information/190914_1.html
-        // ** (改) https://kancolle-a.sega.jp/players/information/200623_1.html
-        sources.insert(239, vec![SundayBest, OriginalIllustration2(false, true)]);
-        // Littorio: https://kancolle-a.sega.jp/players/information/200728_1.html
-        sources.insert(241, vec![Swimsuit]);
-        // Roma: https://kancolle-a.sega.jp/players/information/211005_1.html
-        sources.insert(242, vec![Halloween]);
-        // Libeccio, Libeccio改: https://kancolle-a.sega.jp/players/information/190729_1.html
-        sources.insert(243, vec![Swimsuit]);
-        // 秋津洲: https://kancolle-a.sega.jp/players/information/2207_join_akitusima_swim.html
-        sources.insert(245, vec![Swimsuit]);
-        // Italia: https://kancolle-a.sega.jp/players/information/200728_1.html
-        sources.insert(246, vec![Swimsuit]);
-        // Roma改: https://kancolle-a.sega.jp/players/information/211005_1.html
-        sources.insert(247, vec![Halloween]);
-        // Zara, Zara改: https://kancolle-a.sega.jp/players/information/2209_sauryfestival.html
-        sources.insert(248, vec![PacificSaury]);
-        // 秋津洲改: https://kancolle-a.sega.jp/players/information/2207_join_akitusima_swim.html
-        sources.insert(250, vec![Swimsuit]);
-        // 風雲, 風雲改: https://kancolle-a.sega.jp/players/information/2208_join_kazagumo_swim.html
-        sources.insert(253, vec![Swimsuit]);
-        // 山風, 山風改: https://kancolle-a.sega.jp/players/information/210827_2.html
-        sources.insert(257, vec![Swimsuit]);
-        // 速吸, 速吸改: https://kancolle-a.sega.jp/players/information/2312_xmas.html
-        sources.insert(260, vec![Christmas]);
-        // 翔鶴改二:
-        // * https://kancolle-a.sega.jp/players/information/211214_1.html
-        // * (OriginalIllustration) https://kancolle-a.sega.jp/players/information/210805_1.html
-        sources.insert(261, vec![Christmas, OriginalIllustration1(false)]);
-        // 瑞鶴改二: https://kancolle-a.sega.jp/players/information/211214_1.html
-        sources.insert(262, vec![Christmas]);
-        // 朝潮改二: https://kancolle-a.sega.jp/players/information/201013_1.html
-        sources.insert(263, vec![Halloween]);
-        // 霞改二: https://kancolle-a.sega.jp/players/information/200805_1.html
-        sources.insert(264, vec![Swimsuit]);
-        // 鹿島, 鹿島改: https://kancolle-a.sega.jp/players/information/201201_1.html
-        sources.insert(265, vec![Christmas]);
-        // 翔鶴改二甲: https://kancolle-a.sega.jp/players/information/211214_1.html
-        sources.insert(266, vec![Christmas]);
-        // 瑞鶴改二甲:
-        // * (Christmas) https://kancolle-a.sega.jp/players/information/211214_1.html
-        // * (DecisiveBattle) https://kancolle-a.sega.jp/players/information/2409_join_zuikaku_kai_2_engano.html (エンガノ岬決戦mode)
-        sources.insert(267, vec![DecisiveBattle, Christmas]);
-        // 朝潮改二丁: https://kancolle-a.sega.jp/players/information/201013_1.html
-        sources.insert(268, vec![Halloween]);
-        // 霞改二乙: https://kancolle-a.sega.jp/players/information/200805_1.html
-        sources.insert(270, vec![Swimsuit]);
-        // 神風: https://kancolle-a.sega.jp/players/information/2402_valentine.html
-        sources.insert(271, vec![Valentine]);
-        // 神風改: https://kancolle-a.sega.jp/players/information/2402_valentine.html
-        sources.insert(276, vec![Valentine]);
-        // 満潮改二:
-        // * https://kancolle-a.sega.jp/players/information/2401_seaarea_event14_detail_report.html (スリガオ海峡突入mode)
-        // * https://kancolle-a.sega.jp/players/information/2310_sauryfestival.html
-        sources.insert(289, vec![DecisiveBattle, PacificSaury]);
-        // Richelieu, Richelieu改: https://kancolle-a.sega.jp/players/information/2312_haregimode.html
-        sources.insert(292, vec![SundayBest]);
-        // 鈴谷改二: https://kancolle-a.sega.jp/players/information/201201_1.html
-        sources.insert(303, vec![Christmas]);
-        // 熊野改二: https://kancolle-a.sega.jp/players/information/201201_1.html
-        sources.insert(304, vec![Christmas]);
-        // Johnston, Johnston改: https://kancolle-a.sega.jp/players/information/2207_join_Johnston_swim.html
-        sources.insert(362, vec![Swimsuit]);
-        // Gotland: https://kancolle-a.sega.jp/players/information/2307_join_gotland_swim.html
-        sources.insert(374, vec![Swimsuit]);
-        // 金剛改二丙: https://kancolle-a.sega.jp/players/information/201225_1.html
-        sources.insert(391, vec![OriginalIllustration1(false)]);
-        // Fletcher, Fletcher改: https://kancolle-a.sega.jp/players/information/2407_join_fletcher_swim.html
-        sources.insert(396, vec![Swimsuit]);
-        // Grecale: https://kancolle-a.sega.jp/players/information/2310_halloween.html
-        sources.insert(414, vec![Halloween]);
-        // Ranger: https://kancolle-a.sega.jp/players/information/2504_seaarea_event16_detail.html
-        sources.insert(531, vec![OriginalIllustration1(true)]);
-        // 時雨改三: https://kancolle-a.sega.jp/players/information/2408_join_shigure_swim_start.html
-        sources.insert(561, vec![Swimsuit]);
-
-        // These cannot be determined yet, as there's multiple variations I don't
-        // have in my current data
-
-        // 由良改二:
-        // * (Yukata) https://kancolle-a.sega.jp/players/information/2309_yukata_season.html
-        // * (Swimsuit) https://kancolle-a.sega.jp/players/information/2307_join_yura-kai2_swim.html
-        sources.insert(288, vec![Unknown, Unknown]);
-
-        // Info store for ships I don't have at all.
-        // Grecale改: https://kancolle-a.sega.jp/players/information/2310_halloween.html
-        // Gotland改: https://kancolle-a.sega.jp/players/information/2307_join_gotland_swim.html
-
-        sources
-    });
+#[cfg(not(target_arch = "wasm32"))]
+use lazy_static_include::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+lazy_static_include_bytes! {
+    pub(crate) CARD_PAGE_SOURCES => "src/importer/kancolle_arcade_net/tc_book/card_page_sources.json",
+}
+
+// wasm32 has no filesystem to lazy-load from in debug builds, so always embed.
+#[cfg(target_arch = "wasm32")]
+pub(crate) static CARD_PAGE_SOURCES: &[u8] = include_bytes!("tc_book/card_page_sources.json");
+
+static BUNDLED_CARD_PAGE_SOURCES: OnceLock<CardPageSources> = OnceLock::new();
+
+/// The version of the card page sources data format we understand
+const CARD_PAGE_SOURCES_VERSION: u32 = 1;
+
+/// Where we learnt about a card page source, usually an announcement on
+/// https://kancolle-a.sega.jp/players/information/
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CardPageSourceReference {
+    pub url: String,
+    /// e.g. which ship or source the reference is for, when that's not obvious
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// The sources of the event pages of a single TcBook entry
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookCardPageSources {
+    pub book_no: u16,
+    /// The ships this TcBook entry covers, for human readers
+    pub ships: String,
+    /// The sources of each page after the Normal page, in page order
+    pub sources: Vec<BookShipCardPageSource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub references: Vec<CardPageSourceReference>,
+}
+
+/// Known event pages for ships whose TcBook entry we don't have data for yet
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PendingCardPageSources {
+    pub ships: String,
+    #[serde(default)]
+    pub references: Vec<CardPageSourceReference>,
+}
+
+#[derive(Deserialize)]
+struct CardPageSourcesFile {
+    version: u32,
+    books: Vec<BookCardPageSources>,
+    #[serde(default)]
+    pending: Vec<PendingCardPageSources>,
+}
+
+/// The sources of TcBook event pages, see BookShip::source
+#[derive(Debug, Clone, Default)]
+pub struct CardPageSources {
+    books: HashMap<u16, BookCardPageSources>,
+    pending: Vec<PendingCardPageSources>,
+}
+
+impl CardPageSources {
+    /// The card page sources bundled with this crate
+    // CARD_PAGE_SOURCES is already a &[u8] on wasm32
+    #[cfg_attr(target_arch = "wasm32", allow(clippy::useless_asref))]
+    pub fn bundled() -> &'static CardPageSources {
+        BUNDLED_CARD_PAGE_SOURCES.get_or_init(|| {
+            CardPageSources::from_reader(CARD_PAGE_SOURCES.as_ref())
+                .expect("bundled card_page_sources.json is valid")
+        })
+    }
+
+    /// Parses CardPageSources from the provided JSON reader, in the same format as the
+    /// bundled `card_page_sources.json`.
+    /// Fails if the data is not in a version of the format we understand, or lists a
+    /// book number more than once.
+    pub fn from_reader(reader: impl Read) -> crate::Result<CardPageSources> {
+        let file: CardPageSourcesFile = serde_json::from_reader(reader)?;
+        if file.version != CARD_PAGE_SOURCES_VERSION {
+            return Err(crate::Error::UnsupportedVersion {
+                data: "card page sources".to_string(),
+                version: file.version,
+            });
+        }
+
+        let mut books = HashMap::with_capacity(file.books.len());
+        for book in file.books {
+            let book_no = book.book_no;
+            if books.insert(book_no, book).is_some() {
+                return Err(crate::Error::DuplicateBookNo {
+                    book_no,
+                    data: "card page sources".to_string(),
+                });
+            }
+        }
+
+        Ok(CardPageSources {
+            books,
+            pending: file.pending,
+        })
+    }
+
+    /// Add the entries from other, replacing any of ours for the same book number.
+    pub fn overlay(&mut self, other: CardPageSources) {
+        self.books.extend(other.books);
+        self.pending.extend(other.pending);
+    }
+
+    /// The sources for the given book number, if known
+    pub fn get(&self, book_no: u16) -> Option<&BookCardPageSources> {
+        self.books.get(&book_no)
+    }
+
+    /// All the entries, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = &BookCardPageSources> + '_ {
+        self.books.values()
+    }
+
+    pub fn pending(&self) -> &[PendingCardPageSources] {
+        &self.pending
+    }
+}
+
+fn inconsistent(ship_name: &str, reason: impl Into<String>) -> crate::Error {
+    crate::Error::InconsistentShip {
+        ship_name: ship_name.to_string(),
+        reason: reason.into(),
+    }
+}
+
+/// Checks a page has the expected number of variations, before splitting it.
+fn check_variations(
+    ship_name: &str,
+    card_page: &BookShipCardPage,
+    expected: u16,
+) -> crate::Result<()> {
+    if card_page.variation_num_in_page == expected
+        && card_page.card_img_list.len() == expected as usize
+    {
+        return Ok(());
+    }
+    Err(inconsistent(
+        ship_name,
+        format!(
+            "page {} has {} variations and {} card images, expected {expected} for its source",
+            card_page.priority,
+            card_page.variation_num_in_page,
+            card_page.card_img_list.len()
+        ),
+    ))
+}
+
+/// The Normal page's status images, which Original Illustration pages share.
+fn normal_status_img(
+    ship_name: &str,
+    card_page: &BookShipCardPage,
+    normal_status_img: &Option<Vec<String>>,
+) -> crate::Result<Option<Vec<String>>> {
+    match normal_status_img {
+        Some(_) => Ok(normal_status_img.clone()),
+        None => Err(inconsistent(
+            ship_name,
+            format!(
+                "Original Illustration page {} before the Normal page",
+                card_page.priority
+            ),
+        )),
+    }
 }
 
 impl BookShip {
-    /// Reports the event-source for the given page ("priority") of a TcBook entry
+    /// Reports the event-source for the given page ("priority") of a TcBook entry,
+    /// per the bundled CardPageSources.
+    #[deprecated(
+        note = "ignores any CardPageSources given to ShipsBuilder, use source_with or ShipMod::card_page_source"
+    )]
     pub fn source(&self, priority: u16) -> BookShipCardPageSource {
+        self.source_with(CardPageSources::bundled(), priority)
+    }
+
+    /// Reports the event-source for the given page ("priority") of a TcBook entry,
+    /// per the given CardPageSources.
+    pub fn source_with(
+        &self,
+        card_page_sources: &CardPageSources,
+        priority: u16,
+    ) -> BookShipCardPageSource {
         use BookShipCardPageSource::*;
         if priority == 0 {
             return Normal;
         }
-        init_book_ship_sources();
-        if let Some(sources) = BOOK_SHIP_SOURCES.get().unwrap().get(&self.book_no) {
+        if let Some(sources) = card_page_sources
+            .get(self.book_no)
+            .map(|book| &book.sources)
+        {
             if sources.len() + 1 != self.card_list.len() {
                 // Old data. Not sure if there's a good way to handle this; as new events are inserted before
                 // Original Illustration Cards, but otherwise in order of addition, it appears.
@@ -420,9 +302,21 @@ impl BookShip {
     }
 
     /// Split ourselves into a non-kai and optional kai BookShips.
-    pub(crate) fn into_kai_split(mut self) -> (BookShip, Option<BookShip>) {
+    #[cfg(test)]
+    pub(crate) fn into_kai_split(self) -> crate::Result<(BookShip, Option<BookShip>)> {
+        self.into_kai_split_with(CardPageSources::bundled())
+    }
+
+    /// Split ourselves into a non-kai and optional kai BookShips, per the given
+    /// CardPageSources.
+    /// Fails if our pages don't have the layout their sources imply, e.g. after a change
+    /// to the TcBook data, or from CardPageSources that don't match it.
+    pub(crate) fn into_kai_split_with(
+        mut self,
+        card_page_sources: &CardPageSources,
+    ) -> crate::Result<(BookShip, Option<BookShip>)> {
         if self.card_list.is_empty() || self.card_list[0].variation_num_in_page == 3 {
-            return (self, None);
+            return Ok((self, None));
         }
 
         let mut kai = self.clone();
@@ -436,7 +330,7 @@ impl BookShip {
             use BookShipCardPageSource::*;
             // Cheating using kai because the borrow checker won't let us use self due to
             // the mut borrow to get our iterator.
-            match kai.source(card_page.priority) {
+            match kai.source_with(card_page_sources, card_page.priority) {
                 // 雪風 has no swimsuits, but 雪風改 does. And they share a book entry. >_<
                 Swimsuit if self.ship_name == "雪風" => {
                     // Drop this page.
@@ -450,23 +344,22 @@ impl BookShip {
                     variation_count += card_page.variation_num_in_page;
                     owned_count += card_page.acquire_num_in_page;
                     // Original Illustrations should have the same status icons as the Normal page
-                    assert!(self_normal_status_img.is_some());
-                    card_page.status_img = self_normal_status_img.clone();
+                    card_page.status_img =
+                        normal_status_img(&self.ship_name, card_page, &self_normal_status_img)?;
                 }
                 OriginalIllustration1(true) | OriginalIllustration2(true, true) => {
                     // Drop this page.
                     card_page.acquire_num_in_page = 0;
                     card_page.variation_num_in_page = 0;
                     // Original Illustrations should have the same status icons as the Normal page
-                    assert!(self_normal_status_img.is_some());
-                    card_page.status_img = self_normal_status_img.clone();
+                    card_page.status_img =
+                        normal_status_img(&self.ship_name, card_page, &self_normal_status_img)?;
                     // TODO: We aren't actually dropping this page, so for sanity, clear the array.
                     card_page.card_img_list.clear();
                 }
                 OriginalIllustration2(false, true) => {
                     // Take the first one only
-                    assert_eq!(card_page.variation_num_in_page, 2);
-                    assert_eq!(card_page.card_img_list.len(), 2);
+                    check_variations(&self.ship_name, card_page, 2)?;
                     card_page.card_img_list.remove(1);
                     card_page.variation_num_in_page = 1;
 
@@ -478,16 +371,15 @@ impl BookShip {
                         .count() as u16;
 
                     // Original Illustrations should have the same status icons as the Normal page
-                    assert!(self_normal_status_img.is_some());
-                    card_page.status_img = self_normal_status_img.clone();
+                    card_page.status_img =
+                        normal_status_img(&self.ship_name, card_page, &self_normal_status_img)?;
 
                     variation_count += card_page.variation_num_in_page;
                     owned_count += card_page.acquire_num_in_page;
                 }
                 OriginalIllustration2(true, false) => {
                     // Take the second one only
-                    assert_eq!(card_page.variation_num_in_page, 2);
-                    assert_eq!(card_page.card_img_list.len(), 2);
+                    check_variations(&self.ship_name, card_page, 2)?;
                     card_page.card_img_list.remove(0);
                     card_page.variation_num_in_page = 1;
 
@@ -499,16 +391,15 @@ impl BookShip {
                         .count() as u16;
 
                     // Original Illustrations should have the same status icons as the Normal page
-                    assert!(self_normal_status_img.is_some());
-                    card_page.status_img = self_normal_status_img.clone();
+                    card_page.status_img =
+                        normal_status_img(&self.ship_name, card_page, &self_normal_status_img)?;
 
                     variation_count += card_page.variation_num_in_page;
                     owned_count += card_page.acquire_num_in_page;
                 }
                 source => {
                     // Split, take the first half
-                    assert_eq!(card_page.variation_num_in_page, 6);
-                    assert_eq!(card_page.card_img_list.len(), 6);
+                    check_variations(&self.ship_name, card_page, 6)?;
                     card_page.card_img_list.truncate(3);
                     card_page.variation_num_in_page = 3;
 
@@ -530,8 +421,15 @@ impl BookShip {
 
                     if matches!(source, Normal) {
                         // Store a copy of the normal status page for OriginalIllustrations to use
-                        assert!(self_normal_status_img.is_none());
-                        assert!(card_page.status_img.is_some());
+                        if self_normal_status_img.is_some() {
+                            return Err(inconsistent(&self.ship_name, "more than one Normal page"));
+                        }
+                        if card_page.status_img.is_none() {
+                            return Err(inconsistent(
+                                &self.ship_name,
+                                "no status images on the Normal page",
+                            ));
+                        }
                         self_normal_status_img = card_page.status_img.clone();
                     } else if card_page.status_img.as_ref().is_some_and(|s| s.is_empty()) {
                         // If we emptied the status image array, None it instead.
@@ -549,14 +447,9 @@ impl BookShip {
         self.variation_num = variation_count;
         self.acquire_num = owned_count;
 
-        // NOTE: We cannot remove the 0-variation pages from self yet, as we need source() to
-        // return the correct value until we finish processing the kai data.
-        // In fact, we may not be able to remove them at all, due to source() validating that.
-        // TODO: What about a function to wrap walking the pages with their source?
-        // We may need to remove source() from the public API since splitting the book like this
-        // will break it.
-        // IDEA: Push source() up into Ship, and massage things there? Still messy, but only
-        // internally-messy.
+        // NOTE: We cannot remove the 0-variation pages, as source() relies on the page count
+        // matching the card page sources. Instead ShipMod holds the per-page sources from
+        // split_page_sources, and ShipMod::card_pages skips the emptied pages.
 
         let mut variation_count = 0u16;
         let mut owned_count = 0u16;
@@ -566,7 +459,7 @@ impl BookShip {
             use BookShipCardPageSource::*;
             // Cheating using self because the borrow checker won't let us use kai due to
             // the mut borrow to get our iterator.
-            match self.source(card_page.priority) {
+            match self.source_with(card_page_sources, card_page.priority) {
                 // 雪風 has no swimsuits, but 雪風改 does. And they share a book entry. >_<
                 Swimsuit if kai.ship_name == "雪風改" => {
                     // Keep this page.
@@ -578,8 +471,8 @@ impl BookShip {
                     variation_count += card_page.variation_num_in_page;
                     owned_count += card_page.acquire_num_in_page;
                     // Original Illustrations should have the same status icons as the Normal page
-                    assert!(kai_normal_status_img.is_some());
-                    card_page.status_img = kai_normal_status_img.clone();
+                    card_page.status_img =
+                        normal_status_img(&kai.ship_name, card_page, &kai_normal_status_img)?;
                 }
                 OriginalIllustration1(false) | OriginalIllustration2(false, false) => {
                     // Drop this page.
@@ -588,13 +481,12 @@ impl BookShip {
                     // TODO: We aren't actually dropping this page, so for sanity, clear the array.
                     card_page.card_img_list.clear();
                     // Original Illustrations should have the same status icons as the Normal page
-                    assert!(kai_normal_status_img.is_some());
-                    card_page.status_img = kai_normal_status_img.clone();
+                    card_page.status_img =
+                        normal_status_img(&kai.ship_name, card_page, &kai_normal_status_img)?;
                 }
                 OriginalIllustration2(true, false) => {
                     // Take the first one only
-                    assert_eq!(card_page.variation_num_in_page, 2);
-                    assert_eq!(card_page.card_img_list.len(), 2);
+                    check_variations(&kai.ship_name, card_page, 2)?;
                     card_page.card_img_list.remove(1);
                     card_page.variation_num_in_page = 1;
 
@@ -606,16 +498,15 @@ impl BookShip {
                         .count() as u16;
 
                     // Original Illustrations should have the same status icons as the Normal page
-                    assert!(kai_normal_status_img.is_some());
-                    card_page.status_img = kai_normal_status_img.clone();
+                    card_page.status_img =
+                        normal_status_img(&kai.ship_name, card_page, &kai_normal_status_img)?;
 
                     variation_count += card_page.variation_num_in_page;
                     owned_count += card_page.acquire_num_in_page;
                 }
                 OriginalIllustration2(false, true) => {
                     // Take the second one only
-                    assert_eq!(card_page.variation_num_in_page, 2);
-                    assert_eq!(card_page.card_img_list.len(), 2);
+                    check_variations(&kai.ship_name, card_page, 2)?;
                     card_page.card_img_list.remove(0);
                     card_page.variation_num_in_page = 1;
 
@@ -627,16 +518,15 @@ impl BookShip {
                         .count() as u16;
 
                     // Original Illustrations should have the same status icons as the Normal page
-                    assert!(kai_normal_status_img.is_some());
-                    card_page.status_img = kai_normal_status_img.clone();
+                    card_page.status_img =
+                        normal_status_img(&kai.ship_name, card_page, &kai_normal_status_img)?;
 
                     variation_count += card_page.variation_num_in_page;
                     owned_count += card_page.acquire_num_in_page;
                 }
                 source => {
                     // Split, take the second half
-                    assert_eq!(card_page.variation_num_in_page, 6);
-                    assert_eq!(card_page.card_img_list.len(), 6);
+                    check_variations(&kai.ship_name, card_page, 6)?;
                     drop(card_page.card_img_list.drain(0..3));
                     card_page.variation_num_in_page = 3;
 
@@ -658,8 +548,15 @@ impl BookShip {
 
                     if matches!(source, Normal) {
                         // Store a copy of the normal status page for OriginalIllustrations to use
-                        assert!(kai_normal_status_img.is_none());
-                        assert!(card_page.status_img.is_some());
+                        if kai_normal_status_img.is_some() {
+                            return Err(inconsistent(&kai.ship_name, "more than one Normal page"));
+                        }
+                        if card_page.status_img.is_none() {
+                            return Err(inconsistent(
+                                &kai.ship_name,
+                                "no status images on the Normal page",
+                            ));
+                        }
                         kai_normal_status_img = card_page.status_img.clone();
                     } else if card_page.status_img.as_ref().is_some_and(|s| s.is_empty()) {
                         // If we emptied the status image array, None it instead.
@@ -678,29 +575,32 @@ impl BookShip {
 
         // Fixup isMarried and marriedImg
         if let Some(married_vec) = self.is_married.as_ref() {
-            assert!(self.married_img.is_some());
-            let self_married = if self.acquire_num > 0 {
-                *married_vec.first().unwrap()
-            } else {
-                false
-            };
-            let kai_married = if kai.acquire_num > 0 {
-                *married_vec.last().unwrap()
-            } else {
-                false
-            };
+            let self_married =
+                self.acquire_num > 0 && married_vec.first().copied().unwrap_or_default();
+            let kai_married =
+                kai.acquire_num > 0 && married_vec.last().copied().unwrap_or_default();
             self.is_married = Some([self_married].repeat(self_non_original_pages));
             kai.is_married = Some([kai_married].repeat(kai_non_original_pages));
+
+            // kai is a clone of self, so both or neither have marriedImg
+            let (Some(self_married_img), Some(kai_married_img)) =
+                (self.married_img.as_mut(), kai.married_img.as_mut())
+            else {
+                return Err(inconsistent(
+                    &self.ship_name,
+                    "isMarried without marriedImg",
+                ));
+            };
             if !self_married {
-                self.married_img.as_mut().unwrap().clear();
-            } else if self.married_img.as_ref().unwrap().len() == 2 {
-                self.married_img.as_mut().unwrap().remove(0);
+                self_married_img.clear();
+            } else if self_married_img.len() == 2 {
+                self_married_img.remove(0);
             }
 
             if !kai_married {
-                kai.married_img.as_mut().unwrap().clear();
-            } else if kai.married_img.as_ref().unwrap().len() == 2 {
-                kai.married_img.as_mut().unwrap().remove(1);
+                kai_married_img.clear();
+            } else if kai_married_img.len() == 2 {
+                kai_married_img.remove(1);
             }
         }
 
@@ -710,19 +610,85 @@ impl BookShip {
         // At this point, I wonder if rather than clone-and-edit, we could have just created two
         // new BookShips, populated them, and dropped self in the end.
 
-        (self, Some(kai))
+        Ok((self, Some(kai)))
+    }
+
+    /// The sources of each of our pages, per the given CardPageSources.
+    pub(crate) fn page_sources_with(
+        &self,
+        card_page_sources: &CardPageSources,
+    ) -> Vec<BookShipCardPageSource> {
+        (0..self.card_list.len() as u16)
+            .map(|priority| self.source_with(card_page_sources, priority))
+            .collect()
+    }
+
+    /// The sources of our pages after into_kai_split_with, given the sources of the
+    /// unsplit pages. An Original Illustration page which kept one of its two cards is an
+    /// OriginalIllustration1 page for the half it was kept for.
+    pub(crate) fn split_page_sources(
+        &self,
+        unsplit: &[BookShipCardPageSource],
+        kai: bool,
+    ) -> Vec<BookShipCardPageSource> {
+        use BookShipCardPageSource::*;
+        self.card_list
+            .iter()
+            .zip(unsplit)
+            .map(|(card_page, source)| match source {
+                OriginalIllustration2(..) if card_page.variation_num_in_page == 1 => {
+                    OriginalIllustration1(kai)
+                }
+                source => source.clone(),
+            })
+            .collect()
     }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
-#[serde(deny_unknown_fields)]
 pub struct BookShipCardPage {
     pub priority: u16,
     pub card_img_list: Vec<String>,
     pub status_img: Option<Vec<String>>,
     pub variation_num_in_page: u16,
     pub acquire_num_in_page: u16,
+    /// Any fields not listed above, see read_*_lenient
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl UnknownFields for BookShipCardPage {
+    fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+        self.extra.unknown_fields(path, found);
+    }
+}
+
+mod optional_ship_type {
+    // The book uses an empty string for unknown ship types
+    use crate::ship_attributes::ShipType;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(ship_type: &Option<ShipType>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match ship_type {
+            Some(ship_type) => ship_type.serialize(serializer),
+            None => serializer.serialize_str(""),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<ShipType>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        if s.is_empty() {
+            return Ok(None);
+        }
+        ShipType::deserialize(serde::de::value::StringDeserializer::new(s)).map(Some)
+    }
 }
 
 #[cfg(test)]
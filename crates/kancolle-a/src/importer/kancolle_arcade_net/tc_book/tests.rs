@@ -1,7 +1,11 @@
 use super::*;
+use crate::importer::kancolle_arcade_net::UnknownKind;
+use crate::ship_attributes::ShipType;
 
 use crate::importer::wikiwiki_jp_kancolle_a::{read_kansen_table, KANSEN};
 
+// super only brings this into scope off wasm32, where it lazy-loads the bundled data.
+#[cfg(target_arch = "wasm32")]
 use lazy_static_include::*;
 
 // https://kancolle-arcade.net/ac/api/TcBook/info
@@ -22,7 +26,7 @@ fn test_book_ship_source() {
         book_no: 6,
         ship_class: None,
         ship_class_index: None,
-        ship_type: "".to_string(),
+        ship_type: None,
         ship_model_num: "".to_string(),
         ship_name: "".to_string(),
         card_index_img: "".to_string(),
@@ -33,6 +37,7 @@ fn test_book_ship_source() {
                 status_img: None,
                 variation_num_in_page: 3,
                 acquire_num_in_page: 0,
+                extra: Default::default(),
             },
             BookShipCardPage {
                 priority: 0,
@@ -40,6 +45,7 @@ fn test_book_ship_source() {
                 status_img: None,
                 variation_num_in_page: 3,
                 acquire_num_in_page: 0,
+                extra: Default::default(),
             },
         ],
         variation_num: 6,
@@ -47,12 +53,57 @@ fn test_book_ship_source() {
         lv: 1,
         is_married: None,
         married_img: None,
+        extra: Default::default(),
     };
 
     use BookShipCardPageSource::*;
-    assert_eq!(ship.source(0), Normal);
-    assert_eq!(ship.source(1), SundayBest);
-    assert_eq!(ship.source(2), Unknown);
+    assert_eq!(ship.source_with(CardPageSources::bundled(), 0), Normal);
+    assert_eq!(ship.source_with(CardPageSources::bundled(), 1), SundayBest);
+    assert_eq!(ship.source_with(CardPageSources::bundled(), 2), Unknown);
+}
+
+#[test]
+fn test_card_page_sources_overlay() {
+    let tcbook = read_tclist(TCBOOK_LATEST.as_ref()).unwrap();
+    let 赤城 = tcbook.iter().find(|ship| ship.book_no == 6).unwrap();
+
+    let overlay = r#"{
+        "version": 1,
+        "books": [
+            {"bookNo": 6, "ships": "赤城, 赤城改", "sources": ["Christmas"]},
+            {"bookNo": 9999, "ships": "", "sources": [{"OriginalIllustration2": [false, true]}]}
+        ]
+    }"#;
+    let mut sources = CardPageSources::bundled().clone();
+    sources.overlay(CardPageSources::from_reader(overlay.as_bytes()).unwrap());
+
+    use BookShipCardPageSource::*;
+    #[allow(deprecated)]
+    let bundled_source = 赤城.source(1);
+    assert_eq!(bundled_source, SundayBest);
+    assert_eq!(赤城.source_with(CardPageSources::bundled(), 1), SundayBest);
+    assert_eq!(赤城.source_with(&sources, 1), Christmas);
+    assert_eq!(
+        sources.get(9999).unwrap().sources,
+        vec![OriginalIllustration2(false, true)]
+    );
+    // Untouched entries are kept
+    assert_eq!(sources.get(2).unwrap().sources, vec![Yukata]);
+}
+
+#[test]
+fn test_card_page_sources_duplicate() {
+    let duplicate = r#"{
+        "version": 1,
+        "books": [
+            {"bookNo": 6, "ships": "赤城, 赤城改", "sources": ["SundayBest"]},
+            {"bookNo": 6, "ships": "赤城, 赤城改", "sources": ["Christmas"]}
+        ]
+    }"#;
+    assert!(matches!(
+        CardPageSources::from_reader(duplicate.as_bytes()),
+        Err(crate::Error::DuplicateBookNo { book_no: 6, .. })
+    ));
 }
 
 #[test]
@@ -62,11 +113,28 @@ fn test_book_split_nokai_20240623() {
 
     let 神風改 = &tcbook[248];
 
-    let (神風改_nonkai, 神風改_kai) = 神風改.clone().into_kai_split();
+    let (神風改_nonkai, 神風改_kai) = 神風改.clone().into_kai_split().unwrap();
     assert!(神風改_kai.is_none());
     assert_eq!(神風改, &神風改_nonkai);
 }
 
+#[test]
+fn test_book_split_inconsistent_page() {
+    let tcbook = read_tclist(TCBOOK_2024_06_23.as_ref()).unwrap();
+    let mut 長門 = tcbook[0].clone();
+    長門.card_list[0].card_img_list.pop();
+
+    let err = 長門.into_kai_split().unwrap_err();
+    assert!(
+        matches!(
+            &err,
+            crate::Error::InconsistentShip { ship_name, reason }
+                if ship_name == "長門" && reason.starts_with("page 0 has 6 variations and 5 card images")
+        ),
+        "{err}"
+    );
+}
+
 #[test]
 fn parse_empty_tcbook_reader() {
     read_tclist(std::io::empty()).unwrap_err();
@@ -83,7 +151,7 @@ fn validate_tcbook_split_common(tcbook: &TcBook) {
     // Big enough for everything... Should be no more than 445, see the Ship integration tests.
     let mut split_book: TcBook = Vec::with_capacity(500);
     for book_ship in tcbook.iter().cloned() {
-        let (book_ship, kai_ship) = book_ship.into_kai_split();
+        let (book_ship, kai_ship) = book_ship.into_kai_split().unwrap();
         split_book.push(book_ship);
         if let Some(kai_ship) = kai_ship {
             split_book.push(kai_ship);
@@ -92,6 +160,8 @@ fn validate_tcbook_split_common(tcbook: &TcBook) {
     validate_tcbook_common(&split_book);
 }
 
+// The status_img checks read more clearly as an if-else chain than nested if-lets.
+#[allow(clippy::unnecessary_unwrap)]
 fn validate_tcbook_common(tcbook: &TcBook) {
     const CARD_IMAGE_SUFFIX: &str = ".jpg";
     const STATUS_IMAGE_PREFIX: &str = "i/i_";
@@ -112,7 +182,7 @@ fn validate_tcbook_common(tcbook: &TcBook) {
             // The other pages are for event variations, 3-image sets and then individual original illustrations last.
             // The image set pages are not always the same, e.g. 雪風 has no swimsuit set, but 雪風改 (same number) does.
             // But we should never see more cards on a page than on the first page.
-            match ship.source(card_page.priority) {
+            match ship.source_with(CardPageSources::bundled(), card_page.priority) {
                 OriginalIllustration1(_) if normal_variation == 6 => {
                     assert_ne!(card_page.priority, 0);
                     assert_eq!(card_page.variation_num_in_page, 1);
@@ -196,7 +266,7 @@ fn validate_tcbook_common(tcbook: &TcBook) {
         assert_ne!(ship.book_no, 0);
         assert_eq!(ship.ship_class.as_ref().unwrap(), "");
         assert_eq!(ship.ship_class_index.unwrap(), -1);
-        assert_eq!(ship.ship_type, "");
+        assert_eq!(ship.ship_type, None);
         assert_eq!(ship.ship_model_num, "");
         assert_eq!(ship.ship_name, "未取得");
         assert_eq!(ship.card_index_img, "");
@@ -211,7 +281,7 @@ fn validate_tcbook_common(tcbook: &TcBook) {
     // Universally-true facts about all ships we've scanned already.
 
     // ... And exceptions thereof.
-    let ship_types_without_class = ["工作艦"]; // Unique repair ship
+    let ship_types_without_class = [ShipType::RepairShip]; // Unique repair ship
 
     // Ships with a model num also don't have an index, e.g., DD-445. Is that just for US ships?
     let ship_classes_without_index = [
@@ -229,10 +299,10 @@ fn validate_tcbook_common(tcbook: &TcBook) {
         eprintln!("Ship {_book_no}");
         let card_image_prefix = format!("s/tc_{0}_", ship.book_no);
         assert_ne!(ship.book_no, 0);
-        assert_ne!(ship.ship_type, ""); // Moved earlier because some things depend on this.
+        assert!(ship.ship_type.is_some()); // Moved earlier because some things depend on this.
 
         // Some interactions between ship classes and models, which explains all the Option types here.
-        if ship_types_without_class.contains(&&ship.ship_type[..]) {
+        if ship_types_without_class.contains(ship.ship_type.as_ref().unwrap()) {
             assert!(ship.ship_class.is_none());
             assert!(ship.ship_class_index.is_none());
             assert_eq!(ship.ship_model_num, "");
@@ -254,7 +324,7 @@ fn validate_tcbook_common(tcbook: &TcBook) {
         assert_ne!(ship.card_list.len(), 0);
         // Test for overshoot returning Unknown
         assert_eq!(
-            ship.source(ship.card_list.len() as u16),
+            ship.source_with(CardPageSources::bundled(), ship.card_list.len() as u16),
             BookShipCardPageSource::Unknown
         );
         assert_ne!(ship.variation_num, 0);
@@ -269,7 +339,7 @@ fn validate_tcbook_common(tcbook: &TcBook) {
             .iter()
             .filter(|page| {
                 !matches!(
-                    ship.source(page.priority),
+                    ship.source_with(CardPageSources::bundled(), page.priority),
                     BookShipCardPageSource::OriginalIllustration1(_)
                         | BookShipCardPageSource::OriginalIllustration2(_, _)
                 )
@@ -306,18 +376,22 @@ fn validate_tcbook_common(tcbook: &TcBook) {
             // TODO: Original Illustation status icon list should always match Normal list.
             use BookShipCardPageSource::*;
             if card_list_page.status_img.is_none() {
-                assert!(match ship.source(card_list_page.priority) {
-                    Unknown => true, // We can't assume anything...
-                    Normal | OriginalIllustration1(_) | OriginalIllustration2(_, _) => false,
-                    _ => true,
-                });
+                assert!(
+                    match ship.source_with(CardPageSources::bundled(), card_list_page.priority) {
+                        Unknown => true, // We can't assume anything...
+                        Normal | OriginalIllustration1(_) | OriginalIllustration2(_, _) => false,
+                        _ => true,
+                    }
+                );
                 continue;
             } else if card_list_page.status_img.as_ref().unwrap().is_empty() {
-                assert!(match ship.source(card_list_page.priority) {
-                    Unknown => true, // We can't assume anything...
-                    Normal | OriginalIllustration1(_) | OriginalIllustration2(_, _) => true,
-                    _ => false,
-                });
+                assert!(
+                    match ship.source_with(CardPageSources::bundled(), card_list_page.priority) {
+                        Unknown => true, // We can't assume anything...
+                        Normal | OriginalIllustration1(_) | OriginalIllustration2(_, _) => true,
+                        _ => false,
+                    }
+                );
                 continue;
             }
 
@@ -354,14 +428,14 @@ fn parse_fixture_tcbook_info_20240528() {
     assert_eq!(長門.book_no, 1);
     assert_eq!(長門.ship_class.as_ref().unwrap(), "長門型");
     assert_eq!(長門.ship_class_index.unwrap(), 1);
-    assert_eq!(長門.ship_type, "戦艦");
+    assert_eq!(長門.ship_type, Some(ShipType::Battleship));
     assert_eq!(長門.ship_model_num, "");
     assert_eq!(長門.ship_name, "長門");
     assert_eq!(長門.card_index_img, "s/tc_1_d7ju63kolamj.jpg");
     assert_eq!(長門.card_list.len(), 1);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(長門.source(0), Normal);
+        assert_eq!(長門.source_with(CardPageSources::bundled(), 0), Normal);
     }
     assert_eq!(長門.variation_num, 6);
     assert_eq!(長門.acquire_num, 2);
@@ -394,14 +468,14 @@ fn parse_fixture_tcbook_info_20240528() {
     assert_eq!(扶桑.book_no, 26);
     assert_eq!(扶桑.ship_class.as_ref().unwrap(), "扶桑型");
     assert_eq!(扶桑.ship_class_index.unwrap(), 1);
-    assert_eq!(扶桑.ship_type, "戦艦");
+    assert_eq!(扶桑.ship_type, Some(ShipType::Battleship));
     assert_eq!(扶桑.ship_model_num, "");
     assert_eq!(扶桑.ship_name, "扶桑");
     assert_eq!(扶桑.card_index_img, "s/tc_26_p9u490qtc1a4.jpg");
     assert_eq!(扶桑.card_list.len(), 1);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(扶桑.source(0), Normal);
+        assert_eq!(扶桑.source_with(CardPageSources::bundled(), 0), Normal);
     }
     assert_eq!(扶桑.variation_num, 6);
     assert_eq!(扶桑.acquire_num, 4);
@@ -435,15 +509,18 @@ fn parse_fixture_tcbook_info_20240528() {
     assert_eq!(早霜.book_no, 209);
     assert_eq!(早霜.ship_class.as_ref().unwrap(), "夕雲型");
     assert_eq!(早霜.ship_class_index.unwrap(), 17);
-    assert_eq!(早霜.ship_type, "駆逐艦");
+    assert_eq!(早霜.ship_type, Some(ShipType::Destroyer));
     assert_eq!(早霜.ship_model_num, "");
     assert_eq!(早霜.ship_name, "早霜");
     assert_eq!(早霜.card_index_img, "s/tc_209_6uqm0rr6azd9.jpg");
     assert_eq!(早霜.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(早霜.source(0), Normal);
-        assert_eq!(早霜.source(1), Unknown /*OriginalIllustration1(true)*/);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(
+            早霜.source_with(CardPageSources::bundled(), 1),
+            Unknown /*OriginalIllustration1(true)*/
+        );
     }
     assert_eq!(早霜.variation_num, 7);
     assert_eq!(早霜.acquire_num, 1);
@@ -480,14 +557,14 @@ fn parse_fixture_tcbook_info_20240528() {
     assert_eq!(扶桑改二.book_no, 211);
     assert_eq!(扶桑改二.ship_class.as_ref().unwrap(), "扶桑型");
     assert_eq!(扶桑改二.ship_class_index.unwrap(), 1);
-    assert_eq!(扶桑改二.ship_type, "航空戦艦");
+    assert_eq!(扶桑改二.ship_type, Some(ShipType::AviationBattleship));
     assert_eq!(扶桑改二.ship_model_num, "");
     assert_eq!(扶桑改二.ship_name, "扶桑改二");
     assert_eq!(扶桑改二.card_index_img, "s/tc_211_xkrpspyq72qz.jpg");
     assert_eq!(扶桑改二.card_list.len(), 1);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(早霜.source(0), Normal);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 0), Normal);
     }
     assert_eq!(扶桑改二.variation_num, 3);
     assert_eq!(扶桑改二.acquire_num, 1);
@@ -524,14 +601,14 @@ fn parse_fixture_tcbook_info_20240530() {
     assert_eq!(長門.book_no, 1);
     assert_eq!(長門.ship_class.as_ref().unwrap(), "長門型");
     assert_eq!(長門.ship_class_index.unwrap(), 1);
-    assert_eq!(長門.ship_type, "戦艦");
+    assert_eq!(長門.ship_type, Some(ShipType::Battleship));
     assert_eq!(長門.ship_model_num, "");
     assert_eq!(長門.ship_name, "長門");
     assert_eq!(長門.card_index_img, "s/tc_1_d7ju63kolamj.jpg");
     assert_eq!(長門.card_list.len(), 1);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(長門.source(0), Normal);
+        assert_eq!(長門.source_with(CardPageSources::bundled(), 0), Normal);
     }
     assert_eq!(長門.variation_num, 6);
     assert_eq!(長門.acquire_num, 2);
@@ -565,14 +642,14 @@ fn parse_fixture_tcbook_info_20240530() {
     assert_eq!(扶桑.book_no, 26);
     assert_eq!(扶桑.ship_class.as_ref().unwrap(), "扶桑型");
     assert_eq!(扶桑.ship_class_index.unwrap(), 1);
-    assert_eq!(扶桑.ship_type, "戦艦");
+    assert_eq!(扶桑.ship_type, Some(ShipType::Battleship));
     assert_eq!(扶桑.ship_model_num, "");
     assert_eq!(扶桑.ship_name, "扶桑");
     assert_eq!(扶桑.card_index_img, "s/tc_26_p9u490qtc1a4.jpg");
     assert_eq!(扶桑.card_list.len(), 1);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(扶桑.source(0), Normal);
+        assert_eq!(扶桑.source_with(CardPageSources::bundled(), 0), Normal);
     }
     assert_eq!(扶桑.variation_num, 6);
     assert_eq!(扶桑.acquire_num, 4);
@@ -610,15 +687,18 @@ fn parse_fixture_tcbook_info_20240530() {
     assert_eq!(早霜.book_no, 209);
     assert_eq!(早霜.ship_class.as_ref().unwrap(), "夕雲型");
     assert_eq!(早霜.ship_class_index.unwrap(), 17);
-    assert_eq!(早霜.ship_type, "駆逐艦");
+    assert_eq!(早霜.ship_type, Some(ShipType::Destroyer));
     assert_eq!(早霜.ship_model_num, "");
     assert_eq!(早霜.ship_name, "早霜");
     assert_eq!(早霜.card_index_img, "s/tc_209_6uqm0rr6azd9.jpg");
     assert_eq!(早霜.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(早霜.source(0), Normal);
-        assert_eq!(早霜.source(1), Unknown /*OriginalIllustration1(true)*/);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(
+            早霜.source_with(CardPageSources::bundled(), 1),
+            Unknown /*OriginalIllustration1(true)*/
+        );
     }
     assert_eq!(早霜.variation_num, 7);
     assert_eq!(早霜.acquire_num, 1);
@@ -655,14 +735,14 @@ fn parse_fixture_tcbook_info_20240530() {
     assert_eq!(扶桑改二.book_no, 211);
     assert_eq!(扶桑改二.ship_class.as_ref().unwrap(), "扶桑型");
     assert_eq!(扶桑改二.ship_class_index.unwrap(), 1);
-    assert_eq!(扶桑改二.ship_type, "航空戦艦");
+    assert_eq!(扶桑改二.ship_type, Some(ShipType::AviationBattleship));
     assert_eq!(扶桑改二.ship_model_num, "");
     assert_eq!(扶桑改二.ship_name, "扶桑改二");
     assert_eq!(扶桑改二.card_index_img, "s/tc_211_xkrpspyq72qz.jpg");
     assert_eq!(扶桑改二.card_list.len(), 1);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(早霜.source(0), Normal);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 0), Normal);
     }
     assert_eq!(扶桑改二.variation_num, 3);
     assert_eq!(扶桑改二.acquire_num, 1);
@@ -699,14 +779,14 @@ fn parse_fixture_tcbook_info_20240609() {
     assert_eq!(長門.book_no, 1);
     assert_eq!(長門.ship_class.as_ref().unwrap(), "長門型");
     assert_eq!(長門.ship_class_index.unwrap(), 1);
-    assert_eq!(長門.ship_type, "戦艦");
+    assert_eq!(長門.ship_type, Some(ShipType::Battleship));
     assert_eq!(長門.ship_model_num, "");
     assert_eq!(長門.ship_name, "長門");
     assert_eq!(長門.card_index_img, "s/tc_1_d7ju63kolamj.jpg");
     assert_eq!(長門.card_list.len(), 1);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(長門.source(0), Normal);
+        assert_eq!(長門.source_with(CardPageSources::bundled(), 0), Normal);
     }
     assert_eq!(長門.variation_num, 6);
     assert_eq!(長門.acquire_num, 2);
@@ -740,15 +820,15 @@ fn parse_fixture_tcbook_info_20240609() {
     assert_eq!(扶桑.book_no, 26);
     assert_eq!(扶桑.ship_class.as_ref().unwrap(), "扶桑型");
     assert_eq!(扶桑.ship_class_index.unwrap(), 1);
-    assert_eq!(扶桑.ship_type, "戦艦");
+    assert_eq!(扶桑.ship_type, Some(ShipType::Battleship));
     assert_eq!(扶桑.ship_model_num, "");
     assert_eq!(扶桑.ship_name, "扶桑");
     assert_eq!(扶桑.card_index_img, "s/tc_26_p9u490qtc1a4.jpg");
     assert_eq!(扶桑.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(扶桑.source(0), Normal);
-        assert_eq!(扶桑.source(1), RainySeason);
+        assert_eq!(扶桑.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(扶桑.source_with(CardPageSources::bundled(), 1), RainySeason);
     }
     assert_eq!(扶桑.variation_num, 12);
     assert_eq!(扶桑.acquire_num, 5);
@@ -803,16 +883,19 @@ fn parse_fixture_tcbook_info_20240609() {
     assert_eq!(早霜.book_no, 209);
     assert_eq!(早霜.ship_class.as_ref().unwrap(), "夕雲型");
     assert_eq!(早霜.ship_class_index.unwrap(), 17);
-    assert_eq!(早霜.ship_type, "駆逐艦");
+    assert_eq!(早霜.ship_type, Some(ShipType::Destroyer));
     assert_eq!(早霜.ship_model_num, "");
     assert_eq!(早霜.ship_name, "早霜");
     assert_eq!(早霜.card_index_img, "s/tc_209_6uqm0rr6azd9.jpg");
     assert_eq!(早霜.card_list.len(), 3);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(早霜.source(0), Normal);
-        assert_eq!(早霜.source(1), RainySeason);
-        assert_eq!(早霜.source(2), OriginalIllustration1(true));
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 1), RainySeason);
+        assert_eq!(
+            早霜.source_with(CardPageSources::bundled(), 2),
+            OriginalIllustration1(true)
+        );
     }
     assert_eq!(早霜.variation_num, 13);
     assert_eq!(早霜.acquire_num, 1);
@@ -859,15 +942,15 @@ fn parse_fixture_tcbook_info_20240609() {
     assert_eq!(扶桑改二.book_no, 211);
     assert_eq!(扶桑改二.ship_class.as_ref().unwrap(), "扶桑型");
     assert_eq!(扶桑改二.ship_class_index.unwrap(), 1);
-    assert_eq!(扶桑改二.ship_type, "航空戦艦");
+    assert_eq!(扶桑改二.ship_type, Some(ShipType::AviationBattleship));
     assert_eq!(扶桑改二.ship_model_num, "");
     assert_eq!(扶桑改二.ship_name, "扶桑改二");
     assert_eq!(扶桑改二.card_index_img, "s/tc_211_xkrpspyq72qz.jpg");
     assert_eq!(扶桑改二.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(早霜.source(0), Normal);
-        assert_eq!(早霜.source(1), RainySeason);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 1), RainySeason);
     }
     assert_eq!(扶桑改二.variation_num, 6);
     assert_eq!(扶桑改二.acquire_num, 1);
@@ -911,14 +994,14 @@ fn parse_fixture_tcbook_info_20240610() {
     assert_eq!(長門.book_no, 1);
     assert_eq!(長門.ship_class.as_ref().unwrap(), "長門型");
     assert_eq!(長門.ship_class_index.unwrap(), 1);
-    assert_eq!(長門.ship_type, "戦艦");
+    assert_eq!(長門.ship_type, Some(ShipType::Battleship));
     assert_eq!(長門.ship_model_num, "");
     assert_eq!(長門.ship_name, "長門");
     assert_eq!(長門.card_index_img, "s/tc_1_d7ju63kolamj.jpg");
     assert_eq!(長門.card_list.len(), 1);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(長門.source(0), Normal);
+        assert_eq!(長門.source_with(CardPageSources::bundled(), 0), Normal);
     }
     assert_eq!(長門.variation_num, 6);
     assert_eq!(長門.acquire_num, 2);
@@ -952,15 +1035,15 @@ fn parse_fixture_tcbook_info_20240610() {
     assert_eq!(扶桑.book_no, 26);
     assert_eq!(扶桑.ship_class.as_ref().unwrap(), "扶桑型");
     assert_eq!(扶桑.ship_class_index.unwrap(), 1);
-    assert_eq!(扶桑.ship_type, "戦艦");
+    assert_eq!(扶桑.ship_type, Some(ShipType::Battleship));
     assert_eq!(扶桑.ship_model_num, "");
     assert_eq!(扶桑.ship_name, "扶桑");
     assert_eq!(扶桑.card_index_img, "s/tc_26_p9u490qtc1a4.jpg");
     assert_eq!(扶桑.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(扶桑.source(0), Normal);
-        assert_eq!(扶桑.source(1), RainySeason);
+        assert_eq!(扶桑.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(扶桑.source_with(CardPageSources::bundled(), 1), RainySeason);
     }
     assert_eq!(扶桑.variation_num, 12);
     assert_eq!(扶桑.acquire_num, 5);
@@ -1015,16 +1098,19 @@ fn parse_fixture_tcbook_info_20240610() {
     assert_eq!(早霜.book_no, 209);
     assert_eq!(早霜.ship_class.as_ref().unwrap(), "夕雲型");
     assert_eq!(早霜.ship_class_index.unwrap(), 17);
-    assert_eq!(早霜.ship_type, "駆逐艦");
+    assert_eq!(早霜.ship_type, Some(ShipType::Destroyer));
     assert_eq!(早霜.ship_model_num, "");
     assert_eq!(早霜.ship_name, "早霜");
     assert_eq!(早霜.card_index_img, "s/tc_209_6uqm0rr6azd9.jpg");
     assert_eq!(早霜.card_list.len(), 3);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(早霜.source(0), Normal);
-        assert_eq!(早霜.source(1), RainySeason);
-        assert_eq!(早霜.source(2), OriginalIllustration1(true));
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 1), RainySeason);
+        assert_eq!(
+            早霜.source_with(CardPageSources::bundled(), 2),
+            OriginalIllustration1(true)
+        );
     }
     assert_eq!(早霜.variation_num, 13);
     assert_eq!(早霜.acquire_num, 2);
@@ -1078,15 +1164,15 @@ fn parse_fixture_tcbook_info_20240610() {
     assert_eq!(扶桑改二.book_no, 211);
     assert_eq!(扶桑改二.ship_class.as_ref().unwrap(), "扶桑型");
     assert_eq!(扶桑改二.ship_class_index.unwrap(), 1);
-    assert_eq!(扶桑改二.ship_type, "航空戦艦");
+    assert_eq!(扶桑改二.ship_type, Some(ShipType::AviationBattleship));
     assert_eq!(扶桑改二.ship_model_num, "");
     assert_eq!(扶桑改二.ship_name, "扶桑改二");
     assert_eq!(扶桑改二.card_index_img, "s/tc_211_xkrpspyq72qz.jpg");
     assert_eq!(扶桑改二.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(早霜.source(0), Normal);
-        assert_eq!(早霜.source(1), RainySeason);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 1), RainySeason);
     }
     assert_eq!(扶桑改二.variation_num, 6);
     assert_eq!(扶桑改二.acquire_num, 1);
@@ -1130,14 +1216,14 @@ fn parse_fixture_tcbook_info_20240620() {
     assert_eq!(長門.book_no, 1);
     assert_eq!(長門.ship_class.as_ref().unwrap(), "長門型");
     assert_eq!(長門.ship_class_index.unwrap(), 1);
-    assert_eq!(長門.ship_type, "戦艦");
+    assert_eq!(長門.ship_type, Some(ShipType::Battleship));
     assert_eq!(長門.ship_model_num, "");
     assert_eq!(長門.ship_name, "長門");
     assert_eq!(長門.card_index_img, "s/tc_1_d7ju63kolamj.jpg");
     assert_eq!(長門.card_list.len(), 1);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(長門.source(0), Normal);
+        assert_eq!(長門.source_with(CardPageSources::bundled(), 0), Normal);
     }
     assert_eq!(長門.variation_num, 6);
     assert_eq!(長門.acquire_num, 2);
@@ -1171,15 +1257,15 @@ fn parse_fixture_tcbook_info_20240620() {
     assert_eq!(扶桑.book_no, 26);
     assert_eq!(扶桑.ship_class.as_ref().unwrap(), "扶桑型");
     assert_eq!(扶桑.ship_class_index.unwrap(), 1);
-    assert_eq!(扶桑.ship_type, "戦艦");
+    assert_eq!(扶桑.ship_type, Some(ShipType::Battleship));
     assert_eq!(扶桑.ship_model_num, "");
     assert_eq!(扶桑.ship_name, "扶桑");
     assert_eq!(扶桑.card_index_img, "s/tc_26_p9u490qtc1a4.jpg");
     assert_eq!(扶桑.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(扶桑.source(0), Normal);
-        assert_eq!(扶桑.source(1), RainySeason);
+        assert_eq!(扶桑.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(扶桑.source_with(CardPageSources::bundled(), 1), RainySeason);
     }
     assert_eq!(扶桑.variation_num, 12);
     assert_eq!(扶桑.acquire_num, 6);
@@ -1241,16 +1327,19 @@ fn parse_fixture_tcbook_info_20240620() {
     assert_eq!(早霜.book_no, 209);
     assert_eq!(早霜.ship_class.as_ref().unwrap(), "夕雲型");
     assert_eq!(早霜.ship_class_index.unwrap(), 17);
-    assert_eq!(早霜.ship_type, "駆逐艦");
+    assert_eq!(早霜.ship_type, Some(ShipType::Destroyer));
     assert_eq!(早霜.ship_model_num, "");
     assert_eq!(早霜.ship_name, "早霜");
     assert_eq!(早霜.card_index_img, "s/tc_209_6uqm0rr6azd9.jpg");
     assert_eq!(早霜.card_list.len(), 3);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(早霜.source(0), Normal);
-        assert_eq!(早霜.source(1), RainySeason);
-        assert_eq!(早霜.source(2), OriginalIllustration1(true));
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 1), RainySeason);
+        assert_eq!(
+            早霜.source_with(CardPageSources::bundled(), 2),
+            OriginalIllustration1(true)
+        );
     }
     assert_eq!(早霜.variation_num, 13);
     assert_eq!(早霜.acquire_num, 3);
@@ -1311,15 +1400,15 @@ fn parse_fixture_tcbook_info_20240620() {
     assert_eq!(扶桑改二.book_no, 211);
     assert_eq!(扶桑改二.ship_class.as_ref().unwrap(), "扶桑型");
     assert_eq!(扶桑改二.ship_class_index.unwrap(), 1);
-    assert_eq!(扶桑改二.ship_type, "航空戦艦");
+    assert_eq!(扶桑改二.ship_type, Some(ShipType::AviationBattleship));
     assert_eq!(扶桑改二.ship_model_num, "");
     assert_eq!(扶桑改二.ship_name, "扶桑改二");
     assert_eq!(扶桑改二.card_index_img, "s/tc_211_xkrpspyq72qz.jpg");
     assert_eq!(扶桑改二.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(早霜.source(0), Normal);
-        assert_eq!(早霜.source(1), RainySeason);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 1), RainySeason);
     }
     assert_eq!(扶桑改二.variation_num, 6);
     assert_eq!(扶桑改二.acquire_num, 1);
@@ -1363,14 +1452,14 @@ fn parse_fixture_tcbook_info_20240623() {
     assert_eq!(長門.book_no, 1);
     assert_eq!(長門.ship_class.as_ref().unwrap(), "長門型");
     assert_eq!(長門.ship_class_index.unwrap(), 1);
-    assert_eq!(長門.ship_type, "戦艦");
+    assert_eq!(長門.ship_type, Some(ShipType::Battleship));
     assert_eq!(長門.ship_model_num, "");
     assert_eq!(長門.ship_name, "長門");
     assert_eq!(長門.card_index_img, "s/tc_1_d7ju63kolamj.jpg");
     assert_eq!(長門.card_list.len(), 1);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(長門.source(0), Normal);
+        assert_eq!(長門.source_with(CardPageSources::bundled(), 0), Normal);
     }
     assert_eq!(長門.variation_num, 6);
     assert_eq!(長門.acquire_num, 2);
@@ -1404,15 +1493,15 @@ fn parse_fixture_tcbook_info_20240623() {
     assert_eq!(扶桑.book_no, 26);
     assert_eq!(扶桑.ship_class.as_ref().unwrap(), "扶桑型");
     assert_eq!(扶桑.ship_class_index.unwrap(), 1);
-    assert_eq!(扶桑.ship_type, "戦艦");
+    assert_eq!(扶桑.ship_type, Some(ShipType::Battleship));
     assert_eq!(扶桑.ship_model_num, "");
     assert_eq!(扶桑.ship_name, "扶桑");
     assert_eq!(扶桑.card_index_img, "s/tc_26_p9u490qtc1a4.jpg");
     assert_eq!(扶桑.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(扶桑.source(0), Normal);
-        assert_eq!(扶桑.source(1), RainySeason);
+        assert_eq!(扶桑.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(扶桑.source_with(CardPageSources::bundled(), 1), RainySeason);
     }
     assert_eq!(扶桑.variation_num, 12);
     assert_eq!(扶桑.acquire_num, 6);
@@ -1474,16 +1563,19 @@ fn parse_fixture_tcbook_info_20240623() {
     assert_eq!(早霜.book_no, 209);
     assert_eq!(早霜.ship_class.as_ref().unwrap(), "夕雲型");
     assert_eq!(早霜.ship_class_index.unwrap(), 17);
-    assert_eq!(早霜.ship_type, "駆逐艦");
+    assert_eq!(早霜.ship_type, Some(ShipType::Destroyer));
     assert_eq!(早霜.ship_model_num, "");
     assert_eq!(早霜.ship_name, "早霜");
     assert_eq!(早霜.card_index_img, "s/tc_209_6uqm0rr6azd9.jpg");
     assert_eq!(早霜.card_list.len(), 3);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(早霜.source(0), Normal);
-        assert_eq!(早霜.source(1), RainySeason);
-        assert_eq!(早霜.source(2), OriginalIllustration1(true));
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 1), RainySeason);
+        assert_eq!(
+            早霜.source_with(CardPageSources::bundled(), 2),
+            OriginalIllustration1(true)
+        );
     }
     assert_eq!(早霜.variation_num, 13);
     assert_eq!(早霜.acquire_num, 3);
@@ -1544,15 +1636,15 @@ fn parse_fixture_tcbook_info_20240623() {
     assert_eq!(扶桑改二.book_no, 211);
     assert_eq!(扶桑改二.ship_class.as_ref().unwrap(), "扶桑型");
     assert_eq!(扶桑改二.ship_class_index.unwrap(), 1);
-    assert_eq!(扶桑改二.ship_type, "航空戦艦");
+    assert_eq!(扶桑改二.ship_type, Some(ShipType::AviationBattleship));
     assert_eq!(扶桑改二.ship_model_num, "");
     assert_eq!(扶桑改二.ship_name, "扶桑改二");
     assert_eq!(扶桑改二.card_index_img, "s/tc_211_xkrpspyq72qz.jpg");
     assert_eq!(扶桑改二.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(早霜.source(0), Normal);
-        assert_eq!(早霜.source(1), RainySeason);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 1), RainySeason);
     }
     assert_eq!(扶桑改二.variation_num, 6);
     assert_eq!(扶桑改二.acquire_num, 1);
@@ -1586,15 +1678,15 @@ fn parse_fixture_tcbook_info_20240623() {
     assert_eq!(雪風.book_no, 5);
     assert_eq!(雪風.ship_class.as_ref().unwrap(), "陽炎型");
     assert_eq!(雪風.ship_class_index.unwrap(), 8);
-    assert_eq!(雪風.ship_type, "駆逐艦");
+    assert_eq!(雪風.ship_type, Some(ShipType::Destroyer));
     assert_eq!(雪風.ship_model_num, "");
     assert_eq!(雪風.ship_name, "雪風");
     assert_eq!(雪風.card_index_img, "s/tc_5_gc3ynk3f42p4.jpg");
     assert_eq!(雪風.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(雪風.source(0), Normal);
-        assert_eq!(雪風.source(1), Swimsuit);
+        assert_eq!(雪風.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(雪風.source_with(CardPageSources::bundled(), 1), Swimsuit);
     }
     assert_eq!(雪風.variation_num, 9);
     assert_eq!(雪風.acquire_num, 2);
@@ -1645,14 +1737,14 @@ fn parse_fixture_tcbook_info_20241006() {
     assert_eq!(長門.book_no, 1);
     assert_eq!(長門.ship_class.as_ref().unwrap(), "長門型");
     assert_eq!(長門.ship_class_index.unwrap(), 1);
-    assert_eq!(長門.ship_type, "戦艦");
+    assert_eq!(長門.ship_type, Some(ShipType::Battleship));
     assert_eq!(長門.ship_model_num, "");
     assert_eq!(長門.ship_name, "長門");
     assert_eq!(長門.card_index_img, "s/tc_1_d7ju63kolamj.jpg");
     assert_eq!(長門.card_list.len(), 1);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(長門.source(0), Normal);
+        assert_eq!(長門.source_with(CardPageSources::bundled(), 0), Normal);
     }
     assert_eq!(長門.variation_num, 6);
     assert_eq!(長門.acquire_num, 3);
@@ -1686,15 +1778,15 @@ fn parse_fixture_tcbook_info_20241006() {
     assert_eq!(扶桑.book_no, 26);
     assert_eq!(扶桑.ship_class.as_ref().unwrap(), "扶桑型");
     assert_eq!(扶桑.ship_class_index.unwrap(), 1);
-    assert_eq!(扶桑.ship_type, "戦艦");
+    assert_eq!(扶桑.ship_type, Some(ShipType::Battleship));
     assert_eq!(扶桑.ship_model_num, "");
     assert_eq!(扶桑.ship_name, "扶桑");
     assert_eq!(扶桑.card_index_img, "s/tc_26_p9u490qtc1a4.jpg");
     assert_eq!(扶桑.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(扶桑.source(0), Normal);
-        assert_eq!(扶桑.source(1), RainySeason);
+        assert_eq!(扶桑.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(扶桑.source_with(CardPageSources::bundled(), 1), RainySeason);
     }
     assert_eq!(扶桑.variation_num, 12);
     assert_eq!(扶桑.acquire_num, 7);
@@ -1756,16 +1848,19 @@ fn parse_fixture_tcbook_info_20241006() {
     assert_eq!(早霜.book_no, 209);
     assert_eq!(早霜.ship_class.as_ref().unwrap(), "夕雲型");
     assert_eq!(早霜.ship_class_index.unwrap(), 17);
-    assert_eq!(早霜.ship_type, "駆逐艦");
+    assert_eq!(早霜.ship_type, Some(ShipType::Destroyer));
     assert_eq!(早霜.ship_model_num, "");
     assert_eq!(早霜.ship_name, "早霜");
     assert_eq!(早霜.card_index_img, "s/tc_209_6uqm0rr6azd9.jpg");
     assert_eq!(早霜.card_list.len(), 3);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(早霜.source(0), Normal);
-        assert_eq!(早霜.source(1), RainySeason);
-        assert_eq!(早霜.source(2), OriginalIllustration1(true));
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 1), RainySeason);
+        assert_eq!(
+            早霜.source_with(CardPageSources::bundled(), 2),
+            OriginalIllustration1(true)
+        );
     }
     assert_eq!(早霜.variation_num, 13);
     assert_eq!(早霜.acquire_num, 3);
@@ -1826,15 +1921,15 @@ fn parse_fixture_tcbook_info_20241006() {
     assert_eq!(扶桑改二.book_no, 211);
     assert_eq!(扶桑改二.ship_class.as_ref().unwrap(), "扶桑型");
     assert_eq!(扶桑改二.ship_class_index.unwrap(), 1);
-    assert_eq!(扶桑改二.ship_type, "航空戦艦");
+    assert_eq!(扶桑改二.ship_type, Some(ShipType::AviationBattleship));
     assert_eq!(扶桑改二.ship_model_num, "");
     assert_eq!(扶桑改二.ship_name, "扶桑改二");
     assert_eq!(扶桑改二.card_index_img, "s/tc_211_xkrpspyq72qz.jpg");
     assert_eq!(扶桑改二.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(早霜.source(0), Normal);
-        assert_eq!(早霜.source(1), RainySeason);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(早霜.source_with(CardPageSources::bundled(), 1), RainySeason);
     }
     assert_eq!(扶桑改二.variation_num, 6);
     assert_eq!(扶桑改二.acquire_num, 1);
@@ -1872,15 +1967,15 @@ fn parse_fixture_tcbook_info_20241006() {
     assert_eq!(雪風.book_no, 5);
     assert_eq!(雪風.ship_class.as_ref().unwrap(), "陽炎型");
     assert_eq!(雪風.ship_class_index.unwrap(), 8);
-    assert_eq!(雪風.ship_type, "駆逐艦");
+    assert_eq!(雪風.ship_type, Some(ShipType::Destroyer));
     assert_eq!(雪風.ship_model_num, "");
     assert_eq!(雪風.ship_name, "雪風");
     assert_eq!(雪風.card_index_img, "s/tc_5_gc3ynk3f42p4.jpg");
     assert_eq!(雪風.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(雪風.source(0), Normal);
-        assert_eq!(雪風.source(1), Swimsuit);
+        assert_eq!(雪風.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(雪風.source_with(CardPageSources::bundled(), 1), Swimsuit);
     }
     assert_eq!(雪風.variation_num, 9);
     assert_eq!(雪風.acquire_num, 4);
@@ -1936,20 +2031,23 @@ fn test_book_split_haskai_20240623() {
 
     let 長門 = &tcbook[0];
 
-    let (長門_nonkai, 長門_kai) = 長門.clone().into_kai_split();
+    let (長門_nonkai, 長門_kai) = 長門.clone().into_kai_split().unwrap();
     let 長門_kai = 長門_kai.unwrap();
 
     assert_eq!(長門_nonkai.book_no, 1);
     assert_eq!(長門_nonkai.ship_class.as_ref().unwrap(), "長門型");
     assert_eq!(長門_nonkai.ship_class_index.unwrap(), 1);
-    assert_eq!(長門_nonkai.ship_type, "戦艦");
+    assert_eq!(長門_nonkai.ship_type, Some(ShipType::Battleship));
     assert_eq!(長門_nonkai.ship_model_num, "");
     assert_eq!(長門_nonkai.ship_name, "長門");
     assert_eq!(長門_nonkai.card_index_img, "s/tc_1_d7ju63kolamj.jpg");
     assert_eq!(長門_nonkai.card_list.len(), 1);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(長門_nonkai.source(0), Normal);
+        assert_eq!(
+            長門_nonkai.source_with(CardPageSources::bundled(), 0),
+            Normal
+        );
     }
     assert_eq!(長門_nonkai.variation_num, 3);
     assert_eq!(長門_nonkai.acquire_num, 1);
@@ -1977,14 +2075,14 @@ fn test_book_split_haskai_20240623() {
     assert_eq!(長門_kai.book_no, 1);
     assert_eq!(長門_kai.ship_class.as_ref().unwrap(), "長門型");
     assert_eq!(長門_kai.ship_class_index.unwrap(), 1);
-    assert_eq!(長門_kai.ship_type, "戦艦");
+    assert_eq!(長門_kai.ship_type, Some(ShipType::Battleship));
     assert_eq!(長門_kai.ship_model_num, "");
     assert_eq!(長門_kai.ship_name, "長門改");
     assert_eq!(長門_kai.card_index_img, "s/tc_1_d7ju63kolamj.jpg");
     assert_eq!(長門_kai.card_list.len(), 1);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(長門_kai.source(0), Normal);
+        assert_eq!(長門_kai.source_with(CardPageSources::bundled(), 0), Normal);
     }
     assert_eq!(長門_kai.variation_num, 3);
     assert_eq!(長門_kai.acquire_num, 1);
@@ -2009,7 +2107,7 @@ fn test_book_split_haskai_20240623() {
     // Interesting because it has a variation only in its kai form.
     let 雪風 = &tcbook[4];
 
-    let (雪風_nonkai, 雪風_kai) = 雪風.clone().into_kai_split();
+    let (雪風_nonkai, 雪風_kai) = 雪風.clone().into_kai_split().unwrap();
     let 雪風_kai = 雪風_kai.unwrap();
 
     // TODO: acquire_num == 0 basically never appears in real data. What do we do?
@@ -2017,15 +2115,21 @@ fn test_book_split_haskai_20240623() {
     assert_eq!(雪風_nonkai.book_no, 5);
     assert_eq!(雪風_nonkai.ship_class.as_ref().unwrap(), "陽炎型");
     assert_eq!(雪風_nonkai.ship_class_index.unwrap(), 8);
-    assert_eq!(雪風_nonkai.ship_type, "駆逐艦");
+    assert_eq!(雪風_nonkai.ship_type, Some(ShipType::Destroyer));
     assert_eq!(雪風_nonkai.ship_model_num, "");
     assert_eq!(雪風_nonkai.ship_name, "雪風");
     assert_eq!(雪風_nonkai.card_index_img, "s/tc_5_gc3ynk3f42p4.jpg");
     assert_eq!(雪風_nonkai.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(雪風_nonkai.source(0), Normal);
-        assert_eq!(雪風_nonkai.source(1), Swimsuit);
+        assert_eq!(
+            雪風_nonkai.source_with(CardPageSources::bundled(), 0),
+            Normal
+        );
+        assert_eq!(
+            雪風_nonkai.source_with(CardPageSources::bundled(), 1),
+            Swimsuit
+        );
     }
     assert_eq!(雪風_nonkai.variation_num, 3);
     assert_eq!(雪風_nonkai.acquire_num, 2);
@@ -2060,15 +2164,18 @@ fn test_book_split_haskai_20240623() {
     assert_eq!(雪風_kai.book_no, 5);
     assert_eq!(雪風_kai.ship_class.as_ref().unwrap(), "陽炎型");
     assert_eq!(雪風_kai.ship_class_index.unwrap(), 8);
-    assert_eq!(雪風_kai.ship_type, "駆逐艦");
+    assert_eq!(雪風_kai.ship_type, Some(ShipType::Destroyer));
     assert_eq!(雪風_kai.ship_model_num, "");
     assert_eq!(雪風_kai.ship_name, "雪風改");
     assert_eq!(雪風_kai.card_index_img, "s/tc_5_gc3ynk3f42p4.jpg");
     assert_eq!(雪風_kai.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(雪風_kai.source(0), Normal);
-        assert_eq!(雪風_kai.source(1), Swimsuit);
+        assert_eq!(雪風_kai.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(
+            雪風_kai.source_with(CardPageSources::bundled(), 1),
+            Swimsuit
+        );
     }
     assert_eq!(雪風_kai.variation_num, 6);
     assert_eq!(雪風_kai.acquire_num, 0);
@@ -2100,20 +2207,23 @@ fn test_book_split_haskai_20241006() {
 
     let 長門 = &tcbook[0];
 
-    let (長門_nonkai, 長門_kai) = 長門.clone().into_kai_split();
+    let (長門_nonkai, 長門_kai) = 長門.clone().into_kai_split().unwrap();
     let 長門_kai = 長門_kai.unwrap();
 
     assert_eq!(長門_nonkai.book_no, 1);
     assert_eq!(長門_nonkai.ship_class.as_ref().unwrap(), "長門型");
     assert_eq!(長門_nonkai.ship_class_index.unwrap(), 1);
-    assert_eq!(長門_nonkai.ship_type, "戦艦");
+    assert_eq!(長門_nonkai.ship_type, Some(ShipType::Battleship));
     assert_eq!(長門_nonkai.ship_model_num, "");
     assert_eq!(長門_nonkai.ship_name, "長門");
     assert_eq!(長門_nonkai.card_index_img, "s/tc_1_d7ju63kolamj.jpg");
     assert_eq!(長門_nonkai.card_list.len(), 1);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(長門_nonkai.source(0), Normal);
+        assert_eq!(
+            長門_nonkai.source_with(CardPageSources::bundled(), 0),
+            Normal
+        );
     }
     assert_eq!(長門_nonkai.variation_num, 3);
     assert_eq!(長門_nonkai.acquire_num, 2);
@@ -2141,14 +2251,14 @@ fn test_book_split_haskai_20241006() {
     assert_eq!(長門_kai.book_no, 1);
     assert_eq!(長門_kai.ship_class.as_ref().unwrap(), "長門型");
     assert_eq!(長門_kai.ship_class_index.unwrap(), 1);
-    assert_eq!(長門_kai.ship_type, "戦艦");
+    assert_eq!(長門_kai.ship_type, Some(ShipType::Battleship));
     assert_eq!(長門_kai.ship_model_num, "");
     assert_eq!(長門_kai.ship_name, "長門改");
     assert_eq!(長門_kai.card_index_img, "s/tc_1_d7ju63kolamj.jpg");
     assert_eq!(長門_kai.card_list.len(), 1);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(長門_kai.source(0), Normal);
+        assert_eq!(長門_kai.source_with(CardPageSources::bundled(), 0), Normal);
     }
     assert_eq!(長門_kai.variation_num, 3);
     assert_eq!(長門_kai.acquire_num, 1);
@@ -2173,7 +2283,7 @@ fn test_book_split_haskai_20241006() {
     // Interesting because it has a variation only in its kai form.
     let 雪風 = &tcbook[4];
 
-    let (雪風_nonkai, 雪風_kai) = 雪風.clone().into_kai_split();
+    let (雪風_nonkai, 雪風_kai) = 雪風.clone().into_kai_split().unwrap();
     let 雪風_kai = 雪風_kai.unwrap();
 
     // TODO: acquire_num == 0 basically never appears in real data. What do we do?
@@ -2181,15 +2291,21 @@ fn test_book_split_haskai_20241006() {
     assert_eq!(雪風_nonkai.book_no, 5);
     assert_eq!(雪風_nonkai.ship_class.as_ref().unwrap(), "陽炎型");
     assert_eq!(雪風_nonkai.ship_class_index.unwrap(), 8);
-    assert_eq!(雪風_nonkai.ship_type, "駆逐艦");
+    assert_eq!(雪風_nonkai.ship_type, Some(ShipType::Destroyer));
     assert_eq!(雪風_nonkai.ship_model_num, "");
     assert_eq!(雪風_nonkai.ship_name, "雪風");
     assert_eq!(雪風_nonkai.card_index_img, "s/tc_5_gc3ynk3f42p4.jpg");
     assert_eq!(雪風_nonkai.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(雪風_nonkai.source(0), Normal);
-        assert_eq!(雪風_nonkai.source(1), Swimsuit);
+        assert_eq!(
+            雪風_nonkai.source_with(CardPageSources::bundled(), 0),
+            Normal
+        );
+        assert_eq!(
+            雪風_nonkai.source_with(CardPageSources::bundled(), 1),
+            Swimsuit
+        );
     }
     assert_eq!(雪風_nonkai.variation_num, 3);
     assert_eq!(雪風_nonkai.acquire_num, 2);
@@ -2224,15 +2340,18 @@ fn test_book_split_haskai_20241006() {
     assert_eq!(雪風_kai.book_no, 5);
     assert_eq!(雪風_kai.ship_class.as_ref().unwrap(), "陽炎型");
     assert_eq!(雪風_kai.ship_class_index.unwrap(), 8);
-    assert_eq!(雪風_kai.ship_type, "駆逐艦");
+    assert_eq!(雪風_kai.ship_type, Some(ShipType::Destroyer));
     assert_eq!(雪風_kai.ship_model_num, "");
     assert_eq!(雪風_kai.ship_name, "雪風改");
     assert_eq!(雪風_kai.card_index_img, "s/tc_5_gc3ynk3f42p4.jpg");
     assert_eq!(雪風_kai.card_list.len(), 2);
     {
         use BookShipCardPageSource::*;
-        assert_eq!(雪風_kai.source(0), Normal);
-        assert_eq!(雪風_kai.source(1), Swimsuit);
+        assert_eq!(雪風_kai.source_with(CardPageSources::bundled(), 0), Normal);
+        assert_eq!(
+            雪風_kai.source_with(CardPageSources::bundled(), 1),
+            Swimsuit
+        );
     }
     assert_eq!(雪風_kai.variation_num, 6);
     assert_eq!(雪風_kai.acquire_num, 2);
@@ -2273,17 +2392,20 @@ fn test_book_split_haskai_20241006() {
 fn test_sources_against_latest() {
     let mut tcbook = read_tclist(TCBOOK_LATEST.as_ref()).unwrap();
     tcbook.retain(|ship| ship.acquire_num > 0);
-    init_book_ship_sources();
 
     for book_ship in tcbook.iter() {
         let book_no = book_ship.book_no;
-        let source = BOOK_SHIP_SOURCES.get().unwrap().get(&book_no);
+        let source = CardPageSources::bundled()
+            .get(book_no)
+            .map(|book| &book.sources);
         let expected_len = source.map_or_else(|| 0, |s| s.len()) + 1;
         assert_eq!(book_ship.card_list.len(), expected_len, "{book_no}");
     }
 }
 
 #[test]
+// KANSEN is already a &[u8] on wasm32
+#[cfg_attr(target_arch = "wasm32", allow(clippy::useless_asref))]
 fn test_tcbook_entries_missing_from_wiki() {
     // Ensure we know which TCBook entries are missing from the Wiki data (or vice-versa).
     // Not worried about kaizou kansen, since we can't validate unrevealed TCBook entries as two-row or not.
@@ -2322,3 +2444,46 @@ fn test_tcbook_entries_missing_from_wiki() {
     assert!(book_only.contains_key(&511));
     assert_eq!(book_only[&511], "未取得"); // 大和改二
 }
+
+#[test]
+fn parse_tcbook_unknown_field() {
+    let json = r#"[{"bookNo":1,"shipClass":"","shipClassIndex":1,"shipType":"","shipModelNum":"","shipName":"","cardIndexImg":"","cardList":[{"priority":0,"cardImgList":[],"statusImg":[],"variationNumInPage":0,"acquireNumInPage":0,"newPageField":true}],"variationNum":0,"acquireNum":0,"lv":0,"isMarried":[],"marriedImg":[]}]"#;
+
+    let err = read_tclist(json.as_bytes()).unwrap_err();
+    assert!(err.to_string().contains("[0].cardList[0].newPageField"));
+
+    let (tcbook, unknown_fields) = read_tclist_lenient(json.as_bytes()).unwrap();
+    assert_eq!(tcbook.len(), 1);
+    assert_eq!(
+        unknown_fields,
+        vec![UnknownField {
+            path: "[0].cardList[0].newPageField".to_string(),
+            value: serde_json::Value::Bool(true),
+            kind: UnknownKind::Field,
+        }]
+    );
+}
+
+#[test]
+fn parse_tcbook_unknown_value() {
+    let json = r#"[{"bookNo":1,"shipClass":"","shipClassIndex":1,"shipType":"宇宙戦艦","shipModelNum":"","shipName":"","cardIndexImg":"","cardList":[],"variationNum":0,"acquireNum":0,"lv":0,"isMarried":[],"marriedImg":[]}]"#;
+
+    let err = read_tclist(json.as_bytes()).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(r#"unknown value "宇宙戦艦" at `[0].shipType`"#));
+
+    let (tcbook, unknown_fields) = read_tclist_lenient(json.as_bytes()).unwrap();
+    assert_eq!(
+        tcbook[0].ship_type,
+        Some(ShipType::Unknown("宇宙戦艦".to_string()))
+    );
+    assert_eq!(
+        unknown_fields,
+        vec![UnknownField {
+            path: "[0].shipType".to_string(),
+            value: serde_json::Value::String("宇宙戦艦".to_string()),
+            kind: UnknownKind::Value,
+        }]
+    );
+}
@@ -0,0 +1,170 @@
+//! Support for data fields we don't know about yet.
+//!
+//! Every importer struct collects any fields it doesn't recognise into an `extra` map,
+//! and fields holding one of a fixed set of strings, like ShipType, keep values they
+//! don't recognise as `Unknown`.
+//! The default (strict) readers fail if any such field or value is found, so tests catch
+//! them quickly. The lenient readers instead report them alongside the data, so tools
+//! keep working after a game update adds something new.
+
+use super::SlotDisp;
+use crate::ship_attributes::{DamageStatus, Range, ShipType, Speed};
+use serde::de::{DeserializeOwned, Error as _};
+use serde_json::value::RawValue;
+use serde_json::{Map, Result, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Read;
+
+/// What this crate doesn't know about an UnknownField
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnknownKind {
+    /// The field itself
+    Field,
+    /// The value of a field holding one of a fixed set of strings, e.g. a new ShipType
+    Value,
+}
+
+/// A field found in the source data which this crate doesn't know about.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownField {
+    /// Location of the field in the source data, e.g. `[3].cardList[1].newField`
+    pub path: String,
+    pub value: Value,
+    pub kind: UnknownKind,
+}
+
+impl fmt::Display for UnknownField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            UnknownKind::Field => write!(f, "{}: {}", self.path, self.value),
+            UnknownKind::Value => write!(f, "{} = {}", self.path, self.value),
+        }
+    }
+}
+
+/// Walks a parsed data structure to find the contents of all the `extra` maps.
+pub(crate) trait UnknownFields {
+    fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>);
+}
+
+impl<T: UnknownFields> UnknownFields for Vec<T> {
+    fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+        for (index, item) in self.iter().enumerate() {
+            item.unknown_fields(&format!("{path}[{index}]"), found);
+        }
+    }
+}
+
+impl<T: UnknownFields> UnknownFields for Option<T> {
+    fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+        if let Some(item) = self {
+            item.unknown_fields(path, found);
+        }
+    }
+}
+
+impl UnknownFields for Map<String, Value> {
+    fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+        found.extend(self.iter().map(|(key, value)| UnknownField {
+            path: format!("{path}.{key}"),
+            value: value.clone(),
+            kind: UnknownKind::Field,
+        }));
+    }
+}
+
+/// Reports the `Unknown` values of enums with an `unknown_value` method.
+macro_rules! unknown_values {
+    ($($name:ident),*) => {
+        $(
+            impl UnknownFields for $name {
+                fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+                    if let Some(value) = self.unknown_value() {
+                        found.push(UnknownField {
+                            path: path.to_string(),
+                            value: Value::String(value.to_string()),
+                            kind: UnknownKind::Value,
+                        });
+                    }
+                }
+            }
+        )*
+    };
+}
+
+unknown_values!(ShipType, Speed, Range, DamageStatus, SlotDisp);
+
+/// Parses the JSON reader, failing if any unknown fields are present.
+/// The error names the first unknown field or value by its path, and its line and column.
+pub(crate) fn read_strict<T>(mut reader: impl Read) -> Result<T>
+where
+    T: DeserializeOwned + UnknownFields,
+{
+    let mut json = vec![];
+    reader
+        .read_to_end(&mut json)
+        .map_err(serde_json::Error::io)?;
+    let (result, unknown_fields) = read_lenient::<T>(json.as_slice())?;
+    let Some(unknown_field) = unknown_fields.first() else {
+        return Ok(result);
+    };
+    let mut message = match unknown_field.kind {
+        UnknownKind::Field => format!("unknown field `{}`", unknown_field.path),
+        UnknownKind::Value => format!(
+            "unknown value {} at `{}`",
+            unknown_field.value, unknown_field.path
+        ),
+    };
+    if let Some((line, column)) = locate(&json, &unknown_field.path) {
+        message += &format!(" at line {line} column {column}");
+    }
+    Err(serde_json::Error::custom(message))
+}
+
+/// Finds the line and column of the value at `path`, as given in an UnknownField.
+/// Like serde_json's own errors, both start at 1 and columns count bytes.
+fn locate(json: &[u8], path: &str) -> Option<(usize, usize)> {
+    let mut value: &RawValue = serde_json::from_slice(json).ok()?;
+    let mut rest = path;
+    while !rest.is_empty() {
+        if let Some(index_rest) = rest.strip_prefix('[') {
+            let (index, index_rest) = index_rest.split_once(']')?;
+            let items: Vec<&RawValue> = serde_json::from_str(value.get()).ok()?;
+            value = items.get(index.parse::<usize>().ok()?)?;
+            rest = index_rest;
+        } else if let Some(key_rest) = rest.strip_prefix('.') {
+            let (key, key_rest) =
+                key_rest.split_at(key_rest.find(['.', '[']).unwrap_or(key_rest.len()));
+            let mut fields: HashMap<String, &RawValue> = serde_json::from_str(value.get()).ok()?;
+            value = fields.remove(key)?;
+            rest = key_rest;
+        } else {
+            return None;
+        }
+    }
+
+    let before = &json[..value.get().as_ptr() as usize - json.as_ptr() as usize];
+    let line = before.iter().filter(|&&byte| byte == b'\n').count() + 1;
+    let column = before.len()
+        - before
+            .iter()
+            .rposition(|&byte| byte == b'\n')
+            .map_or(0, |newline| newline + 1)
+        + 1;
+    Some((line, column))
+}
+
+/// Parses the JSON reader, also returning any unknown fields that were present.
+pub(crate) fn read_lenient<T>(reader: impl Read) -> Result<(T, Vec<UnknownField>)>
+where
+    T: DeserializeOwned + UnknownFields,
+{
+    let result: T = serde_json::from_reader(reader)?;
+    let mut unknown_fields = vec![];
+    result.unknown_fields("", &mut unknown_fields);
+    Ok((result, unknown_fields))
+}
+
+#[cfg(test)]
+mod tests;
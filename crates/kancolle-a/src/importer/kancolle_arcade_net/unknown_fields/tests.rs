@@ -0,0 +1,53 @@
+use super::*;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Card {
+    ship_type: ShipType,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+impl UnknownFields for Card {
+    fn unknown_fields(&self, path: &str, found: &mut Vec<UnknownField>) {
+        self.extra.unknown_fields(path, found);
+        self.ship_type
+            .unknown_fields(&format!("{path}.shipType"), found);
+    }
+}
+
+#[test]
+fn test_read_strict_unknown_field_location() {
+    let json = "[\n  {\"shipType\": \"駆逐艦\"},\n  {\"shipType\": \"駆逐艦\", \"newField\": 1}\n]";
+
+    // Columns count bytes, as in serde_json's own errors
+    let err = read_strict::<Vec<Card>>(json.as_bytes()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unknown field `[1].newField` at line 3 column 41"
+    );
+
+    let (cards, unknown_fields) = read_lenient::<Vec<Card>>(json.as_bytes()).unwrap();
+    assert_eq!(cards.len(), 2);
+    assert_eq!(unknown_fields.len(), 1);
+}
+
+#[test]
+fn test_read_strict_unknown_value_location() {
+    let json = "[\n  {\"shipType\": \"駆逐艦\"},\n  {\"shipType\": \"新艦種\"}\n]";
+
+    let err = read_strict::<Vec<Card>>(json.as_bytes()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unknown value \"新艦種\" at `[1].shipType` at line 3 column 16"
+    );
+}
+
+#[test]
+fn test_read_strict_syntax_error_location() {
+    let json = "[\n  {\"shipType\": }\n]";
+
+    let err = read_strict::<Vec<Card>>(json.as_bytes()).unwrap_err();
+    assert_eq!((err.line(), err.column()), (2, 16));
+}
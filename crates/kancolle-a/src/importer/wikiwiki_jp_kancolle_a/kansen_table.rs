@@ -1,13 +1,16 @@
 //! Module for importer from https://wikiwiki.jp/kancolle-a/艦船/テーブル or https://wikiwiki.jp/kancolle-a/改造艦船/テーブル
 
+use crate::ship_attributes::{Range, ShipType, Speed};
 use crate::Result;
 use csv::{ReaderBuilder, StringRecord};
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::io::Read;
 
+#[cfg(not(target_arch = "wasm32"))]
 use lazy_static_include::*;
 
+#[cfg(not(target_arch = "wasm32"))]
 lazy_static_include_bytes! {
     // https://wikiwiki.jp/kancolle-a/?cmd=edit&page=艦船%2Fテーブル
     pub(crate) KANSEN => "src/importer/wikiwiki_jp_kancolle_a/kansen_table/艦船_テーブル.txt",
@@ -15,6 +18,12 @@ lazy_static_include_bytes! {
     pub(crate) KAIZOU_KANSEN => "src/importer/wikiwiki_jp_kancolle_a/kansen_table/改造艦船_テーブル.txt",
 }
 
+// wasm32 has no filesystem to lazy-load from in debug builds, so always embed.
+#[cfg(target_arch = "wasm32")]
+pub(crate) static KANSEN: &[u8] = include_bytes!("kansen_table/艦船_テーブル.txt");
+#[cfg(target_arch = "wasm32")]
+pub(crate) static KAIZOU_KANSEN: &[u8] = include_bytes!("kansen_table/改造艦船_テーブル.txt");
+
 // TODO: Yes, I know "kansen" means "ship". Naming is hard.
 type KansenTable = Vec<KansenShip>;
 
@@ -32,7 +41,8 @@ type KansenTable = Vec<KansenShip>;
 // Also, will need to parse links. [[text]] and [[text>page_path]].
 
 /// Clean the given record from wikiwiki format to plain-text CSV
-/// Specifially: Drops the first and last cells, and removes any markup.
+/// Specifially: Drops the first and last cells, and removes any markup, including
+/// footnotes, e.g. `―((航空攻撃の場合))` becomes `―`.
 fn clean_record(original: &StringRecord) -> StringRecord {
     let mut result = StringRecord::with_capacity(original.as_slice().len(), original.len() - 1);
     // TODO: Proper error handling.
@@ -40,8 +50,11 @@ fn clean_record(original: &StringRecord) -> StringRecord {
     // Probably not perfect regexes, but they'll hold.
     let simple_link_regex = Regex::new(r"\[\[([^]]*)\]\]").unwrap();
     let complex_link_regex = Regex::new(r"\[\[([^]>]*)>([^]]*)\]\]").unwrap();
+    let footnote_regex = Regex::new(r"\(\(.*?\)\)").unwrap();
     for field in original.iter().take(original.len() - 1).skip(1) {
         let field = field.replace("&br;", " ");
+        // Before trimming, as footnotes may have whitespace before them
+        let field = footnote_regex.replace_all(&field, "");
         let field = field.trim();
         let field = field.trim_start_matches('~');
         let field = complex_link_regex.replace_all(field, "$1");
@@ -100,7 +113,7 @@ pub(crate) fn read_kansen_table(reader: impl Read) -> Result<KansenTable> {
 /// Optional stats are absent if no one has scanned a level 1 card yet, i.e. 初期値未確認.
 // TODO: Optional stat fields are actually "Unknown", maybe make that an explicit enum?
 // TODO: ship_class_index is "-" for a few ships, should we trim that or make it optional?
-#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(deny_unknown_fields)]
 pub struct KansenShip {
     #[serde(rename = "No.")]
@@ -114,7 +127,7 @@ pub struct KansenShip {
     #[serde(rename = "艦番")]
     pub ship_class_index: String,
     #[serde(rename = "艦種")]
-    pub ship_type: String,
+    pub ship_type: ShipType,
     #[serde(rename = "耐久")]
     pub endurance: u16,
     #[serde(rename = "火力")]
@@ -132,11 +145,11 @@ pub struct KansenShip {
     #[serde(rename = "対潜")]
     pub anti_submarine: Option<u16>,
     #[serde(rename = "速力")]
-    pub speed: String, // Enum
+    pub speed: Speed,
     #[serde(rename = "索敵")]
     pub search: Option<u16>,
     #[serde(rename = "射程")]
-    pub range: String, // Enum
+    pub range: Range,
     #[serde(rename = "運")]
     pub luck: u16,
     #[serde(rename = "備考")]
@@ -1,7 +1,9 @@
 use super::*;
+use crate::ship_attributes::{Range, ShipType, Speed};
 
-// I think we're getting this from super? rustc complains it's unused.
-//use lazy_static_include::*;
+// super only brings this into scope off wasm32, where it lazy-loads the bundled data.
+#[cfg(target_arch = "wasm32")]
+use lazy_static_include::*;
 
 lazy_static_include_bytes! {
 // https://wikiwiki.jp/kancolle-a/?cmd=edit&page=艦船%2Fテーブル
@@ -41,7 +43,7 @@ fn parse_fixture_kansen_2024_09_02() {
         ship_name: "長門".to_string(),
         ship_class: "長門型".to_string(),
         ship_class_index: "1番艦".to_string(),
-        ship_type: "戦艦".to_string(),
+        ship_type: ShipType::Battleship,
         endurance: 80,
         firepower: Some(82),
         armor: Some(75),
@@ -50,9 +52,9 @@ fn parse_fixture_kansen_2024_09_02() {
         anti_aircraft: Some(31),
         aircraft_load: 12,
         anti_submarine: Some(0),
-        speed: "低".to_string(),
+        speed: Speed::Slow,
         search: Some(12),
-        range: "長".to_string(),
+        range: Range::Long,
         luck: 20,
         notes: "長門改,長門改二(No.341)".to_string(),
     };
@@ -78,7 +80,7 @@ fn parse_fixture_kansen_2024_09_08() {
         ship_name: "長門".to_string(),
         ship_class: "長門型".to_string(),
         ship_class_index: "1番艦".to_string(),
-        ship_type: "戦艦".to_string(),
+        ship_type: ShipType::Battleship,
         endurance: 80,
         firepower: Some(82),
         armor: Some(75),
@@ -87,9 +89,9 @@ fn parse_fixture_kansen_2024_09_08() {
         anti_aircraft: Some(31),
         aircraft_load: 12,
         anti_submarine: Some(0),
-        speed: "低".to_string(),
+        speed: Speed::Slow,
         search: Some(12),
-        range: "長".to_string(),
+        range: Range::Long,
         luck: 20,
         notes: "長門改,長門改二(No.341)".to_string(),
     };
@@ -106,7 +108,7 @@ fn parse_fixture_kaizou_kansen_2024_09_08() {
         ship_name: "長門改".to_string(),
         ship_class: "長門型".to_string(),
         ship_class_index: "1番艦".to_string(),
-        ship_type: "戦艦".to_string(),
+        ship_type: ShipType::Battleship,
         endurance: 90,
         firepower: Some(90),
         armor: Some(85),
@@ -115,9 +117,9 @@ fn parse_fixture_kaizou_kansen_2024_09_08() {
         anti_aircraft: Some(33),
         aircraft_load: 12,
         anti_submarine: Some(0),
-        speed: "低速".to_string(),
+        speed: Speed::Slow,
         search: Some(15),
-        range: "長".to_string(),
+        range: Range::Long,
         luck: 32,
         notes: "".to_string(),
     };
@@ -143,6 +145,9 @@ fn test_clean_record() {
         "&br;Leading newline",
         "Trailing newline&br;",
         "&br;", // Newline only
+        "Footnote((A note))",
+        "((Only a footnote))",
+        "[[Link]]((Note with [[Link]])) and ((another))",
         "",
     ]);
     let expected = StringRecord::from(vec![
@@ -161,7 +166,29 @@ fn test_clean_record() {
         "Leading newline",
         "Trailing newline",
         "",
+        "Footnote",
+        "",
+        "Link and",
     ]);
 
     assert_eq!(clean_record(&test_record), expected);
 }
+
+#[test]
+// KANSEN is already a &[u8] on wasm32
+#[cfg_attr(target_arch = "wasm32", allow(clippy::useless_asref))]
+fn parse_kansen_table_footnote() {
+    // Saratoga's range has a footnote: 航空攻撃の場合。砲撃の射程は「中」
+    let kansen_table = read_kansen_table(KANSEN.as_ref()).unwrap();
+    let saratoga = kansen_table
+        .iter()
+        .find(|ship| ship.ship_name == "Saratoga")
+        .unwrap();
+    assert_eq!(saratoga.range, Range::None);
+
+    for ship in &kansen_table {
+        assert!(!matches!(ship.ship_type, ShipType::Unknown(_)));
+        assert!(!matches!(ship.speed, Speed::Unknown(_)));
+        assert!(!matches!(ship.range, Range::Unknown(_)));
+    }
+}
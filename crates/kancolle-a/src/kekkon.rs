@@ -0,0 +1,165 @@
+//! Planning ケッコンカッコカリ (marriage) across the fleet.
+//!
+//! A ShipMod can be married once its Character reaches level 99, if it is on the
+//! kekkon list and its start date has passed. Marriage is per form: the TcBook
+//! records which forms are married in `isMarried`, and the Character List in `married`.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use strum::Display;
+
+use crate::importer::kancolle_arcade_net::Character;
+use crate::ships::{ShipMod, Ships};
+
+/// The level at which a ship can be married
+pub const KEKKON_LEVEL: u16 = 99;
+
+/// Where a ShipMod stands for marriage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Display)]
+pub enum KekkonStatus {
+    /// Level 99 and available, so can be married now
+    Eligible,
+    /// On the kekkon list, but not until the given date
+    NotYetAvailable(NaiveDate),
+    /// Available, but below level 99
+    LevelNeeded,
+    /// Not on the kekkon list, so marriage is not yet possible
+    NotListed,
+    Married,
+}
+
+/// A ShipMod we have a Character for, and its marriage status
+#[derive(Debug, Clone)]
+pub struct KekkonCandidate<'a> {
+    shipmod: &'a ShipMod,
+    character: &'a Character,
+    status: KekkonStatus,
+    married_forms: Vec<&'a str>,
+}
+
+impl<'a> KekkonCandidate<'a> {
+    fn new(
+        shipmod: &'a ShipMod,
+        character: &'a Character,
+        married_forms: Vec<&'a str>,
+        today: NaiveDate,
+    ) -> Self {
+        let status = if character.married || married_forms.contains(&shipmod.name().as_str()) {
+            KekkonStatus::Married
+        } else {
+            match shipmod.kekkon() {
+                None => KekkonStatus::NotListed,
+                Some(kekkon) if kekkon.start_time > today => {
+                    KekkonStatus::NotYetAvailable(kekkon.start_time)
+                }
+                Some(_) if character.lv < KEKKON_LEVEL => KekkonStatus::LevelNeeded,
+                Some(_) => KekkonStatus::Eligible,
+            }
+        };
+        KekkonCandidate {
+            shipmod,
+            character,
+            status,
+            married_forms,
+        }
+    }
+
+    pub fn shipmod(&self) -> &'a ShipMod {
+        self.shipmod
+    }
+
+    pub fn status(&self) -> KekkonStatus {
+        self.status
+    }
+
+    pub fn level(&self) -> u16 {
+        self.character.lv
+    }
+
+    /// Progress through the current level
+    pub fn exp_percent(&self) -> u16 {
+        self.character.exp_percent
+    }
+
+    /// When this ShipMod became, or will become, marriageable, if it's on the kekkon list
+    pub fn start_time(&self) -> Option<NaiveDate> {
+        self.shipmod
+            .kekkon()
+            .as_ref()
+            .map(|kekkon| kekkon.start_time)
+    }
+
+    /// The forms of this ship which are married, per the TcBook.
+    /// Forms share a level, so these show which forms are still to marry.
+    pub fn married_forms(&self) -> &[&'a str] {
+        &self.married_forms
+    }
+}
+
+/// Every ShipMod we have a Character for, highest level first.
+pub struct KekkonPlan<'a> {
+    candidates: Vec<KekkonCandidate<'a>>,
+}
+
+impl<'a> KekkonPlan<'a> {
+    /// `today` decides which kekkon start dates are in the future, and should be the
+    /// date in Japan.
+    /// Requires Character data, otherwise the plan will be empty. TcBook data is needed
+    /// to see married forms we have no Character for.
+    pub fn new(ships: &'a Ships, today: NaiveDate) -> KekkonPlan<'a> {
+        let mut candidates: Vec<KekkonCandidate> = ships
+            .values()
+            .flat_map(|ship| {
+                let married_forms: Vec<&str> = ship
+                    .mods()
+                    .iter()
+                    .filter(|shipmod| {
+                        shipmod.book().as_ref().is_some_and(|book| {
+                            book.is_married.iter().flatten().any(|married| *married)
+                        })
+                    })
+                    .map(|shipmod| shipmod.name().as_str())
+                    .collect();
+                ship.mods().iter().filter_map(move |shipmod| {
+                    let character = shipmod.character().as_ref()?;
+                    Some(KekkonCandidate::new(
+                        shipmod,
+                        character,
+                        married_forms.clone(),
+                        today,
+                    ))
+                })
+            })
+            .collect();
+
+        candidates.sort_by(|left, right| {
+            (right.level(), right.exp_percent())
+                .cmp(&(left.level(), left.exp_percent()))
+                .then_with(|| left.shipmod.cmp(right.shipmod))
+        });
+
+        KekkonPlan { candidates }
+    }
+
+    pub fn candidates(&self) -> &[KekkonCandidate<'a>] {
+        &self.candidates
+    }
+
+    pub fn with_status(&self, status: KekkonStatus) -> impl Iterator<Item = &KekkonCandidate<'a>> {
+        self.candidates
+            .iter()
+            .filter(move |candidate| candidate.status == status)
+    }
+
+    /// The ShipMods which can be married now
+    pub fn eligible(&self) -> impl Iterator<Item = &KekkonCandidate<'a>> {
+        self.with_status(KekkonStatus::Eligible)
+    }
+
+    /// The ShipMods whose kekkon start date is still to come
+    pub fn not_yet_available(&self) -> impl Iterator<Item = &KekkonCandidate<'a>> {
+        self.candidates
+            .iter()
+            .filter(|candidate| matches!(candidate.status, KekkonStatus::NotYetAvailable(_)))
+    }
+}
@@ -1,4 +1,15 @@
+pub mod blueprint_costs;
+pub mod blueprint_forecast;
+pub mod calendar;
+pub mod cards;
+pub mod development;
+pub mod kekkon;
+pub mod planning;
+pub mod remodels;
+pub mod ship_attributes;
+pub mod ship_classes;
 pub mod ships;
+pub mod time;
 
 pub mod importer {
     pub mod kancolle_arcade_net;
@@ -6,4 +17,7 @@ pub mod importer {
 }
 
 pub mod error;
-pub use error::{Error, Result};
+pub use error::{DataSource, Error, Result, Warning};
+
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
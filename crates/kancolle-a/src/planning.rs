@@ -0,0 +1,321 @@
+//! Renovation planning: what to do next for each ship in the collection.
+//!
+//! Each Ship is checked for missing forms, remodels we could build with blueprints or
+//! are working towards, stars still to collect, and ShipMods ready to marry. The
+//! resulting recommendations are ordered by a configurable priority of their kinds.
+
+use chrono::NaiveDate;
+use serde::{Serialize, Serializer};
+use std::cmp::Ordering;
+use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
+
+use crate::kekkon::KekkonPlan;
+use crate::ships::{blueprint_name_from, Ship, ShipMod, Ships};
+
+/// What a Recommendation is for. The default priority is in declaration order.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Display,
+    EnumString,
+    EnumIter,
+)]
+pub enum RecommendationKind {
+    /// No Character for any form, so a drop is needed
+    MissingAll,
+    /// No Character, or no Normal card, for the base form
+    MissingBase,
+    /// The next form is missing, and we have the blueprints to build it
+    Constructable,
+    /// The next form is missing, and the current form is still collecting stars
+    UpgradeAvailable,
+    /// The next form is missing, and the current form has all its stars
+    UpgradeReady,
+    /// The ShipMod can be married now, see kekkon::KekkonStatus::Eligible
+    Kekkonable,
+    /// The last form has stars still to collect
+    StarsNeeded,
+}
+
+/// Something to do for a Ship
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Recommendation<'a> {
+    kind: RecommendationKind,
+    ship_name: &'a str,
+    /// The ShipMod this applies to, if not the whole Ship
+    #[serde(serialize_with = "shipmod_name")]
+    current: Option<&'a ShipMod>,
+    /// The ShipMod to remodel into, for Constructable, UpgradeAvailable and UpgradeReady
+    #[serde(serialize_with = "shipmod_name")]
+    next: Option<&'a ShipMod>,
+    /// The blueprint and large-scale blueprint cost of remodelling into next, if known
+    blueprint_cost: Option<(u16, u8)>,
+    blueprints_held: u16,
+}
+
+fn shipmod_name<S>(shipmod: &Option<&ShipMod>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_some(&shipmod.map(|shipmod| shipmod.name()))
+}
+
+impl<'a> Recommendation<'a> {
+    fn new(kind: RecommendationKind, ship: &'a Ship) -> Self {
+        Recommendation {
+            kind,
+            ship_name: ship.name(),
+            current: None,
+            next: None,
+            blueprint_cost: None,
+            blueprints_held: ship
+                .blueprint()
+                .as_ref()
+                .map_or(0, |blueprint| blueprint.blueprint_total_num),
+        }
+    }
+
+    fn remodel(
+        kind: RecommendationKind,
+        ship: &'a Ship,
+        current: &'a ShipMod,
+        next: &'a ShipMod,
+    ) -> Self {
+        Recommendation {
+            current: Some(current),
+            next: Some(next),
+            blueprint_cost: ship.shipmod_blueprint_cost(next.remodel_level()),
+            ..Recommendation::new(kind, ship)
+        }
+    }
+
+    fn shipmod(kind: RecommendationKind, ship: &'a Ship, current: &'a ShipMod) -> Self {
+        Recommendation {
+            current: Some(current),
+            ..Recommendation::new(kind, ship)
+        }
+    }
+
+    pub fn kind(&self) -> RecommendationKind {
+        self.kind
+    }
+
+    /// The base ship name
+    pub fn ship_name(&self) -> &'a str {
+        self.ship_name
+    }
+
+    pub fn current(&self) -> Option<&'a ShipMod> {
+        self.current
+    }
+
+    pub fn next(&self) -> Option<&'a ShipMod> {
+        self.next
+    }
+
+    /// The blueprint and large-scale blueprint cost of remodelling into next, if known
+    pub fn blueprint_cost(&self) -> Option<(u16, u8)> {
+        self.blueprint_cost
+    }
+
+    pub fn blueprints_held(&self) -> u16 {
+        self.blueprints_held
+    }
+
+    /// The stars on the current ShipMod, if we have its Character
+    pub fn stars(&self) -> Option<u16> {
+        self.current?
+            .character()
+            .as_ref()
+            .map(|character| character.star_num)
+    }
+
+    /// Why this was recommended, for display
+    pub fn reason(&self) -> String {
+        use RecommendationKind::*;
+        let current = self.current.map_or("", |shipmod| shipmod.name());
+        let next = self.next.map_or("", |shipmod| shipmod.name());
+        let blueprints = match self.blueprint_cost {
+            Some((cost, _)) => format!("{}/{cost} blueprints", self.blueprints_held),
+            None => format!("{} blueprints", self.blueprints_held),
+        };
+        let stars = self.stars().unwrap_or(0);
+        match self.kind {
+            MissingAll => "no form collected".to_string(),
+            MissingBase => "base form not collected".to_string(),
+            Constructable => format!("{blueprints}, enough to build {next}"),
+            UpgradeAvailable => format!("{current} at {stars}/5 stars, {blueprints} for {next}"),
+            UpgradeReady => format!("{current} at 5/5 stars, {blueprints} for {next}"),
+            Kekkonable => format!("{current} is level 99 or above and not married"),
+            StarsNeeded => format!("{current} at {stars}/5 stars"),
+        }
+    }
+}
+
+pub struct RenovationPlanner {
+    priorities: Vec<RecommendationKind>,
+    character_only: bool,
+    ship_names: Vec<String>,
+}
+
+impl Default for RenovationPlanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RenovationPlanner {
+    pub fn new() -> RenovationPlanner {
+        RenovationPlanner {
+            priorities: RecommendationKind::iter().collect(),
+            character_only: false,
+            ship_names: vec![],
+        }
+    }
+
+    /// Recommend only these kinds, highest priority first.
+    pub fn priorities(mut self, priorities: Vec<RecommendationKind>) -> RenovationPlanner {
+        self.priorities = priorities;
+        self
+    }
+
+    /// Only consider a form missing if we have no Character for it, rather than also
+    /// if we are missing its Normal card.
+    pub fn character_only(mut self, character_only: bool) -> RenovationPlanner {
+        self.character_only = character_only;
+        self
+    }
+
+    /// Only consider these ships, by the name of any of their forms.
+    pub fn ship_names(mut self, ship_names: Vec<String>) -> RenovationPlanner {
+        self.ship_names = ship_names;
+        self
+    }
+
+    /// No Character, or if not character_only, no book entry or Normal card.
+    fn missing(&self, shipmod: &ShipMod) -> bool {
+        shipmod.character().is_none()
+            || (!self.character_only
+                && shipmod.book().as_ref().is_none_or(|book| {
+                    book.card_list
+                        .first()
+                        .is_none_or(|page| page.card_img_list[0].is_empty())
+                }))
+    }
+
+    fn ship_recommendations<'a>(
+        &self,
+        ship: &'a Ship,
+        kekkon_plan: &KekkonPlan<'a>,
+        recommendations: &mut Vec<Recommendation<'a>>,
+    ) {
+        use RecommendationKind::*;
+
+        if ship.mods().is_empty()
+            || ship
+                .mods()
+                .iter()
+                .all(|shipmod| shipmod.character().is_none())
+        {
+            recommendations.push(Recommendation::new(MissingAll, ship));
+            return;
+        }
+
+        if self.missing(ship.mods().first().unwrap()) {
+            recommendations.push(Recommendation::new(MissingBase, ship));
+        }
+
+        for candidate in kekkon_plan
+            .eligible()
+            .filter(|candidate| ship.shipmod_by_name(candidate.shipmod().name()).is_some())
+        {
+            recommendations.push(Recommendation::shipmod(
+                Kekkonable,
+                ship,
+                candidate.shipmod(),
+            ));
+        }
+
+        for pair in ship.mods().windows(2) {
+            let (current, next) = (&pair[0], &pair[1]);
+            let Some(character) = current.character() else {
+                continue;
+            };
+            if !self.missing(next) {
+                continue;
+            }
+
+            let constructable = ship.blueprint().as_ref().is_some_and(|blueprint| {
+                ship.shipmod_blueprint_cost(next.remodel_level())
+                    .is_some_and(|(cost, _)| blueprint.blueprint_total_num >= cost)
+            });
+            let kind = if constructable {
+                Constructable
+            } else if character.star_num == 5 {
+                UpgradeReady
+            } else {
+                UpgradeAvailable
+            };
+            recommendations.push(Recommendation::remodel(kind, ship, current, next));
+        }
+
+        let last = ship.mods().last().unwrap();
+        if last
+            .character()
+            .as_ref()
+            .is_some_and(|character| character.star_num < 5)
+        {
+            recommendations.push(Recommendation::shipmod(StarsNeeded, ship, last));
+        }
+    }
+
+    /// Recommendations for every Ship, highest priority first. Within a kind, fewest
+    /// stars first, then in the game's order.
+    /// `today` is the date in Japan, which decides which ShipMods can be married.
+    pub fn plan<'a>(&self, ships: &'a Ships, today: NaiveDate) -> Vec<Recommendation<'a>> {
+        let ship_names: Vec<&str> = self
+            .ship_names
+            .iter()
+            .map(|ship_name| blueprint_name_from(ships.remodels(), ship_name))
+            .collect();
+        let kekkon_plan = KekkonPlan::new(ships, today);
+
+        let mut ship_list: Vec<&Ship> = ships
+            .values()
+            .filter(|ship| ship_names.is_empty() || ship_names.contains(&ship.name().as_str()))
+            .collect();
+        ship_list.sort();
+
+        let mut recommendations = vec![];
+        for ship in ship_list {
+            self.ship_recommendations(ship, &kekkon_plan, &mut recommendations);
+        }
+
+        let priority = |recommendation: &Recommendation| {
+            self.priorities
+                .iter()
+                .position(|kind| *kind == recommendation.kind)
+        };
+        recommendations.retain(|recommendation| priority(recommendation).is_some());
+        // Stable, so the game order of the ships is kept within the same stars.
+        recommendations.sort_by(|left, right| {
+            priority(left)
+                .cmp(&priority(right))
+                .then_with(|| match left.kind {
+                    RecommendationKind::UpgradeAvailable | RecommendationKind::StarsNeeded => {
+                        left.stars().cmp(&right.stars())
+                    }
+                    _ => Ordering::Equal,
+                })
+        });
+        recommendations
+    }
+}
@@ -0,0 +1,241 @@
+//! The remodel (改造) graph: which forms of a ship can be remodelled into which others.
+//!
+//! Most ships remodel in a straight line, e.g. 鳥海 → 鳥海改 → 鳥海改二, but some
+//! are renamed (春日丸 → 大鷹), and some 改二 forms can be converted back and forth
+//! (最上改二 ⇄ 最上改二特). The bundled data is in `remodels/remodels.json`, and
+//! includes some forms from the web version which are not yet in Kancolle Arcade,
+//! e.g. 鈴谷航改二 and 熊野航改二.
+//!
+//! Ship level requirements are only given for the 改二 branches, i.e. the conversions
+//! and the remodels leading to them, and are those of the web version.
+
+use derive_getters::Getters;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::sync::OnceLock;
+
+use crate::{Error, Result};
+
+#[cfg(not(target_arch = "wasm32"))]
+use lazy_static_include::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+lazy_static_include_bytes! {
+    pub(crate) REMODELS => "src/remodels/remodels.json",
+}
+
+// wasm32 has no filesystem to lazy-load from in debug builds, so always embed.
+#[cfg(target_arch = "wasm32")]
+pub(crate) static REMODELS: &[u8] = include_bytes!("remodels/remodels.json");
+
+static BUNDLED: OnceLock<RemodelGraph> = OnceLock::new();
+
+/// A form of a ship, e.g. 千代田航
+#[derive(Debug, Clone, PartialEq, Eq, Getters, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemodelForm {
+    name: String,
+
+    /// The number of remodels from the base ship, matching the Character List's `remodelLv`.
+    /// Forms on different branches may share a remodel level.
+    level: u16,
+}
+
+/// What is needed to perform a remodel, where known.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Getters, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemodelRequirements {
+    /// The minimum ship level
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ship_level: Option<u16>,
+}
+
+/// An edge in the remodel graph
+#[derive(Debug, Clone, PartialEq, Eq, Getters, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Remodel {
+    from: String,
+    to: String,
+
+    /// True for conversions (転換改装) between forms which can be switched back and
+    /// forth. Each direction is a separate Remodel.
+    #[serde(default)]
+    conversion: bool,
+
+    #[serde(default)]
+    requirements: RemodelRequirements,
+}
+
+/// All the forms of a single ship, and the remodels between them.
+#[derive(Debug, Clone, Getters, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemodelLine {
+    /// Base ship name, i.e. the form which is level 0
+    ship: String,
+
+    /// In remodel level order
+    forms: Vec<RemodelForm>,
+
+    #[serde(default)]
+    remodels: Vec<Remodel>,
+}
+
+impl RemodelLine {
+    fn validate(&self) -> Result<()> {
+        let inconsistent = |reason: String| {
+            Err(Error::InconsistentShip {
+                ship_name: self.ship.clone(),
+                reason,
+            })
+        };
+
+        match self.forms.first() {
+            Some(form) if form.name == self.ship && form.level == 0 => (),
+            _ => return inconsistent("remodel line does not start at the base ship".to_string()),
+        }
+        if !self.forms.is_sorted_by_key(|form| form.level) {
+            return inconsistent("forms are not in remodel level order".to_string());
+        }
+
+        let form = |name: &str| self.forms.iter().find(|form| form.name == name);
+        for remodel in self.remodels.iter() {
+            let (Some(from), Some(to)) = (form(&remodel.from), form(&remodel.to)) else {
+                return inconsistent(format!(
+                    "remodel {} → {} is not between forms of this ship",
+                    remodel.from, remodel.to
+                ));
+            };
+            if !remodel.conversion && to.level <= from.level {
+                return inconsistent(format!(
+                    "remodel {} → {} does not raise the remodel level",
+                    remodel.from, remodel.to
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The remodel graph for all ships we know of.
+#[derive(Debug, Clone)]
+pub struct RemodelGraph {
+    lines: Vec<RemodelLine>,
+
+    /// Form name to index in lines
+    forms: HashMap<String, usize>,
+}
+
+impl RemodelGraph {
+    /// The remodel graph bundled with this crate
+    // REMODELS is already a &[u8] on wasm32
+    #[cfg_attr(target_arch = "wasm32", allow(clippy::useless_asref))]
+    pub fn bundled() -> &'static RemodelGraph {
+        BUNDLED.get_or_init(|| {
+            RemodelGraph::from_reader(REMODELS.as_ref()).expect("bundled remodels.json is valid")
+        })
+    }
+
+    /// Parses a RemodelGraph from the provided JSON reader, in the same format as the
+    /// bundled `remodels.json`.
+    /// Fails if a form appears twice, or a remodel is not between forms of the same ship.
+    pub fn from_reader(reader: impl Read) -> Result<RemodelGraph> {
+        let lines: Vec<RemodelLine> = serde_json::from_reader(reader)?;
+
+        let mut forms = HashMap::new();
+        for (index, line) in lines.iter().enumerate() {
+            line.validate()?;
+            for form in line.forms.iter() {
+                if forms.insert(form.name.clone(), index).is_some() {
+                    return Err(Error::InconsistentShip {
+                        ship_name: line.ship.clone(),
+                        reason: format!("{} is already a form of another ship", form.name),
+                    });
+                }
+            }
+        }
+
+        Ok(RemodelGraph { lines, forms })
+    }
+
+    /// All the remodel lines, one per base ship
+    pub fn lines(&self) -> &[RemodelLine] {
+        &self.lines
+    }
+
+    /// The remodel line containing the given form
+    pub fn line(&self, form_name: &str) -> Option<&RemodelLine> {
+        self.forms.get(form_name).map(|&index| &self.lines[index])
+    }
+
+    pub fn form(&self, form_name: &str) -> Option<&RemodelForm> {
+        self.line(form_name)?
+            .forms
+            .iter()
+            .find(|form| form.name == form_name)
+    }
+
+    /// The base ship name for the given form, e.g. 春日丸 for 大鷹改
+    pub fn base_name(&self, form_name: &str) -> Option<&str> {
+        self.line(form_name).map(|line| line.ship.as_str())
+    }
+
+    pub fn remodel_level(&self, form_name: &str) -> Option<u16> {
+        self.form(form_name).map(|form| form.level)
+    }
+
+    /// The remodels available from the given form, including conversions.
+    pub fn next_forms(&self, form_name: &str) -> Vec<&Remodel> {
+        self.line(form_name)
+            .map(|line| {
+                line.remodels
+                    .iter()
+                    .filter(|remodel| remodel.from == form_name)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// True if the two forms are alternatives to each other, i.e. one is converted into
+    /// the other, or both are remodelled from the same form, so may share a remodel level.
+    pub fn are_branches(&self, form_name: &str, other_name: &str) -> bool {
+        let Some(line) = self.line(form_name) else {
+            return false;
+        };
+        let converted = |from: &str, to: &str| {
+            line.remodels
+                .iter()
+                .any(|remodel| remodel.conversion && remodel.from == from && remodel.to == to)
+        };
+        let parents = |name: &str| -> Vec<&str> {
+            line.remodels
+                .iter()
+                .filter(|remodel| remodel.to == name && !remodel.conversion)
+                .map(|remodel| remodel.from.as_str())
+                .collect()
+        };
+
+        form_name != other_name
+            && (converted(form_name, other_name)
+                || converted(other_name, form_name)
+                || parents(form_name)
+                    .iter()
+                    .any(|parent| parents(other_name).contains(parent)))
+    }
+
+    /// The remodels leading to the given form, including conversions.
+    pub fn previous_forms(&self, form_name: &str) -> Vec<&Remodel> {
+        self.line(form_name)
+            .map(|line| {
+                line.remodels
+                    .iter()
+                    .filter(|remodel| remodel.to == form_name)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests;
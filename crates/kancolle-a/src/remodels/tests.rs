@@ -0,0 +1,141 @@
+use super::*;
+
+#[test]
+fn parse_bundled_remodels() {
+    let remodels = RemodelGraph::bundled();
+
+    assert_eq!(remodels.base_name("大鷹改"), Some("春日丸"));
+    assert_eq!(remodels.remodel_level("千代田航改二"), Some(5));
+    assert_eq!(remodels.base_name("鳥海改三"), None);
+
+    // Every form is in exactly one line, starting at the base ship.
+    for line in remodels.lines() {
+        assert_eq!(remodels.remodel_level(line.ship()), Some(0));
+        for form in line.forms() {
+            assert_eq!(remodels.base_name(form.name()), Some(line.ship().as_str()));
+        }
+    }
+}
+
+#[test]
+fn remodel_graph_next_forms() {
+    let remodels = RemodelGraph::bundled();
+
+    let next_names = |form_name: &str| -> Vec<(&str, bool)> {
+        remodels
+            .next_forms(form_name)
+            .into_iter()
+            .map(|remodel| (remodel.to().as_str(), *remodel.conversion()))
+            .collect()
+    };
+
+    // Linear
+    assert_eq!(next_names("千代田改"), vec![("千代田甲", false)]);
+    assert_eq!(next_names("千代田甲"), vec![("千代田航", false)]);
+    assert_eq!(next_names("千代田航改二"), vec![]);
+    assert_eq!(next_names("春日丸"), vec![("大鷹", false)]);
+    assert_eq!(next_names("響改"), vec![("Верный", false)]);
+
+    // Conversions
+    assert_eq!(next_names("最上改二"), vec![("最上改二特", true)]);
+    assert_eq!(next_names("最上改二特"), vec![("最上改二", true)]);
+    assert_eq!(next_names("龍鳳改二戊"), vec![("龍鳳改二", true)]);
+
+    assert_eq!(
+        remodels
+            .previous_forms("最上改二")
+            .into_iter()
+            .map(|remodel| remodel.from().as_str())
+            .collect::<Vec<_>>(),
+        vec!["最上改", "最上改二特"]
+    );
+
+    // Unknown
+    assert!(remodels.next_forms("鳥海改三").is_empty());
+}
+
+#[test]
+fn remodel_graph_requirements() {
+    let remodels = RemodelGraph::bundled();
+
+    let ship_level = |from: &str, to: &str| -> Option<u16> {
+        remodels
+            .next_forms(from)
+            .into_iter()
+            .find(|remodel| remodel.to() == to)
+            .unwrap()
+            .requirements()
+            .ship_level()
+            .to_owned()
+    };
+
+    assert_eq!(ship_level("朝潮改", "朝潮改二"), Some(70));
+    assert_eq!(ship_level("朝潮改二", "朝潮改二丁"), Some(85));
+    assert_eq!(ship_level("朝潮改二丁", "朝潮改二"), Some(70));
+    assert_eq!(ship_level("龍鳳改二戊", "龍鳳改二"), Some(95));
+    assert_eq!(ship_level("鳥海", "鳥海改"), None);
+
+    // Every conversion has a way back
+    for line in remodels.lines() {
+        for remodel in line
+            .remodels()
+            .iter()
+            .filter(|remodel| *remodel.conversion())
+        {
+            assert!(remodels
+                .next_forms(remodel.to())
+                .iter()
+                .any(|back| back.to() == remodel.from() && *back.conversion()));
+        }
+    }
+}
+
+#[test]
+fn remodel_graph_branches() {
+    let remodels = RemodelGraph::bundled();
+
+    // Conversions
+    assert!(remodels.are_branches("龍鳳改二", "龍鳳改二戊"));
+    assert!(remodels.are_branches("龍鳳改二戊", "龍鳳改二"));
+    assert!(remodels.are_branches("鈴谷改二", "鈴谷航改二"));
+    // Successive forms, and forms of different ships
+    assert!(!remodels.are_branches("龍鳳改", "龍鳳改二戊"));
+    assert!(!remodels.are_branches("龍鳳", "龍鳳改二"));
+    assert!(!remodels.are_branches("龍鳳改二", "龍鳳改二"));
+    assert!(!remodels.are_branches("最上改二", "鈴谷改二"));
+    assert!(!remodels.are_branches("鳥海改三", "鳥海改二"));
+
+    // Both remodelled from the same form
+    let forked = r#"[
+        {"ship": "A", "forms": [{"name": "A", "level": 0}, {"name": "A改", "level": 1}, {"name": "A乙", "level": 1}],
+         "remodels": [{"from": "A", "to": "A改"}, {"from": "A", "to": "A乙"}]}
+    ]"#;
+    let forked = RemodelGraph::from_reader(forked.as_bytes()).unwrap();
+    assert!(forked.are_branches("A改", "A乙"));
+    assert!(!forked.are_branches("A", "A乙"));
+}
+
+#[test]
+fn parse_invalid_remodels() {
+    let duplicate_form = r#"[
+        {"ship": "最上", "forms": [{"name": "最上", "level": 0}]},
+        {"ship": "三隈", "forms": [{"name": "三隈", "level": 0}, {"name": "最上", "level": 1}]}
+    ]"#;
+    assert!(matches!(
+        RemodelGraph::from_reader(duplicate_form.as_bytes()),
+        Err(Error::InconsistentShip { ship_name, .. }) if ship_name == "三隈"
+    ));
+
+    let foreign_remodel = r#"[
+        {"ship": "最上", "forms": [{"name": "最上", "level": 0}], "remodels": [{"from": "最上", "to": "三隈"}]}
+    ]"#;
+    assert!(matches!(
+        RemodelGraph::from_reader(foreign_remodel.as_bytes()),
+        Err(Error::InconsistentShip { ship_name, .. }) if ship_name == "最上"
+    ));
+
+    let backwards_remodel = r#"[
+        {"ship": "最上", "forms": [{"name": "最上", "level": 0}, {"name": "最上改", "level": 1}], "remodels": [{"from": "最上改", "to": "最上"}]}
+    ]"#;
+    assert!(RemodelGraph::from_reader(backwards_remodel.as_bytes()).is_err());
+}
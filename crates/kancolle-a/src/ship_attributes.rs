@@ -0,0 +1,240 @@
+//! Enumerated attributes of ships, e.g. ship type or speed.
+//!
+//! Each value (de)serializes as it appears in the source data, and also has Japanese
+//! and English names for display. Values this crate doesn't know about yet are kept as
+//! `Unknown`, and reported by the lenient readers, see UnknownField.
+
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use strum::{Display, EnumIter, EnumProperty, EnumString, IntoStaticStr};
+
+/// Serialize and Deserialize via AsRef<str> and strum's FromStr, and display names via
+/// the `ja` and `en` strum properties.
+macro_rules! ship_attribute {
+    ($name:ident) => {
+        impl $name {
+            /// The Japanese name, as used in-game. For Unknown, the value from the source data.
+            pub fn japanese_name(&self) -> &str {
+                match self {
+                    $name::Unknown(value) => value,
+                    _ => self.get_str("ja").unwrap(),
+                }
+            }
+
+            /// The English name. For Unknown, the value from the source data.
+            pub fn english_name(&self) -> &str {
+                match self {
+                    $name::Unknown(value) => value,
+                    _ => self.get_str("en").unwrap(),
+                }
+            }
+
+            /// The value from the source data, if this crate doesn't know it
+            pub fn unknown_value(&self) -> Option<&str> {
+                match self {
+                    $name::Unknown(value) => Some(value),
+                    _ => None,
+                }
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                match self {
+                    $name::Unknown(value) => value,
+                    known => known.into(),
+                }
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_str(self.as_ref())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                // Never fails, as unknown values parse as Unknown
+                String::deserialize(deserializer)?
+                    .parse()
+                    .map_err(D::Error::custom)
+            }
+        }
+    };
+}
+
+/// 艦種
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, IntoStaticStr, Display, EnumString, EnumIter, EnumProperty,
+)]
+pub enum ShipType {
+    #[strum(serialize = "駆逐艦", props(ja = "駆逐艦", en = "Destroyer"))]
+    Destroyer,
+    #[strum(serialize = "軽巡洋艦", props(ja = "軽巡洋艦", en = "Light Cruiser"))]
+    LightCruiser,
+    #[strum(
+        serialize = "重雷装巡洋艦",
+        props(ja = "重雷装巡洋艦", en = "Torpedo Cruiser")
+    )]
+    TorpedoCruiser,
+    #[strum(
+        serialize = "練習巡洋艦",
+        props(ja = "練習巡洋艦", en = "Training Cruiser")
+    )]
+    TrainingCruiser,
+    #[strum(
+        serialize = "防空巡洋艦",
+        props(ja = "防空巡洋艦", en = "Anti-Aircraft Cruiser")
+    )]
+    AntiAircraftCruiser,
+    #[strum(
+        serialize = "軽(航空)巡洋艦",
+        props(ja = "軽(航空)巡洋艦", en = "Light (Aviation) Cruiser")
+    )]
+    LightAviationCruiser,
+    #[strum(serialize = "重巡洋艦", props(ja = "重巡洋艦", en = "Heavy Cruiser"))]
+    HeavyCruiser,
+    #[strum(
+        serialize = "航空巡洋艦",
+        props(ja = "航空巡洋艦", en = "Aviation Cruiser")
+    )]
+    AviationCruiser,
+    #[strum(
+        serialize = "改装航空巡洋艦",
+        props(ja = "改装航空巡洋艦", en = "Converted Aviation Cruiser")
+    )]
+    ConvertedAviationCruiser,
+    #[strum(
+        serialize = "特殊改装航空巡洋艦",
+        props(ja = "特殊改装航空巡洋艦", en = "Special Converted Aviation Cruiser")
+    )]
+    SpecialConvertedAviationCruiser,
+    #[strum(serialize = "戦艦", props(ja = "戦艦", en = "Battleship"))]
+    Battleship,
+    #[strum(serialize = "高速戦艦", props(ja = "高速戦艦", en = "Fast Battleship"))]
+    FastBattleship,
+    #[strum(
+        serialize = "航空戦艦",
+        props(ja = "航空戦艦", en = "Aviation Battleship")
+    )]
+    AviationBattleship,
+    #[strum(
+        serialize = "改装航空戦艦",
+        props(ja = "改装航空戦艦", en = "Converted Aviation Battleship")
+    )]
+    ConvertedAviationBattleship,
+    #[strum(
+        serialize = "軽空母",
+        props(ja = "軽空母", en = "Light Aircraft Carrier")
+    )]
+    LightCarrier,
+    #[strum(
+        serialize = "正規空母",
+        props(ja = "正規空母", en = "Aircraft Carrier")
+    )]
+    StandardCarrier,
+    #[strum(
+        serialize = "装甲空母",
+        props(ja = "装甲空母", en = "Armored Aircraft Carrier")
+    )]
+    ArmoredCarrier,
+    #[strum(
+        serialize = "水上機母艦",
+        props(ja = "水上機母艦", en = "Seaplane Tender")
+    )]
+    SeaplaneTender,
+    #[strum(serialize = "潜水艦", props(ja = "潜水艦", en = "Submarine"))]
+    Submarine,
+    #[strum(
+        serialize = "潜水空母",
+        props(ja = "潜水空母", en = "Aircraft Carrying Submarine")
+    )]
+    AircraftCarryingSubmarine,
+    #[strum(
+        serialize = "潜水母艦",
+        props(ja = "潜水母艦", en = "Submarine Tender")
+    )]
+    SubmarineTender,
+    #[strum(
+        serialize = "揚陸艦",
+        props(ja = "揚陸艦", en = "Amphibious Assault Ship")
+    )]
+    AmphibiousAssaultShip,
+    #[strum(serialize = "工作艦", props(ja = "工作艦", en = "Repair Ship"))]
+    RepairShip,
+    #[strum(serialize = "補給艦", props(ja = "補給艦", en = "Fleet Oiler"))]
+    FleetOiler,
+    /// A value this crate doesn't know about yet
+    #[strum(default)]
+    Unknown(String),
+}
+
+ship_attribute!(ShipType);
+
+/// 速力. The wiki sometimes abbreviates these to 高 and 低.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, IntoStaticStr, Display, EnumString, EnumIter, EnumProperty,
+)]
+pub enum Speed {
+    #[strum(to_string = "高速", serialize = "高", props(ja = "高速", en = "Fast"))]
+    Fast,
+    #[strum(to_string = "低速", serialize = "低", props(ja = "低速", en = "Slow"))]
+    Slow,
+    /// A value this crate doesn't know about yet
+    #[strum(default)]
+    Unknown(String),
+}
+
+ship_attribute!(Speed);
+
+/// 射程
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, IntoStaticStr, Display, EnumString, EnumIter, EnumProperty,
+)]
+pub enum Range {
+    /// Ships which do not shell at all, e.g. submarines
+    #[strum(serialize = "―", props(ja = "―", en = "None"))]
+    None,
+    #[strum(serialize = "短", props(ja = "短", en = "Short"))]
+    Short,
+    #[strum(serialize = "中", props(ja = "中", en = "Medium"))]
+    Medium,
+    #[strum(serialize = "長", props(ja = "長", en = "Long"))]
+    Long,
+    #[strum(serialize = "超長", props(ja = "超長", en = "Very Long"))]
+    VeryLong,
+    /// A value this crate doesn't know about yet
+    #[strum(default)]
+    Unknown(String),
+}
+
+ship_attribute!(Range);
+
+/// Damage state of a ship, as reported by the Character List.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, IntoStaticStr, Display, EnumString, EnumIter, EnumProperty,
+)]
+pub enum DamageStatus {
+    #[strum(serialize = "NORMAL", props(ja = "無傷", en = "Undamaged"))]
+    Normal,
+    #[strum(serialize = "S_BREAK", props(ja = "小破", en = "Lightly Damaged"))]
+    LightDamage,
+    #[strum(serialize = "M_BREAK", props(ja = "中破", en = "Moderately Damaged"))]
+    ModerateDamage,
+    #[strum(serialize = "L_BREAK", props(ja = "大破", en = "Heavily Damaged"))]
+    HeavyDamage,
+    /// A value this crate doesn't know about yet
+    #[strum(default)]
+    Unknown(String),
+}
+
+ship_attribute!(DamageStatus);
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,52 @@
+use super::*;
+
+use strum::IntoEnumIterator;
+
+#[test]
+fn test_ship_attribute_names() {
+    assert_eq!(ShipType::Destroyer.japanese_name(), "駆逐艦");
+    assert_eq!(ShipType::Destroyer.english_name(), "Destroyer");
+    assert_eq!(DamageStatus::ModerateDamage.japanese_name(), "中破");
+    assert_eq!(DamageStatus::ModerateDamage.to_string(), "M_BREAK");
+
+    // Every value must have both names.
+    for ship_type in ShipType::iter() {
+        assert_eq!(ship_type.japanese_name(), ship_type.as_ref());
+        ship_type.english_name();
+    }
+    for speed in Speed::iter() {
+        assert_eq!(speed.japanese_name(), speed.as_ref());
+        speed.english_name();
+    }
+    for range in Range::iter() {
+        assert_eq!(range.japanese_name(), range.as_ref());
+        range.english_name();
+    }
+    for damage_status in DamageStatus::iter() {
+        damage_status.japanese_name();
+        damage_status.english_name();
+    }
+}
+
+#[test]
+fn test_ship_attribute_serde() {
+    let speed: Speed = serde_json::from_str(r#""高""#).unwrap();
+    assert_eq!(speed, Speed::Fast);
+    assert_eq!(serde_json::to_string(&speed).unwrap(), r#""高速""#);
+
+    let damage_status: DamageStatus = serde_json::from_str(r#""S_BREAK""#).unwrap();
+    assert_eq!(damage_status, DamageStatus::LightDamage);
+    assert_eq!(
+        serde_json::to_string(&damage_status).unwrap(),
+        r#""S_BREAK""#
+    );
+
+    // Values we don't know are kept, to be reported by the lenient readers
+    let ship_type: ShipType = serde_json::from_str(r#""宇宙戦艦""#).unwrap();
+    assert_eq!(ship_type, ShipType::Unknown("宇宙戦艦".to_string()));
+    assert_eq!(ship_type.unknown_value(), Some("宇宙戦艦"));
+    assert_eq!(ship_type.japanese_name(), "宇宙戦艦");
+    assert_eq!(ship_type.english_name(), "宇宙戦艦");
+    assert_eq!(serde_json::to_string(&ship_type).unwrap(), r#""宇宙戦艦""#);
+    assert_eq!(ShipType::Destroyer.unknown_value(), None);
+}
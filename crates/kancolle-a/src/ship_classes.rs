@@ -0,0 +1,106 @@
+//! Ship classes (艦型), e.g. 陽炎型, and the ships belonging to them.
+//!
+//! Only the Blueprint List carries the numeric `shipClassId`, while the Character List,
+//! TcBook and wiki carry the Japanese class name. A ShipClass unifies whichever of
+//! those we have for its members.
+
+use derive_getters::Getters;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::ships::Ship;
+
+/// A ship belonging to a ShipClass
+#[derive(Debug, Clone, PartialEq, Eq, Getters, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipClassMember {
+    /// Base ship name
+    ship_name: String,
+
+    /// Position in the class, e.g. 2 for 不知火 in 陽炎型.
+    /// Absent for single-ship classes like 明石型, or if we have no data for it.
+    class_index: Option<u16>,
+}
+
+/// A ship class, e.g. 陽炎型
+#[derive(Debug, Clone, Getters, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipClass {
+    /// The `shipClassId` from the Blueprint List, if any member has blueprints.
+    id: Option<u16>,
+
+    /// The Japanese class name, if any member has Character, TcBook or wiki data.
+    name: Option<String>,
+
+    /// Member ships, in class index order.
+    members: Vec<ShipClassMember>,
+}
+
+impl ShipClass {
+    fn new(id: Option<u16>, name: Option<String>) -> ShipClass {
+        ShipClass {
+            id,
+            name,
+            members: vec![],
+        }
+    }
+}
+
+/// Build the ShipClasses covering all the given ships, in id order, then name order.
+/// Ships with no class information at all are not included.
+pub(crate) fn build_ship_classes<'a>(ships: impl Iterator<Item = &'a Ship>) -> Vec<ShipClass> {
+    let ships: Vec<&Ship> = ships.collect();
+
+    // Any ship with both an id and a name tells us the name for every ship with that id.
+    let id_names: HashMap<u16, &str> = ships
+        .iter()
+        .filter_map(|ship| Some((ship.class_id()?, ship.class_name()?)))
+        .collect();
+
+    let mut classes: Vec<ShipClass> = vec![];
+    for ship in ships {
+        let id = ship.class_id();
+        let name = ship
+            .class_name()
+            .or_else(|| id.and_then(|id| id_names.get(&id).copied()));
+        if id.is_none() && name.is_none() {
+            continue;
+        }
+
+        let class = match classes.iter().position(|class| match name {
+            Some(name) => class.name.as_deref() == Some(name),
+            None => class.id == id,
+        }) {
+            Some(index) => &mut classes[index],
+            None => {
+                classes.push(ShipClass::new(id, name.map(str::to_string)));
+                classes.last_mut().unwrap()
+            }
+        };
+        class.id = class.id.or(id);
+        class.members.push(ShipClassMember {
+            ship_name: ship.name().clone(),
+            class_index: ship.class_index(),
+        });
+    }
+
+    for class in classes.iter_mut() {
+        class.members.sort_by(|left, right| {
+            (
+                left.class_index.is_none(),
+                left.class_index,
+                &left.ship_name,
+            )
+                .cmp(&(
+                    right.class_index.is_none(),
+                    right.class_index,
+                    &right.ship_name,
+                ))
+        });
+    }
+    classes.sort_by(|left, right| {
+        (left.id.is_none(), left.id, &left.name).cmp(&(right.id.is_none(), right.id, &right.name))
+    });
+
+    classes
+}
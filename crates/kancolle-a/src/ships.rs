@@ -1,21 +1,29 @@
 //! The abstract concept of a ship(girl) in Kancolle Arcade
 
 use derive_getters::Getters;
+use serde::{Serialize, Serializer};
 use std::{
-    collections::{hash_map::Iter as HashMapIter, HashMap},
+    cmp::Ordering,
+    collections::{hash_map::Iter as HashMapIter, BTreeMap, HashMap},
     io::Read,
     iter::FusedIterator,
     ops::Deref,
 };
 
+use crate::blueprint_costs::BlueprintCostTable;
+use crate::cards::Card;
 use crate::importer::{
     kancolle_arcade_net::{
-        self, ApiEndpoint, BlueprintShip, BookShip, Character, ClientBuilder, KekkonKakkoKari,
-        KANMUSU,
+        self, ApiEndpoint, BlueprintShip, BookShip, BookShipCardPage, BookShipCardPageSource,
+        CardPageSources, Character, ClientBuilder, DispSortNo, ImageRef, KekkonKakkoKari,
+        UnknownField, KANMUSU,
     },
     wikiwiki_jp_kancolle_a::KansenShip,
 };
-use crate::Result;
+use crate::remodels::{Remodel, RemodelGraph};
+use crate::ship_attributes::ShipType;
+use crate::ship_classes::{self, ShipClass};
+use crate::{DataSource, Error, Result, Warning};
 
 use crate::importer::wikiwiki_jp_kancolle_a::{self, KAIZOU_KANSEN, KANSEN};
 
@@ -27,7 +35,11 @@ pub struct ShipsBuilder {
     kekkon: Option<Box<dyn Read>>,
     wiki_kansen_list: Option<Box<dyn Read>>,
     wiki_kaizou_kansen_list: Option<Box<dyn Read>>,
+    remodels: Option<Box<dyn Read>>,
+    blueprint_costs: Option<Box<dyn Read>>,
+    card_page_sources: Option<Box<dyn Read>>,
     api_client_builder: Option<ClientBuilder>,
+    lenient: bool,
 }
 
 impl Default for ShipsBuilder {
@@ -48,22 +60,30 @@ impl ShipsBuilder {
             kekkon: None,
             wiki_kansen_list: None,
             wiki_kaizou_kansen_list: None,
+            remodels: None,
+            blueprint_costs: None,
+            card_page_sources: None,
             api_client_builder: None,
+            lenient: false,
         }
     }
 
     pub async fn build(mut self) -> Result<Ships> {
         if let Some(api_client_builder) = self.api_client_builder {
-            if self.book.is_none() || self.blueprint.is_none() || self.character.is_none() {
+            // Each wanted endpoint, paired with the source it fills in.
+            let (endpoints, sources): (Vec<_>, Vec<_>) = [
+                (ApiEndpoint::TcBookInfo, &mut self.book),
+                (ApiEndpoint::BlueprintListInfo, &mut self.blueprint),
+                (ApiEndpoint::CharacterListInfo, &mut self.character),
+            ]
+            .into_iter()
+            .filter(|(_, source)| source.is_none())
+            .unzip();
+            if !endpoints.is_empty() {
                 let client = api_client_builder.build()?;
-                if self.book.is_none() {
-                    self.book = Some(client.fetch(&ApiEndpoint::TcBookInfo).await?)
-                };
-                if self.blueprint.is_none() {
-                    self.blueprint = Some(client.fetch(&ApiEndpoint::BlueprintListInfo).await?)
-                }
-                if self.character.is_none() {
-                    self.character = Some(client.fetch(&ApiEndpoint::CharacterListInfo).await?)
+                let snapshot = client.fetch_snapshot(&endpoints).await;
+                for (source, response) in sources.into_iter().zip(snapshot) {
+                    *source = Some(response?);
                 }
             }
             self.api_client_builder = None
@@ -161,6 +181,58 @@ impl ShipsBuilder {
         self
     }
 
+    /// Use the remodel graph bundled with this crate. This is the default.
+    pub fn static_remodels(mut self) -> ShipsBuilder {
+        self.remodels = None;
+        self
+    }
+
+    /// Use a remodel graph in the format of the bundled `remodels.json`, e.g. to add
+    /// ships released since this crate was.
+    pub fn remodels_from_reader<R>(mut self, reader: R) -> ShipsBuilder
+    where
+        R: Read + 'static,
+    {
+        self.remodels = Some(Box::new(reader));
+        self
+    }
+
+    /// Use only the blueprint cost table bundled with this crate. This is the default.
+    pub fn static_blueprint_costs(mut self) -> ShipsBuilder {
+        self.blueprint_costs = None;
+        self
+    }
+
+    /// Add blueprint costs in the format of the bundled `blueprint_costs.json`, e.g. to
+    /// correct costs or mark new remodels as buyable. Entries replace any bundled entries
+    /// for the same ship, ship class or ship type, as does `default` if given.
+    pub fn blueprint_costs_from_reader<R>(mut self, reader: R) -> ShipsBuilder
+    where
+        R: Read + 'static,
+    {
+        self.blueprint_costs = Some(Box::new(reader));
+        self
+    }
+
+    /// Use only the card page sources bundled with this crate. This is the default.
+    pub fn static_card_page_sources(mut self) -> ShipsBuilder {
+        self.card_page_sources = None;
+        self
+    }
+
+    /// Add card page sources in the format of the bundled `card_page_sources.json`,
+    /// e.g. for event pages released since this crate was. Entries replace any bundled
+    /// entries for the same book number.
+    /// `build` fails with `Error::InconsistentShip` if an entry's sources don't fit the
+    /// layout of its TcBook entry's pages.
+    pub fn card_page_sources_from_reader<R>(mut self, reader: R) -> ShipsBuilder
+    where
+        R: Read + 'static,
+    {
+        self.card_page_sources = Some(Box::new(reader));
+        self
+    }
+
     pub fn jsessionid(mut self, jsessionid: String) -> ShipsBuilder {
         self.api_client_builder = Some(
             self.api_client_builder
@@ -178,9 +250,29 @@ impl ShipsBuilder {
         );
         self
     }
+
+    /// Fail to build if the kancolle-arcade.net data has fields we don't know about.
+    /// This is the default.
+    pub fn strict(mut self) -> ShipsBuilder {
+        self.lenient = false;
+        self
+    }
+
+    /// Accept kancolle-arcade.net data with fields we don't know about, e.g., after
+    /// a game update. The unknown fields are reported in Ships::warnings.
+    pub fn lenient(mut self) -> ShipsBuilder {
+        self.lenient = true;
+        self
+    }
 }
 
-pub struct Ships(HashMap<String, Ship>);
+pub struct Ships {
+    ships: HashMap<String, Ship>,
+    classes: Vec<ShipClass>,
+    remodels: RemodelGraph,
+    card_page_sources: CardPageSources,
+    warnings: Vec<Warning>,
+}
 
 struct ShipModIter<'a> {
     ship_iter: HashMapIter<'a, String, Ship>,
@@ -211,20 +303,130 @@ impl<'a> Iterator for ShipModIter<'a> {
 
 impl Ships {
     pub fn shipmod_by_name(&self, shipmod_name: &str) -> Option<&ShipMod> {
-        let ship_name = ship_blueprint_name(shipmod_name);
-        self.0
+        let ship_name = blueprint_name_from(&self.remodels, shipmod_name);
+        self.ships
             .get(ship_name)
             .and_then(|ship| ship.shipmod_by_name(shipmod_name))
     }
 
     pub fn shipmod_iter(&self) -> impl Iterator<Item = &ShipMod> + '_ {
-        let mut ship_iter = self.0.iter();
+        let mut ship_iter = self.ships.iter();
         let shipmod_iter = ship_iter.next().map(|(_, ship)| ship.mods.iter());
         ShipModIter {
             ship_iter,
             shipmod_iter,
         }
     }
+
+    /// Every image for the data we have: owned cards, married cards, status icons, and
+    /// equipment, without duplicates.
+    pub fn images(&self) -> Vec<ImageRef> {
+        let mut images: Vec<ImageRef> = vec![];
+        for ship in self.ships.values() {
+            if let Some(blueprint) = ship.blueprint() {
+                images.extend(blueprint.status_image());
+            }
+        }
+        for shipmod in self.shipmod_iter() {
+            if let Some(book) = shipmod.book() {
+                images.extend(book.married_images());
+                for (_, card_page) in shipmod.card_pages() {
+                    images.extend(card_page.card_images());
+                    images.extend(card_page.status_images());
+                }
+            }
+            if let Some(character) = shipmod.character() {
+                images.extend(character.card_image());
+                images.extend(character.status_image());
+                images.extend(
+                    character
+                        .develop_equipment_list
+                        .iter()
+                        .filter_map(|equipment| equipment.equipment_image()),
+                );
+            }
+        }
+        images.sort();
+        images.dedup();
+        images
+    }
+
+    /// Every card in the TcBook, owned or not, across all ShipMods.
+    pub fn cards(&self) -> impl Iterator<Item = Card<'_>> + '_ {
+        self.shipmod_iter().flat_map(|shipmod| shipmod.cards())
+    }
+
+    /// The remodel graph these Ships were built with.
+    pub fn remodels(&self) -> &RemodelGraph {
+        &self.remodels
+    }
+
+    /// The remodels available from the given ShipMod, including conversions,
+    /// e.g. 最上改二 to 最上改二特.
+    pub fn next_forms(&self, shipmod_name: &str) -> Vec<&Remodel> {
+        self.remodels.next_forms(shipmod_name)
+    }
+
+    /// The remodels leading to the given ShipMod, including conversions.
+    pub fn previous_forms(&self, shipmod_name: &str) -> Vec<&Remodel> {
+        self.remodels.previous_forms(shipmod_name)
+    }
+
+    /// The card page sources these Ships were built with, see BookShip::source_with
+    pub fn card_page_sources(&self) -> &CardPageSources {
+        &self.card_page_sources
+    }
+
+    /// Problems found in the source data that did not prevent building the Ships.
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// All the ship classes we know of, in `shipClassId` order, then name order.
+    pub fn ship_classes(&self) -> &[ShipClass] {
+        &self.classes
+    }
+
+    /// The ship class with the given Japanese name, e.g. 陽炎型
+    pub fn ship_class(&self, class_name: &str) -> Option<&ShipClass> {
+        self.classes
+            .iter()
+            .find(|class| class.name().as_deref() == Some(class_name))
+    }
+
+    /// The Ships grouped by class, in the same order as ship_classes.
+    /// Ships we have no class information for are not included.
+    pub fn by_class(&self) -> Vec<(&ShipClass, Vec<&Ship>)> {
+        self.classes
+            .iter()
+            .map(|class| {
+                let ships = class
+                    .members()
+                    .iter()
+                    .filter_map(|member| self.ships.get(member.ship_name()))
+                    .collect();
+                (class, ships)
+            })
+            .collect()
+    }
+
+    /// Export the merged data as a JSON object of Ships keyed by base ship name.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+// Serialized as the map of ships, sorted by name so the output is stable.
+impl Serialize for Ships {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.ships
+            .iter()
+            .collect::<BTreeMap<_, _>>()
+            .serialize(serializer)
+    }
 }
 
 // Implementing Deref but not DerefMut so it can't be mutated.
@@ -232,121 +434,142 @@ impl Deref for Ships {
     type Target = HashMap<String, Ship>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.ships
     }
 }
 
-/// Determine the unmodified (blueprint) ship name for the given ship
+/// Determine the unmodified (blueprint) ship name for the given ship,
+/// per the bundled RemodelGraph.
 pub fn ship_blueprint_name(ship_name: &str) -> &str {
-    // Base case: Ships
-    let base_name = if let Some(split_name) = ship_name.split_once('改') {
-        split_name.0
-    } else {
-        ship_name
-    };
+    blueprint_name_from(RemodelGraph::bundled(), ship_name)
+}
 
-    // Ships that are renamed in modification, or have non-改 variants.
-    match base_name {
-        // Tested against data
-        "龍鳳" => "大鯨",
-        "Верный" => "響",
-        "Italia" => "Littorio",
-        "千代田甲" | "千代田航" => "千代田",
-        "千歳甲" | "千歳航" => "千歳",
-        "呂500" => "U-511",
-        // Untested against data as I don't own them.
-        // два is Russian for 二, so it should probably match /Гангут .*/...
-        "Октябрьская революция" | "Гангут два" => "Гангут",
-        "大鷹" => "春日丸",
-        _ => base_name,
-    }
+/// Guess the ship remodel level based on its name, per the bundled RemodelGraph.
+/// Fails if the name is not in the graph and has a remodel suffix we don't recognise.
+pub fn ship_remodel_level_guess(ship_name: &str) -> Result<u16> {
+    remodel_level_from(RemodelGraph::bundled(), ship_name)
 }
 
-/// Guess the ship remodel level based on its name
-pub fn ship_remodel_level_guess(ship_name: &str) -> u16 {
-    let (base_name, kai_name) = match ship_name.find('改') {
+/// Split a ship name not in the RemodelGraph at the first 改, e.g. 大鷹改三 into 大鷹
+/// and 改三, so the part before can be looked up instead.
+fn split_kai(ship_name: &str) -> (&str, &str) {
+    match ship_name.find('改') {
         None => (ship_name, ""),
         Some(index) => ship_name.split_at(index),
-    };
+    }
+}
 
-    let base_level: u16 = match base_name {
-        // Tested against data
-        "龍鳳" => 1,
-        "Верный" => 2,
-        "Italia" => 1,
-        "千代田甲" | "千歳甲" => 2,
-        "千代田航" | "千歳航" => 3,
-        "呂500" => 2,
-        // Untested against data as I don't own them.
-        "Октябрьская революция" => 1,
-        "大鷹" => 1,
-        _ => 0,
-    };
+pub(crate) fn blueprint_name_from<'a>(remodels: &'a RemodelGraph, ship_name: &'a str) -> &'a str {
+    if let Some(base_name) = remodels.base_name(ship_name) {
+        return base_name;
+    }
 
+    let (base_name, _) = split_kai(ship_name);
+    remodels.base_name(base_name).unwrap_or(base_name)
+}
+
+fn remodel_level_from(remodels: &RemodelGraph, ship_name: &str) -> Result<u16> {
+    if let Some(level) = remodels.remodel_level(ship_name) {
+        return Ok(level);
+    }
+
+    let (base_name, kai_name) = split_kai(ship_name);
+    let base_level = remodels.remodel_level(base_name).unwrap_or(0);
+
+    // A guess for forms missing from the RemodelGraph, e.g. a new 改二 branch. Where the
+    // branch is a conversion from 改二, the Character List may disagree.
     let kai_level: u16 = match kai_name {
         "" => 0,
         "改" => 1,
         "改二" => 2,
-        // TODO: 改二＿ perhaps, for some future-proofing?
         "改三" | "改二甲" | "改二丁" | "改二乙" | "改二特" | "改二丙" => 3,
-        _ => panic!("Unknown kai_level {kai_name}"),
+        _ => {
+            return Err(Error::UnknownRemodel {
+                ship_name: ship_name.to_string(),
+                suffix: kai_name.to_string(),
+            })
+        }
     };
 
-    base_level + kai_level
+    Ok(base_level + kai_level)
 }
 
-/// Report the number of blueprints and large-scale blueprints needed for each stage.
-/// `stage` is 0-indexed, i.e. it's the cost to upgrade _from_ that level.
-/// Generally based on ship type, so is not aware of which specific ships have 改二 or later mods.
-fn ship_blueprint_costs(ship_name: &str, ship_type: &str, stage: u16) -> Option<(u16, u8)> {
-    // Special ships.
-    let stage_costs = match ship_name {
-        // TODO: This is shipClassId 5 (ship class name 千歳型). The id is available in blueprints, and the name
-        // is available in Character, Book, and Wiki data. Could we rely on always having at least one of those?
-        // Notably, kekkon list does not list ship class information at all.
-        // How _reliable_ is the Wiki here? Conversely, since we're matching _blueprint_ names, name-matching
-        // is probably safest, but requires maintenance.
-        "千歳" | "千代田" => vec![(3, 0), (4, 0), (5, 0), (6, 0), (8, 2)],
-        // TODO: Wiki lists base as 春日丸級 and mods as 大鷹型; need to find shipClassId too
-        // Confirm that this forms an actual ship series: 春日丸, 大鷹, 大鷹改.
-        "春日丸" => vec![(3, 0), (5, 0)],
-        _ => match ship_type {
-            "駆逐艦" | "軽巡洋艦" | "潜水艦" => vec![(3, 0), (6, 1), (6, 3)],
-            "戦艦" | "軽空母" | "正規空母" | "重巡洋艦" => {
-                vec![(3, 0), (8, 2), (8, 4)]
-            }
-            _ => vec![(3, 0)],
-        },
-    };
-    stage_costs
-        .get(stage as usize)
-        .map(|costs| (costs.0 as u16, costs.1 as u8))
+/// Unpack the result of a lenient read, keeping any unknown fields and values as warnings.
+fn lenient_read<T>(
+    result: serde_json::Result<(T, Vec<UnknownField>)>,
+    data_source: DataSource,
+    warnings: &mut Vec<Warning>,
+) -> Result<T> {
+    let (data, unknown_fields) = result?;
+    warnings.extend(
+        unknown_fields
+            .into_iter()
+            .map(|field| Warning::unknown(data_source, field)),
+    );
+    Ok(data)
 }
 
 impl Ships {
     /// Import a list of ships from the given datasource
     fn new(builder: ShipsBuilder) -> Result<Self> {
-        let book = match builder.book {
+        let mut warnings = vec![];
+        let lenient = builder.lenient;
+
+        let remodels = match builder.remodels {
+            None => RemodelGraph::bundled().clone(),
+            Some(reader) => RemodelGraph::from_reader(reader)?,
+        };
+
+        let mut blueprint_costs = BlueprintCostTable::bundled().clone();
+        if let Some(reader) = builder.blueprint_costs {
+            blueprint_costs.overlay(BlueprintCostTable::from_reader(reader)?);
+        }
+
+        let mut card_page_sources = CardPageSources::bundled().clone();
+        if let Some(reader) = builder.card_page_sources {
+            card_page_sources.overlay(CardPageSources::from_reader(reader)?);
+        }
+
+        let mut book = match builder.book {
             None => None,
-            Some(reader) => {
-                let mut book = kancolle_arcade_net::read_tclist(reader)?;
-                book.retain(|ship| ship.acquire_num > 0);
-                Some(book)
-            }
+            Some(reader) if lenient => Some(lenient_read(
+                kancolle_arcade_net::read_tclist_lenient(reader),
+                DataSource::Book,
+                &mut warnings,
+            )?),
+            Some(reader) => Some(kancolle_arcade_net::read_tclist(reader)?),
         };
+        if let Some(book) = book.as_mut() {
+            book.retain(|ship| ship.acquire_num > 0);
+        }
 
         let bplist = match builder.blueprint {
             None => None,
+            Some(reader) if lenient => Some(lenient_read(
+                kancolle_arcade_net::read_blueprintlist_lenient(reader),
+                DataSource::Blueprint,
+                &mut warnings,
+            )?),
             Some(reader) => Some(kancolle_arcade_net::read_blueprintlist(reader)?),
         };
 
         let characters = match builder.character {
             None => None,
+            Some(reader) if lenient => Some(lenient_read(
+                kancolle_arcade_net::read_characterlist_lenient(reader),
+                DataSource::Character,
+                &mut warnings,
+            )?),
             Some(reader) => Some(kancolle_arcade_net::read_characterlist(reader)?),
         };
 
         let kekkonlist = match builder.kekkon {
             None => None,
+            Some(reader) if lenient => Some(lenient_read(
+                kancolle_arcade_net::read_kekkonkakkokarilist_lenient(reader),
+                DataSource::Kekkon,
+                &mut warnings,
+            )?),
             Some(reader) => Some(kancolle_arcade_net::read_kekkonkakkokarilist(reader)?),
         };
 
@@ -364,8 +587,6 @@ impl Ships {
         // HACK: 500 is more than the wiki list (445), so it'll do for now. This is a temporary array anyway.
         let mut shipmods: HashMap<String, ShipMod> = HashMap::with_capacity(500);
 
-        // TODO: Don't panic in case of duplicates, return an error.
-
         // Helper function for use with or_insert_with_key.
         let ship_inserter = |ship_name: &String| Ship::new(ship_name.clone());
 
@@ -389,7 +610,10 @@ impl Ships {
                 match &mut ship.wiki_list_entry {
                     None => ship.wiki_list_entry = Some(wiki_row),
                     Some(_) => {
-                        panic!("Duplicate wiki list entry for {}", wiki_row.ship_name)
+                        return Err(Error::DuplicateEntry {
+                            ship_name: wiki_row.ship_name,
+                            data_source: DataSource::Wiki,
+                        })
                     }
                 }
             }
@@ -404,7 +628,10 @@ impl Ships {
                 match &mut ship.kekkon {
                     None => ship.kekkon = Some(kekkon),
                     Some(_) => {
-                        panic!("Duplicate kekkon entry for {}", kekkon.name)
+                        return Err(Error::DuplicateEntry {
+                            ship_name: kekkon.name,
+                            data_source: DataSource::Kekkon,
+                        })
                     }
                 };
             }
@@ -418,7 +645,10 @@ impl Ships {
                 match &mut ship.character {
                     None => ship.character = Some(character),
                     Some(_) => {
-                        panic!("Duplicate character entry for {}", character.ship_name)
+                        return Err(Error::DuplicateEntry {
+                            ship_name: character.ship_name,
+                            data_source: DataSource::Character,
+                        })
                     }
                 };
             }
@@ -426,16 +656,25 @@ impl Ships {
 
         if let Some(book) = book {
             for book_ship in book.into_iter() {
-                let (book_nonkai, book_kai) = book_ship.into_kai_split();
+                let page_sources = book_ship.page_sources_with(&card_page_sources);
+                let (book_nonkai, book_kai) = book_ship.into_kai_split_with(&card_page_sources)?;
+                let split = book_kai.is_some();
 
                 if let Some(book_kai) = book_kai {
                     let ship = shipmods
                         .entry(book_kai.ship_name.clone())
                         .or_insert_with_key(shipmod_inserter);
                     match &mut ship.book {
-                        None => ship.book = Some(book_kai),
+                        None => {
+                            ship.card_page_sources =
+                                book_kai.split_page_sources(&page_sources, true);
+                            ship.book = Some(book_kai)
+                        }
                         Some(_) => {
-                            panic!("Duplicate book entry for {}", book_kai.ship_name)
+                            return Err(Error::DuplicateEntry {
+                                ship_name: book_kai.ship_name,
+                                data_source: DataSource::Book,
+                            })
                         }
                     };
                 }
@@ -444,9 +683,18 @@ impl Ships {
                     .entry(book_nonkai.ship_name.clone())
                     .or_insert_with_key(shipmod_inserter);
                 match &mut ship.book {
-                    None => ship.book = Some(book_nonkai),
+                    None => {
+                        ship.card_page_sources = match split {
+                            true => book_nonkai.split_page_sources(&page_sources, false),
+                            false => page_sources,
+                        };
+                        ship.book = Some(book_nonkai)
+                    }
                     Some(_) => {
-                        panic!("Duplicate book entry for {}", book_nonkai.ship_name)
+                        return Err(Error::DuplicateEntry {
+                            ship_name: book_nonkai.ship_name,
+                            data_source: DataSource::Book,
+                        })
                     }
                 };
             }
@@ -457,11 +705,11 @@ impl Ships {
                 shipmods
                     .entry(bp_ship.ship_name.clone())
                     .or_insert_with_key(shipmod_inserter);
-                // TODO: Fail if we have a duplicate blueprint.
             }
         }
 
-        for ship in shipmods.values() {
+        for ship in shipmods.values_mut() {
+            ship.resolve_remodel_level(&remodels)?;
             ship.validate()?
         }
 
@@ -479,7 +727,10 @@ impl Ships {
                 match &mut ship.blueprint {
                     None => ship.blueprint = Some(bp_ship),
                     Some(_) => {
-                        panic!("Duplicate blueprint entry for {}", bp_ship.ship_name)
+                        return Err(Error::DuplicateEntry {
+                            ship_name: bp_ship.ship_name,
+                            data_source: DataSource::Blueprint,
+                        })
                     }
                 }
             }
@@ -487,7 +738,7 @@ impl Ships {
 
         // Now distribute the ShipMods.
         for (modname, shipmod) in shipmods.into_iter() {
-            let basename = ship_blueprint_name(&modname);
+            let basename = blueprint_name_from(&remodels, &modname);
             let ship = ships
                 .entry(basename.to_string())
                 .or_insert_with_key(ship_inserter);
@@ -498,19 +749,31 @@ impl Ships {
 
         for (_, ship) in ships.iter_mut() {
             ship.sort_ship_mods();
-            ship.validate()?;
+            ship.validate(&remodels)?;
+            ship.resolve_blueprint_costs(&blueprint_costs);
+            ship.resolve_disp_sort();
         }
 
         ships.shrink_to_fit();
 
-        Ok(Ships(ships))
+        let classes = ship_classes::build_ship_classes(ships.values());
+
+        Ok(Ships {
+            ships,
+            classes,
+            remodels,
+            card_page_sources,
+            warnings,
+        })
     }
 }
 
 /// A Kancolle Arcade shipgirl, covering all modification stages.
 /// Only the name is reliably unique.
 /// Many other fields may either surprisingly overlap, or are optional.
-#[derive(Debug, Getters)]
+/// Ordered as the game orders its base ship, see ShipMod::disp_sort.
+#[derive(Debug, Getters, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Ship {
     /// Base ship name
     name: String,
@@ -522,6 +785,11 @@ pub struct Ship {
     /// If there are gaps in our data (which should be unlikely) they will not
     /// be visible here.
     mods: Vec<ShipMod>,
+
+    /// Determined once all the data is in, see resolve_blueprint_costs
+    #[getter(skip)]
+    #[serde(skip)]
+    blueprint_costs: Vec<Option<(u16, u8)>>,
 }
 
 impl Ship {
@@ -546,42 +814,182 @@ impl Ship {
         if self.mods.last().unwrap().remodel_level() < remodel_level {
             return None;
         }
+        self.blueprint_costs
+            .get(remodel_level as usize - 1)
+            .copied()
+            .flatten()
+    }
+
+    /// The ship type of the base ship, which determines blueprint costs.
+    fn base_ship_type(&self) -> Option<ShipType> {
+        if self.mods.is_empty() {
+            return None;
+        }
         // Having a blueprint is the best data, otherwise check character data, tc_book data, and finally wiki data.
-        // Generally the base_name should equal our ship_name, but not building that assumption in here.
-        let (base_name, base_ship_type) = {
-            if self.blueprint().is_some() {
-                (
-                    &self.blueprint().as_ref().unwrap().ship_name,
-                    &self.blueprint().as_ref().unwrap().ship_type,
-                )
-            } else if self.mods()[0].character().is_some()
-                && self.mods()[0].character().as_ref().unwrap().remodel_lv == 0
-            {
-                (
-                    &self.mods()[0].character().as_ref().unwrap().ship_name,
-                    &self.mods()[0].character().as_ref().unwrap().ship_type,
-                )
-            } else if self.mods()[0].book().is_some()
-                && ship_remodel_level_guess(&self.mods()[0].book().as_ref().unwrap().ship_name) == 0
-            {
-                (
-                    &self.mods()[0].book().as_ref().unwrap().ship_name,
-                    &self.mods()[0].book().as_ref().unwrap().ship_type,
-                )
-            } else if self.mods()[0].wiki_list_entry().is_some()
-                && ship_remodel_level_guess(
-                    &self.mods()[0].wiki_list_entry().as_ref().unwrap().ship_name,
-                ) == 0
-            {
-                (
-                    &self.mods()[0].wiki_list_entry().as_ref().unwrap().ship_name,
-                    &self.mods()[0].wiki_list_entry().as_ref().unwrap().ship_type,
-                )
-            } else {
-                return None;
-            }
+        if self.blueprint().is_some() {
+            Some(self.blueprint().as_ref().unwrap().ship_type.clone())
+        } else if self.mods()[0].character().is_some()
+            && self.mods()[0].character().as_ref().unwrap().remodel_lv == 0
+        {
+            Some(
+                self.mods()[0]
+                    .character()
+                    .as_ref()
+                    .unwrap()
+                    .ship_type
+                    .clone(),
+            )
+        } else if self.mods()[0]
+            .book()
+            .as_ref()
+            .is_some_and(|book| book.ship_type.is_some())
+            && self.mods()[0].remodel_level() == 0
+        {
+            self.mods()[0].book().as_ref().unwrap().ship_type.clone()
+        } else if self.mods()[0].wiki_list_entry().is_some() && self.mods()[0].remodel_level() == 0
+        {
+            Some(
+                self.mods()[0]
+                    .wiki_list_entry()
+                    .as_ref()
+                    .unwrap()
+                    .ship_type
+                    .clone(),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Determine the blueprint cost of each stage from the data we have been given.
+    /// Stages where every known ShipMod is not remodelable yet have no cost.
+    fn resolve_blueprint_costs(&mut self, blueprint_costs: &BlueprintCostTable) {
+        let Some(base_ship_type) = self.base_ship_type() else {
+            return;
         };
-        ship_blueprint_costs(base_name, base_ship_type, remodel_level - 1)
+        let stage_costs = blueprint_costs.stage_costs(
+            &self.name,
+            self.class_id(),
+            self.class_name(),
+            base_ship_type,
+        );
+        self.blueprint_costs = stage_costs
+            .iter()
+            .enumerate()
+            .map(|(stage, &cost)| {
+                let mut stage_mods = self
+                    .mods
+                    .iter()
+                    .filter(|shipmod| shipmod.remodel_level() as usize == stage + 1)
+                    .peekable();
+                let remodelable = stage_mods.peek().is_none()
+                    || stage_mods
+                        .any(|shipmod| blueprint_costs.remodelable(&self.name, shipmod.name()));
+                remodelable.then_some(cost)
+            })
+            .collect();
+    }
+
+    /// The `shipClassId` of this ship, only known if we have Blueprint data for it.
+    pub fn class_id(&self) -> Option<u16> {
+        self.blueprint
+            .as_ref()
+            .map(|blueprint| blueprint.ship_class_id)
+    }
+
+    /// The in-game sort order of our base ship, see ShipMod::disp_sort
+    pub fn disp_sort(&self) -> Option<DispSortNo> {
+        self.mods.first().map(|shipmod| shipmod.disp_sort)
+    }
+
+    /// Determine the sort order of each ShipMod from the data we have been given.
+    /// Only the Character List gives us this directly, but it is the same across all
+    /// ShipMods apart from the remodel level, so we can fill in the rest.
+    /// Ships with no Character data sort by book number, after any class-mates we have.
+    fn resolve_disp_sort(&mut self) {
+        let base_disp_sort = self
+            .mods
+            .iter()
+            .find_map(|shipmod| shipmod.character().as_ref())
+            .map(|character| character.disp_sort())
+            .unwrap_or_else(|| DispSortNo {
+                base_book_no: self
+                    .mods
+                    .first()
+                    .filter(|shipmod| shipmod.remodel_level() == 0)
+                    .and_then(|shipmod| {
+                        shipmod
+                            .book()
+                            .as_ref()
+                            .map(|book| book.book_no)
+                            .or(shipmod.wiki_list_entry().as_ref().map(|wiki| wiki.book_no))
+                    })
+                    .unwrap_or(u16::MAX),
+                ship_class_id: self.class_id().unwrap_or(0),
+                ship_class_index: self.class_index().unwrap_or(0),
+                remodel_lv: 0,
+            });
+        for shipmod in self.mods.iter_mut() {
+            shipmod.disp_sort = match shipmod.character() {
+                Some(character) => character.disp_sort(),
+                None => DispSortNo {
+                    remodel_lv: shipmod.remodel_level,
+                    ..base_disp_sort
+                },
+            };
+        }
+    }
+
+    /// The Japanese class name of this ship, e.g. 陽炎型.
+    /// Taken from the first known mod, as later mods may be a different class, e.g. 大鷹型
+    /// for 春日丸級.
+    pub fn class_name(&self) -> Option<&str> {
+        let shipmod = self.mods.first()?;
+        shipmod
+            .character()
+            .as_ref()
+            .and_then(|character| character.ship_class.as_deref())
+            .or_else(|| {
+                shipmod
+                    .book()
+                    .as_ref()
+                    .and_then(|book| book.ship_class.as_deref())
+            })
+            .or_else(|| {
+                shipmod
+                    .wiki_list_entry()
+                    .as_ref()
+                    .map(|wiki| wiki.ship_class.as_str())
+            })
+            .filter(|class_name| !class_name.is_empty())
+    }
+
+    /// This ship's position in its class, e.g. 2 for 不知火 in 陽炎型.
+    pub fn class_index(&self) -> Option<u16> {
+        if let Some(blueprint) = self.blueprint.as_ref() {
+            return Some(blueprint.ship_class_index);
+        }
+        let shipmod = self.mods.first()?;
+        shipmod
+            .character()
+            .as_ref()
+            .and_then(|character| character.ship_class_index)
+            .or_else(|| {
+                shipmod
+                    .book()
+                    .as_ref()
+                    .and_then(|book| book.ship_class_index)
+                    .and_then(|index| u16::try_from(index).ok())
+            })
+            .or_else(|| {
+                // e.g. 2番艦, or - for single-ship classes
+                shipmod
+                    .wiki_list_entry()
+                    .as_ref()
+                    .and_then(|wiki| wiki.ship_class_index.strip_suffix("番艦"))
+                    .and_then(|index| index.parse().ok())
+            })
+            .filter(|&index| index > 0)
     }
 
     fn new(name: String) -> Ship {
@@ -590,32 +998,58 @@ impl Ship {
             blueprint: None,
             // Big enough for 千代田 and 千歳航, we'll shrink-to-fit when sorting.
             mods: Vec::with_capacity(6),
+            blueprint_costs: vec![],
         }
     }
 
     /// Validate that the ShipMods match and are sorted correctly.
-    fn validate(&self) -> Result<()> {
+    fn validate(&self, remodels: &RemodelGraph) -> Result<()> {
+        let inconsistent = |reason: String| {
+            Err(Error::InconsistentShip {
+                ship_name: self.name.clone(),
+                reason,
+            })
+        };
+
         if let Some(ref blueprint) = self.blueprint() {
-            assert_eq!(self.name(), &blueprint.ship_name);
+            if self.name() != &blueprint.ship_name {
+                return Err(Error::InconsistentData {
+                    ship_name: self.name.clone(),
+                    data_source: DataSource::Blueprint,
+                    reason: format!("blueprint is for {}", blueprint.ship_name),
+                });
+            }
         }
 
-        let mut last_remodel_level = match self.mods().first() {
-            Some(shipmod) => shipmod.remodel_level(),
-            None => 0,
-        };
-
-        let mut first = true;
+        let mut last_shipmod: Option<&ShipMod> = None;
 
         for shipmod in self.mods().iter() {
-            assert_eq!(self.name(), ship_blueprint_name(shipmod.name()));
-            if !first {
-                assert!(shipmod.remodel_level() > last_remodel_level);
-                last_remodel_level = shipmod.remodel_level();
+            if self.name() != blueprint_name_from(remodels, shipmod.name()) {
+                return inconsistent(format!("{} is not a mod of this ship", shipmod.name()));
+            }
+
+            if let Some(last_shipmod) = last_shipmod {
+                // Branches, e.g. 龍鳳改二 and 龍鳳改二戊, may share a remodel level.
+                let branches = remodels.are_branches(shipmod.name(), last_shipmod.name());
+                if shipmod.remodel_level() < last_shipmod.remodel_level()
+                    || (shipmod.remodel_level() == last_shipmod.remodel_level() && !branches)
+                {
+                    return inconsistent(format!(
+                        "{} remodel level {} does not follow {} remodel level {}",
+                        shipmod.name(),
+                        shipmod.remodel_level(),
+                        last_shipmod.name(),
+                        last_shipmod.remodel_level()
+                    ));
+                }
             }
-            first = false;
+            last_shipmod = Some(shipmod);
 
-            if self.name() == shipmod.name() {
-                assert_eq!(shipmod.remodel_level(), 0);
+            if self.name() == shipmod.name() && shipmod.remodel_level() != 0 {
+                return inconsistent(format!(
+                    "base ship has remodel level {}",
+                    shipmod.remodel_level()
+                ));
             }
         }
 
@@ -629,12 +1063,38 @@ impl Ship {
     }
 }
 
+impl PartialEq for Ship {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ship {}
+
+impl PartialOrd for Ship {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ship {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Ships with no mods are of no interest, so go last.
+        let key = |ship: &Ship| (ship.disp_sort().is_none(), ship.disp_sort());
+        key(self)
+            .cmp(&key(other))
+            .then_with(|| self.name.cmp(&other.name))
+    }
+}
+
 /// A Kancolle Arcade shipgirl at a particular modification stage
 /// Only the name is reliably unique.
 /// Many other fields may either surprisingly overlap, or are optional.
+/// Ordered as the game orders them, see disp_sort.
 /// TODO: Replace derive_getters with hand-written getters that return Option<&T>
 /// instead of &Option<T> so I can remove all the as_ref calls in callers.
-#[derive(Debug, Getters)]
+#[derive(Debug, Getters, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ShipMod {
     /// Full ship name
     name: String,
@@ -655,17 +1115,63 @@ pub struct ShipMod {
     /// The kansen ship list entry for this ship from
     /// https://wikiwiki.jp/kancolle-a/
     wiki_list_entry: Option<KansenShip>,
+
+    /// Determined once all the data is in, see resolve_remodel_level
+    #[getter(skip)]
+    remodel_level: u16,
+
+    /// Determined once all the data is in, see Ship::resolve_disp_sort
+    #[getter(skip)]
+    #[serde(skip)]
+    disp_sort: DispSortNo,
+
+    /// The source of each page of book, see card_pages
+    #[getter(skip)]
+    #[serde(skip)]
+    card_page_sources: Vec<BookShipCardPageSource>,
 }
 
 impl ShipMod {
     pub fn remodel_level(&self) -> u16 {
-        // Trivial if we have a character
-        if let Some(character) = self.character() {
-            return character.remodel_lv;
+        self.remodel_level
+    }
+
+    /// Our position in the in-game ordering, as the Character List `dispSortNo`.
+    /// For ShipMods without Character data, this is filled in from the rest of the Ship.
+    pub fn disp_sort(&self) -> DispSortNo {
+        self.disp_sort
+    }
+
+    /// Reports the event-source for the given page ("priority") of our TcBook entry,
+    /// accounting for any split of a shared entry between us and our 改 form.
+    /// None if we have no TcBook entry, or the page has no cards for this ShipMod.
+    pub fn card_page_source(&self, priority: u16) -> Option<&BookShipCardPageSource> {
+        let card_page = self.book.as_ref()?.card_list.get(priority as usize)?;
+        match card_page.variation_num_in_page {
+            0 => None,
+            _ => self.card_page_sources.get(priority as usize),
         }
+    }
 
-        // Otherwise, we need to guess.
-        ship_remodel_level_guess(self.name())
+    /// Our TcBook pages with their event-sources, skipping any page which has no cards
+    /// for this ShipMod after splitting a shared entry, e.g. 雪風's Swimsuit page.
+    pub fn card_pages(
+        &self,
+    ) -> impl Iterator<Item = (BookShipCardPageSource, &BookShipCardPage)> + '_ {
+        self.book
+            .iter()
+            .flat_map(|book| book.card_list.iter())
+            .zip(self.card_page_sources.iter())
+            .filter(|(card_page, _)| card_page.variation_num_in_page > 0)
+            .map(|(card_page, source)| (source.clone(), card_page))
+    }
+
+    /// Every card on our TcBook pages, owned or not, see card_pages.
+    pub fn cards(&self) -> impl Iterator<Item = Card<'_>> + '_ {
+        let book_no = self.book.as_ref().map_or(0, |book| book.book_no);
+        self.card_pages().flat_map(move |(source, card_page)| {
+            Card::from_page(&self.name, book_no, source, card_page)
+        })
     }
 
     // TODO: More APIs, particulary when there's multiple sources of truth, and some are more trustworthy
@@ -678,33 +1184,68 @@ impl ShipMod {
             character: None,
             kekkon: None,
             wiki_list_entry: None,
+            remodel_level: 0,
+            disp_sort: DispSortNo::default(),
+            card_page_sources: vec![],
         }
     }
 
+    /// Determine our remodel level from the data we have been given.
+    fn resolve_remodel_level(&mut self, remodels: &RemodelGraph) -> Result<()> {
+        self.remodel_level = match self.character() {
+            // Trivial if we have a character
+            Some(character) => character.remodel_lv,
+            // Otherwise, we need to guess.
+            None => remodel_level_from(remodels, self.name())?,
+        };
+        Ok(())
+    }
+
     /// Validate the various data elements agree when present
     fn validate(&self) -> Result<()> {
-        // TODO: We should error in the failure cases, not panic.
+        let inconsistent = |data_source: DataSource, reason: String| {
+            Err(Error::InconsistentData {
+                ship_name: self.name.clone(),
+                data_source,
+                reason,
+            })
+        };
 
         if let Some(book) = self.book.as_ref() {
-            assert_ne!(
-                book.variation_num, 0,
-                "Ship {} created from \"Unknown\" book entry {}",
-                self.name, book.book_no
-            );
+            if book.variation_num == 0 {
+                return inconsistent(
+                    DataSource::Book,
+                    format!("created from \"Unknown\" book entry {}", book.book_no),
+                );
+            }
 
-            let normal_page = &book.card_list[0];
-            assert_eq!(
-                normal_page.variation_num_in_page, 3,
-                "Unexpected variation count {} on normal page of {}",
-                normal_page.variation_num_in_page, book.book_no
-            );
+            let normal_variations = book
+                .card_list
+                .first()
+                .map(|normal_page| normal_page.variation_num_in_page);
+            if normal_variations != Some(3) {
+                return inconsistent(
+                    DataSource::Book,
+                    format!(
+                        "unexpected variation count {:?} on normal page of {}",
+                        normal_variations, book.book_no
+                    ),
+                );
+            }
         }
 
         if let Some(kekkon) = self.kekkon.as_ref() {
-            assert_eq!(self.name, kekkon.name);
+            if self.name != kekkon.name {
+                return inconsistent(DataSource::Kekkon, format!("entry is for {}", kekkon.name));
+            }
         }
         if let Some(character) = self.character.as_ref() {
-            assert_eq!(self.name, character.ship_name);
+            if self.name != character.ship_name {
+                return inconsistent(
+                    DataSource::Character,
+                    format!("entry is for {}", character.ship_name),
+                );
+            }
         }
 
         // TODO: Check consistency across the passed-in items where they overlap, e.g., names, types.
@@ -713,5 +1254,27 @@ impl ShipMod {
     }
 }
 
+impl PartialEq for ShipMod {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ShipMod {}
+
+impl PartialOrd for ShipMod {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ShipMod {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.disp_sort
+            .cmp(&other.disp_sort)
+            .then_with(|| self.name.cmp(&other.name))
+    }
+}
+
 #[cfg(test)]
 mod tests;
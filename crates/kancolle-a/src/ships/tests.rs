@@ -57,6 +57,9 @@ fn test_ship_blueprint_name() {
         ("春日丸", "大鷹"),
         ("春日丸", "大鷹改"),
         ("春日丸", "大鷹改二"),
+        // Unknown forms of renamed ships
+        ("春日丸", "大鷹改三"),
+        ("Гангут", "Гангут два改"),
     ];
 
     for (expected, input) in tests {
@@ -65,12 +68,33 @@ fn test_ship_blueprint_name() {
 }
 
 #[test]
-fn test_ship_blueprint_costs() {
-    assert_eq!(
-        (3, 0),
-        ship_blueprint_costs("Anyname", "Anytype", 0).unwrap()
-    );
-    assert_eq!(None, ship_blueprint_costs("Anyname", "Anytype", 6));
+fn test_ship_remodel_level_guess() {
+    let tests = vec![
+        ("鳥海", 0),
+        ("鳥海改", 1),
+        ("鳥海改二", 2),
+        ("時雨改三", 3),
+        ("朝潮改二丁", 3),
+        ("龍鳳", 1),
+        ("千代田航改二", 5),
+        // Branches
+        ("龍鳳改二戊", 3),
+        ("龍鳳改二", 3),
+        ("最上改二特", 3),
+        // Unknown forms of renamed ships
+        ("Гангут два改", 3),
+        // Forms missing from the RemodelGraph
+        ("鳥海改三", 3),
+        ("鳥海改二丙", 3),
+    ];
+
+    for (input, expected) in tests {
+        assert_eq!(expected, ship_remodel_level_guess(input).unwrap());
+    }
 
-    // TODO: What's a good way to test this further that isn't just repeating the function?
+    // Future remodels shouldn't panic.
+    assert!(matches!(
+        ship_remodel_level_guess("時雨改三乙"),
+        Err(Error::UnknownRemodel { ship_name, suffix }) if ship_name == "時雨改三乙" && suffix == "改三乙"
+    ));
 }
@@ -0,0 +1,16 @@
+//! Module for Japan time, which the game's dates and deadlines are given in
+
+use chrono::{FixedOffset, NaiveDate, Utc};
+
+/// Japan time, which has no daylight saving.
+pub fn japan() -> FixedOffset {
+    FixedOffset::east_opt(9 * 60 * 60).unwrap()
+}
+
+/// Today's date in Japan, e.g. for comparing against kekkon start dates.
+pub fn today_in_japan() -> NaiveDate {
+    Utc::now().with_timezone(&japan()).date_naive()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,21 @@
+use super::*;
+
+use chrono::TimeZone;
+
+#[test]
+fn test_japan() {
+    assert_eq!(japan().local_minus_utc(), 9 * 60 * 60);
+    let midnight = japan().with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap();
+    assert_eq!(
+        midnight.to_utc(),
+        Utc.with_ymd_and_hms(2025, 5, 31, 15, 0, 0).unwrap()
+    );
+}
+
+#[test]
+fn test_today_in_japan() {
+    let today = today_in_japan();
+    let utc_today = Utc::now().date_naive();
+    // Japan is never behind UTC, and never more than a day ahead.
+    assert!(today == utc_today || today == utc_today.succ_opt().unwrap());
+}
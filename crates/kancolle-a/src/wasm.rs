@@ -0,0 +1,277 @@
+//! JavaScript facade for wasm32 builds, via wasm-bindgen
+//!
+//! The Rust builders consume themselves, so the JavaScript classes do too: each builder
+//! method returns a new object, and the object it was called on is no longer usable.
+//! Data is passed in and out as JSON strings, i.e. exactly what the API endpoints return.
+//!
+//! The crate is only built as an rlib, so build the JavaScript package with e.g.
+//! `cargo rustc -p kancolle-a --lib --release --target wasm32-unknown-unknown --crate-type cdylib`
+//! and then run `wasm-bindgen` on the result.
+
+use std::io::{Cursor, Read};
+
+use wasm_bindgen::prelude::*;
+
+use crate::importer::kancolle_arcade_net::{
+    self, ApiEndpoint, Client as ApiClient, ClientBuilder as ApiClientBuilder,
+};
+use crate::importer::wikiwiki_jp_kancolle_a;
+use crate::ships::{Ships as RustShips, ShipsBuilder as RustShipsBuilder};
+
+fn reader(json: String) -> Cursor<Vec<u8>> {
+    Cursor::new(json.into_bytes())
+}
+
+#[wasm_bindgen]
+pub struct ShipsBuilder(RustShipsBuilder);
+
+#[wasm_bindgen]
+impl ShipsBuilder {
+    /// A builder with no data sources at all.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> ShipsBuilder {
+        ShipsBuilder(RustShipsBuilder::new())
+    }
+
+    /// A builder with the bundled kekkon and wiki data sources.
+    #[wasm_bindgen(js_name = withDefaults)]
+    pub fn with_defaults() -> ShipsBuilder {
+        ShipsBuilder(RustShipsBuilder::default())
+    }
+
+    pub async fn build(self) -> Result<Ships, JsError> {
+        Ok(Ships(self.0.build().await?))
+    }
+
+    #[wasm_bindgen(js_name = bookFromJson)]
+    pub fn book_from_json(self, json: String) -> ShipsBuilder {
+        ShipsBuilder(self.0.book_from_reader(reader(json)))
+    }
+
+    #[wasm_bindgen(js_name = blueprintFromJson)]
+    pub fn blueprint_from_json(self, json: String) -> ShipsBuilder {
+        ShipsBuilder(self.0.blueprint_from_reader(reader(json)))
+    }
+
+    #[wasm_bindgen(js_name = characterFromJson)]
+    pub fn character_from_json(self, json: String) -> ShipsBuilder {
+        ShipsBuilder(self.0.character_from_reader(reader(json)))
+    }
+
+    #[wasm_bindgen(js_name = kekkonFromJson)]
+    pub fn kekkon_from_json(self, json: String) -> ShipsBuilder {
+        ShipsBuilder(self.0.kekkon_from_reader(reader(json)))
+    }
+
+    /// A remodel graph to use instead of the bundled one
+    #[wasm_bindgen(js_name = remodelsFromJson)]
+    pub fn remodels_from_json(self, json: String) -> ShipsBuilder {
+        ShipsBuilder(self.0.remodels_from_reader(reader(json)))
+    }
+
+    /// Blueprint costs to add to the bundled ones
+    #[wasm_bindgen(js_name = blueprintCostsFromJson)]
+    pub fn blueprint_costs_from_json(self, json: String) -> ShipsBuilder {
+        ShipsBuilder(self.0.blueprint_costs_from_reader(reader(json)))
+    }
+
+    /// Card page sources to add to the bundled ones
+    #[wasm_bindgen(js_name = cardPageSourcesFromJson)]
+    pub fn card_page_sources_from_json(self, json: String) -> ShipsBuilder {
+        ShipsBuilder(self.0.card_page_sources_from_reader(reader(json)))
+    }
+
+    pub fn jsessionid(self, jsessionid: String) -> ShipsBuilder {
+        ShipsBuilder(self.0.jsessionid(jsessionid))
+    }
+
+    pub fn userpass(self, username: String, password: String) -> ShipsBuilder {
+        ShipsBuilder(self.0.userpass(username, password))
+    }
+
+    /// Accept data with unknown fields, see `Ships.warnings`
+    pub fn lenient(self) -> ShipsBuilder {
+        ShipsBuilder(self.0.lenient())
+    }
+}
+
+impl Default for ShipsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+pub struct Ships(RustShips);
+
+#[wasm_bindgen]
+impl Ships {
+    /// The number of (base) ships known.
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.0.len()
+    }
+
+    /// The base ship names, in no particular order.
+    #[wasm_bindgen(js_name = shipNames)]
+    pub fn ship_names(&self) -> Vec<String> {
+        self.0.keys().cloned().collect()
+    }
+
+    /// The ShipMod names of the given base ship, in remodel order.
+    #[wasm_bindgen(js_name = shipmodNames)]
+    pub fn shipmod_names(&self, ship_name: &str) -> Option<Vec<String>> {
+        self.0.get(ship_name).map(|ship| {
+            ship.mods()
+                .iter()
+                .map(|shipmod| shipmod.name().clone())
+                .collect()
+        })
+    }
+
+    /// The number of blueprints held for the given base ship.
+    #[wasm_bindgen(js_name = blueprintTotal)]
+    pub fn blueprint_total(&self, ship_name: &str) -> Option<u16> {
+        self.0
+            .get(ship_name)
+            .and_then(|ship| ship.blueprint().as_ref())
+            .map(|blueprint| blueprint.blueprint_total_num)
+    }
+
+    /// The number of blueprints needed to build the given remodel_level of a base ship.
+    #[wasm_bindgen(js_name = shipmodBlueprintCost)]
+    pub fn shipmod_blueprint_cost(&self, ship_name: &str, remodel_level: u16) -> Option<u16> {
+        self.0
+            .get(ship_name)
+            .and_then(|ship| ship.shipmod_blueprint_cost(remodel_level))
+            .map(|(blueprints, _)| blueprints)
+    }
+
+    /// The remodel level of the given ShipMod.
+    #[wasm_bindgen(js_name = remodelLevel)]
+    pub fn remodel_level(&self, shipmod_name: &str) -> Option<u16> {
+        self.0
+            .shipmod_by_name(shipmod_name)
+            .map(|shipmod| shipmod.remodel_level())
+    }
+
+    /// The names of the forms the given ShipMod can be remodelled or converted into.
+    #[wasm_bindgen(js_name = nextForms)]
+    pub fn next_forms(&self, shipmod_name: &str) -> Vec<String> {
+        self.0
+            .next_forms(shipmod_name)
+            .into_iter()
+            .map(|remodel| remodel.to().clone())
+            .collect()
+    }
+
+    /// The merged data as JSON, see `Ships::to_json` in the Rust API.
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> Result<String, JsError> {
+        Ok(self.0.to_json()?)
+    }
+
+    /// Problems found in the source data, as human-readable text.
+    pub fn warnings(&self) -> Vec<String> {
+        self.0
+            .warnings()
+            .iter()
+            .map(|warning| warning.to_string())
+            .collect()
+    }
+}
+
+/// HTTPS client for https://kancolle-arcade.net/ac/api/
+///
+/// In the browser, the session cookie is managed by the browser itself.
+#[wasm_bindgen]
+pub struct ClientBuilder(ApiClientBuilder);
+
+#[wasm_bindgen]
+impl ClientBuilder {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> ClientBuilder {
+        ClientBuilder(ApiClientBuilder::new())
+    }
+
+    pub fn build(self) -> Result<Client, JsError> {
+        Ok(Client(self.0.build()?))
+    }
+
+    pub fn jsessionid(self, jsessionid: String) -> ClientBuilder {
+        ClientBuilder(self.0.jsessionid(jsessionid))
+    }
+
+    pub fn userpass(self, username: String, password: String) -> ClientBuilder {
+        ClientBuilder(self.0.userpass(username, password))
+    }
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+pub struct Client(ApiClient);
+
+#[wasm_bindgen]
+impl Client {
+    /// Fetch the JSON text of the given API path, e.g. `TcBook/info`
+    pub async fn fetch(&self, path: String) -> Result<String, JsError> {
+        let mut body = String::new();
+        self.0
+            .fetch(&ApiEndpoint::Other(path))
+            .await?
+            .read_to_string(&mut body)?;
+        Ok(body)
+    }
+}
+
+// Importers: These parse the given JSON text and return the parsed data as JSON, i.e. with
+// the field types checked and, for the wiki table, converted from wiki markup.
+// Also useful to check a user-supplied file before handing it to a ShipsBuilder.
+
+fn to_json(data: &impl serde::Serialize) -> Result<String, JsError> {
+    Ok(serde_json::to_string(data)?)
+}
+
+#[wasm_bindgen(js_name = readTcBook)]
+pub fn read_tcbook(json: &str) -> Result<String, JsError> {
+    to_json(&kancolle_arcade_net::read_tclist(json.as_bytes())?)
+}
+
+#[wasm_bindgen(js_name = readBlueprintList)]
+pub fn read_blueprint_list(json: &str) -> Result<String, JsError> {
+    to_json(&kancolle_arcade_net::read_blueprintlist(json.as_bytes())?)
+}
+
+#[wasm_bindgen(js_name = readCharacterList)]
+pub fn read_character_list(json: &str) -> Result<String, JsError> {
+    to_json(&kancolle_arcade_net::read_characterlist(json.as_bytes())?)
+}
+
+#[wasm_bindgen(js_name = readKekkonKakkoKariList)]
+pub fn read_kekkonkakkokari_list(json: &str) -> Result<String, JsError> {
+    to_json(&kancolle_arcade_net::read_kekkonkakkokarilist(
+        json.as_bytes(),
+    )?)
+}
+
+#[wasm_bindgen(js_name = readPlacePlaces)]
+pub fn read_place_places(json: &str) -> Result<String, JsError> {
+    to_json(&kancolle_arcade_net::read_place_places(json.as_bytes())?)
+}
+
+#[wasm_bindgen(js_name = readPlaceDistricts)]
+pub fn read_place_districts(json: &str) -> Result<String, JsError> {
+    to_json(&kancolle_arcade_net::read_place_districts(json.as_bytes())?)
+}
+
+#[wasm_bindgen(js_name = readKansenTable)]
+pub fn read_kansen_table(table: &str) -> Result<String, JsError> {
+    to_json(&wikiwiki_jp_kancolle_a::read_kansen_table(
+        table.as_bytes(),
+    )?)
+}
@@ -0,0 +1,223 @@
+//! Tests for the API client, against a minimal local HTTP server.
+#![cfg(not(target_arch = "wasm32"))]
+
+use kancolle_a::importer::kancolle_arcade_net::{ApiEndpoint, ClientBuilder};
+use kancolle_a::Error;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// What the local server has seen.
+#[derive(Default)]
+struct ServerState {
+    /// Whether Auth/login should succeed
+    accept_login: AtomicBool,
+    logged_in: AtomicBool,
+    logins: AtomicUsize,
+    requests: AtomicUsize,
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+}
+
+/// Serves each path's own name as its content, after a short delay, or 404 for paths
+/// containing "missing". Paths under "Event/" need no login, everything else is 403
+/// until a POST to Auth/login succeeds.
+/// Returns the base URL, and the server's state.
+fn serve(accept_login: bool) -> (String, Arc<ServerState>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}/", listener.local_addr().unwrap());
+    let state = Arc::new(ServerState::default());
+    state.accept_login.store(accept_login, Ordering::SeqCst);
+
+    let server_state = state.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let state = server_state.clone();
+            std::thread::spawn(move || {
+                let mut stream = stream.unwrap();
+                let mut request_line = String::new();
+                let mut reader = BufReader::new(&stream);
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                let mut header = String::new();
+                while reader.read_line(&mut header).unwrap() > 2 {
+                    if let Some(length) =
+                        header.to_ascii_lowercase().strip_prefix("content-length:")
+                    {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    header.clear();
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                state.requests.fetch_add(1, Ordering::SeqCst);
+                let in_flight = state.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                state.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(20));
+
+                let path = request_line.split(' ').nth(1).unwrap_or("").to_string();
+                let (status, content) = if path == "/Auth/login" {
+                    state.logins.fetch_add(1, Ordering::SeqCst);
+                    let login = state.accept_login.load(Ordering::SeqCst);
+                    state.logged_in.store(login, Ordering::SeqCst);
+                    (
+                        "200 OK",
+                        format!(
+                            r#"{{"login": {login}, "loginCode": "{}", "confirmed": true, "aimeCard": {{"cardNum": 0, "cardList": []}}, "hashAuthKey": null}}"#,
+                            if login { "0000" } else { "9999" }
+                        ),
+                    )
+                } else if path.contains("missing") {
+                    ("404 Not Found", String::new())
+                } else if !path.starts_with("/Event/") && !state.logged_in.load(Ordering::SeqCst) {
+                    ("403 Forbidden", String::new())
+                } else {
+                    ("200 OK", path)
+                };
+
+                state.in_flight.fetch_sub(1, Ordering::SeqCst);
+                let response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{content}",
+                    content.len()
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            });
+        }
+    });
+
+    (base_url, state)
+}
+
+fn read_to_string(mut reader: Box<dyn Read>) -> String {
+    let mut content = String::new();
+    reader.read_to_string(&mut content).unwrap();
+    content
+}
+
+fn is_status(error: &Error, status: u16) -> bool {
+    matches!(error, Error::ReqwestError(error) if error.status().map(|status| status.as_u16()) == Some(status))
+}
+
+#[tokio::test]
+async fn test_api_client_fetch() {
+    let (base_url, state) = serve(true);
+    let client = ClientBuilder::new().api_base(base_url).build().unwrap();
+
+    let event_info = client.fetch(&ApiEndpoint::EventInfo).await.unwrap();
+    assert_eq!(read_to_string(event_info), "/Event/info");
+
+    let missing = client
+        .fetch(&ApiEndpoint::Other("Event/missing".to_string()))
+        .await;
+    assert!(is_status(&missing.err().unwrap(), 404));
+
+    // No userpass, so no login
+    let tc_book = client.fetch(&ApiEndpoint::TcBookInfo).await;
+    assert!(is_status(&tc_book.err().unwrap(), 403));
+    assert_eq!(state.logins.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_api_client_fetch_snapshot_login_once() {
+    let (base_url, state) = serve(true);
+    let client = ClientBuilder::new()
+        .api_base(base_url)
+        .userpass("user".to_string(), "pass".to_string())
+        .build()
+        .unwrap();
+
+    let endpoints = [
+        ApiEndpoint::TcBookInfo,
+        ApiEndpoint::EventInfo,
+        ApiEndpoint::BlueprintListInfo,
+        ApiEndpoint::Other("Event/missing".to_string()),
+        ApiEndpoint::CharacterListInfo,
+    ];
+    let mut snapshot = client.fetch_snapshot(&endpoints).await.into_iter();
+
+    assert_eq!(
+        read_to_string(snapshot.next().unwrap().unwrap()),
+        "/TcBook/info"
+    );
+    assert_eq!(
+        read_to_string(snapshot.next().unwrap().unwrap()),
+        "/Event/info"
+    );
+    assert_eq!(
+        read_to_string(snapshot.next().unwrap().unwrap()),
+        "/BlueprintList/info"
+    );
+    assert!(is_status(&snapshot.next().unwrap().err().unwrap(), 404));
+    assert_eq!(
+        read_to_string(snapshot.next().unwrap().unwrap()),
+        "/CharacterList/info"
+    );
+    assert!(snapshot.next().is_none());
+
+    // One login for the three forbidden endpoints, which were then retried.
+    assert_eq!(state.logins.load(Ordering::SeqCst), 1);
+    assert_eq!(state.requests.load(Ordering::SeqCst), 5 + 1 + 3);
+
+    // The session is reused
+    let quest_info = client.fetch(&ApiEndpoint::QuestInfo).await.unwrap();
+    assert_eq!(read_to_string(quest_info), "/Quest/info");
+    assert_eq!(state.logins.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_api_client_fetch_snapshot_login_failed() {
+    let (base_url, state) = serve(false);
+    let client = ClientBuilder::new()
+        .api_base(base_url)
+        .userpass("user".to_string(), "wrong".to_string())
+        .build()
+        .unwrap();
+
+    let endpoints = [
+        ApiEndpoint::EventInfo,
+        ApiEndpoint::TcBookInfo,
+        ApiEndpoint::BlueprintListInfo,
+    ];
+    let mut snapshot = client.fetch_snapshot(&endpoints).await.into_iter();
+
+    assert_eq!(
+        read_to_string(snapshot.next().unwrap().unwrap()),
+        "/Event/info"
+    );
+    // The first forbidden endpoint reports the login failure, the rest keep their 403.
+    assert!(matches!(
+        snapshot.next().unwrap().err().unwrap(),
+        Error::AuthenticationFailed(login_code) if login_code == "9999"
+    ));
+    assert!(is_status(&snapshot.next().unwrap().err().unwrap(), 403));
+    assert!(snapshot.next().is_none());
+
+    assert_eq!(state.logins.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_api_client_max_concurrent_requests() {
+    let (base_url, state) = serve(true);
+    let client = ClientBuilder::new()
+        .api_base(base_url)
+        .max_concurrent_requests(3)
+        .build()
+        .unwrap();
+
+    let endpoints: Vec<_> = (0..20)
+        .map(|index| ApiEndpoint::Other(format!("Event/{index}")))
+        .collect();
+    let snapshot = client.fetch_snapshot(&endpoints).await;
+
+    // Still in order
+    for (index, response) in snapshot.into_iter().enumerate() {
+        assert_eq!(read_to_string(response.unwrap()), format!("/Event/{index}"));
+    }
+    assert_eq!(state.requests.load(Ordering::SeqCst), 20);
+    let max_in_flight = state.max_in_flight.load(Ordering::SeqCst);
+    assert!(max_in_flight <= 3, "{max_in_flight} requests in flight");
+    assert!(max_in_flight > 1, "requests were not concurrent");
+}
@@ -0,0 +1,81 @@
+use chrono::TimeZone;
+use kancolle_a::blueprint_forecast::{savings_target, BlueprintForecast, ExpiryBasis};
+use kancolle_a::ships::ShipsBuilder;
+use kancolle_a::time::japan;
+
+use lazy_static_include::*;
+
+lazy_static_include_bytes! {
+    TCBOOK => "tests/fixtures/latest/TcBook_info.json",
+    BPLIST => "tests/fixtures/latest/BlueprintList_info.json",
+}
+
+#[tokio::test]
+async fn test_blueprint_forecast() {
+    let ships = ShipsBuilder::default()
+        .book_from_reader(TCBOOK.as_ref())
+        .blueprint_from_reader(BPLIST.as_ref())
+        .build()
+        .await
+        .unwrap();
+
+    // The fixture's current month is June 2025
+    let june = japan().with_ymd_and_hms(2025, 6, 30, 23, 59, 59).unwrap();
+    let forecast = BlueprintForecast::new(&ships, june, ExpiryBasis::Reported);
+
+    assert!(!forecast.forecasts().is_empty());
+    assert!(forecast.expiring_count() > 0);
+    // At most the 127 reported as expiring this month
+    assert!(forecast.expiring_count() <= 127);
+    for ship_forecast in forecast.forecasts() {
+        assert!(ship_forecast.expiring_count() > 0);
+        assert!(ship_forecast.expiring_count() <= ship_forecast.held());
+        for batch in ship_forecast.expiring() {
+            assert!(batch.expires_at <= june);
+        }
+        // A savings target we can't yet afford spends nothing
+        if !ship_forecast.target_affordable() {
+            let expiring_this_month: u16 = ship_forecast
+                .ship()
+                .blueprint()
+                .as_ref()
+                .unwrap()
+                .expiration_date_list
+                .iter()
+                .filter(|expiration_date| expiration_date.expire_this_month)
+                .map(|expiration_date| expiration_date.blueprint_num)
+                .sum();
+            assert_eq!(ship_forecast.expiring_count(), expiring_this_month);
+        }
+        let mut spend_options = ship_forecast.spend_options().iter().peekable();
+        while let Some(remodel) = spend_options.next() {
+            assert!(remodel.blueprints() <= ship_forecast.held());
+            // Missing cards first
+            if let Some(next) = spend_options.peek() {
+                assert!(remodel.card_missing() || !next.card_missing());
+            }
+        }
+    }
+    // Earliest expiry first
+    assert!(forecast
+        .forecasts()
+        .windows(2)
+        .all(|pair| pair[0].first_expiry() <= pair[1].first_expiry()));
+
+    // Expiring on the 11th, but not by the end of the month
+    let mid_june = japan().with_ymd_and_hms(2025, 6, 20, 0, 0, 0).unwrap();
+    let forecast = BlueprintForecast::new(&ships, mid_june, ExpiryBasis::Reported);
+    assert!(!forecast.forecasts().is_empty());
+    let forecast = BlueprintForecast::new(&ships, mid_june, ExpiryBasis::MonthEnd);
+    assert!(forecast.forecasts().is_empty());
+    let forecast = BlueprintForecast::new(&ships, june, ExpiryBasis::MonthEnd);
+    assert!(!forecast.forecasts().is_empty());
+
+    // Targets are remodels without a Normal card
+    for ship in ships.values() {
+        if let Some(target) = savings_target(ship) {
+            assert!(target.shipmod().remodel_level() > 0);
+            assert!(target.card_missing());
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use chrono::{TimeZone, Utc};
+use kancolle_a::blueprint_forecast::ExpiryBasis;
+use kancolle_a::calendar::Calendar;
+use kancolle_a::ships::ShipsBuilder;
+
+use lazy_static_include::*;
+
+lazy_static_include_bytes! {
+    BPLIST => "tests/fixtures/latest/BlueprintList_info.json",
+}
+
+#[tokio::test]
+async fn test_calendar_blueprint_expiries() {
+    let ships = ShipsBuilder::default()
+        .blueprint_from_reader(BPLIST.as_ref())
+        .build()
+        .await
+        .unwrap();
+    let dtstamp = Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap();
+
+    // One per month, June to October 2025, totalling the fixture's blueprints
+    let calendar = Calendar::new(dtstamp).blueprint_expiries(&ships, ExpiryBasis::Reported);
+    assert_eq!(calendar.events().len(), 5);
+    assert_eq!(calendar.events()[0].summary, "127 blueprints expire");
+    let ics = calendar.to_ics();
+    assert!(ics.contains("DTSTART;TZID=Asia/Tokyo:20250611T235959\r\n"));
+    assert!(ics.contains("UID:blueprint-expiry-20250611@kancolle-a\r\n"));
+
+    let calendar = Calendar::new(dtstamp).blueprint_expiries(&ships, ExpiryBasis::MonthEnd);
+    let ics = calendar.to_ics();
+    assert!(ics.contains("DTSTART;TZID=Asia/Tokyo:20250630T235959\r\n"));
+}
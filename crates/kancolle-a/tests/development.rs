@@ -0,0 +1,62 @@
+use kancolle_a::development::{DevelopmentCost, DevelopmentPlan};
+use kancolle_a::importer::kancolle_arcade_net::{read_equipbook, read_personal_basic_info};
+use kancolle_a::ships::ShipsBuilder;
+
+use lazy_static_include::*;
+
+lazy_static_include_bytes! {
+    CHARLIST => "tests/fixtures/latest/CharacterList_info.json",
+    EQUIPBOOK => "tests/fixtures/latest/EquipBook_info.json",
+    BASIC_INFO => "tests/fixtures/latest/Personal_basicInfo.json",
+}
+
+#[tokio::test]
+async fn test_development_plan() {
+    let ships = ShipsBuilder::new()
+        .character_from_reader(CHARLIST.as_ref())
+        .build()
+        .await
+        .unwrap();
+    let equip_book = read_equipbook(EQUIPBOOK.as_ref()).unwrap();
+
+    let plan = DevelopmentPlan::new(&ships);
+    assert!(!plan.developments().is_empty());
+    for development in plan.developments() {
+        assert!(development.remaining() > 0);
+        assert!(development.book_equipment(&equip_book).is_some());
+    }
+    // In the game's order
+    assert!(plan
+        .developments()
+        .windows(2)
+        .all(|pair| pair[0].shipmod() <= pair[1].shipmod()));
+
+    let 伊勢改二: Vec<_> = plan
+        .developments()
+        .iter()
+        .filter(|development| development.shipmod().name() == "伊勢改二")
+        .collect();
+    assert_eq!(伊勢改二.len(), 2);
+    assert!(伊勢改二[0].level_met());
+    assert_eq!(
+        伊勢改二[0].cost(),
+        DevelopmentCost {
+            strategy_points: 2160,
+            material_medals: 4,
+        }
+    );
+
+    let total = plan.total_cost();
+    let available = plan.available_cost();
+    assert!(available.strategy_points <= total.strategy_points);
+    assert!(available.material_medals <= total.material_medals);
+    assert!(plan.available().count() < plan.developments().len());
+
+    let basic_info = read_personal_basic_info(BASIC_INFO.as_ref()).unwrap();
+    let held = DevelopmentCost::held(&basic_info);
+    assert_eq!(held.strategy_points, 28498);
+    assert_eq!(
+        total.shortfall(&held).strategy_points,
+        total.strategy_points.saturating_sub(28498)
+    );
+}
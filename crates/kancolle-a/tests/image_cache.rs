@@ -0,0 +1,147 @@
+//! Tests for the image cache, against a minimal local HTTP server.
+#![cfg(not(target_arch = "wasm32"))]
+
+use kancolle_a::importer::kancolle_arcade_net::image_cache::{ImageCacheBuilder, MANIFEST_FILE};
+use kancolle_a::importer::kancolle_arcade_net::{ImageKind, ImageRef};
+use kancolle_a::ships::ShipsBuilder;
+use kancolle_a::Error;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use lazy_static_include::*;
+
+lazy_static_include_bytes! {
+    TCBOOK => "tests/fixtures/latest/TcBook_info.json",
+    CHARLIST => "tests/fixtures/latest/CharacterList_info.json",
+}
+
+/// Serves each path's own name as its content, or 404 for paths containing "missing".
+/// Returns the base URL, and a count of requests served.
+fn serve() -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}/", listener.local_addr().unwrap());
+    let requests = Arc::new(AtomicUsize::new(0));
+
+    let counter = requests.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request_line = String::new();
+            let mut reader = BufReader::new(&stream);
+            reader.read_line(&mut request_line).unwrap();
+            // Drain the headers
+            let mut header = String::new();
+            while reader.read_line(&mut header).unwrap() > 2 {
+                header.clear();
+            }
+
+            counter.fetch_add(1, Ordering::SeqCst);
+            let path = request_line.split(' ').nth(1).unwrap_or("").to_string();
+            let response = match path.contains("missing") {
+                true => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_string(),
+                false => format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{path}",
+                    path.len()
+                ),
+            };
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+
+    (base_url, requests)
+}
+
+fn cache_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!(
+        "kancolle-a-image-cache-{name}-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&directory);
+    directory
+}
+
+#[tokio::test]
+async fn test_image_cache_fetch() {
+    let (base_url, requests) = serve();
+    let directory = cache_directory("fetch");
+
+    let images = vec![
+        ImageRef::new(ImageKind::Card, "s/tc_1_d7ju63kolamj.jpg").unwrap(),
+        ImageRef::new(ImageKind::Status, "i/i_d7ju63kolamj_n.png").unwrap(),
+        ImageRef::new(ImageKind::Equipment, "e/equip_missing.png").unwrap(),
+    ];
+
+    let mut cache = ImageCacheBuilder::new(&directory)
+        .base_url(base_url.clone())
+        .build()
+        .unwrap();
+    let report = cache.fetch_all(images.clone()).await.unwrap();
+    assert_eq!(report.fetched, images[0..2]);
+    assert!(report.skipped.is_empty());
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+    assert_eq!(
+        std::fs::read_to_string(cache.local_path(&images[0]).unwrap()).unwrap(),
+        "/pictureBook/s/tc_1_d7ju63kolamj.jpg"
+    );
+    assert_eq!(
+        cache.local_path(&images[1]).unwrap(),
+        directory.join("chara/i/i_d7ju63kolamj_n.png")
+    );
+    assert_eq!(cache.entry(&images[1]).unwrap().size, 29);
+    assert!(directory.join(MANIFEST_FILE).exists());
+
+    // A new cache on the same directory only fetches what it doesn't have
+    let mut cache = ImageCacheBuilder::new(&directory)
+        .base_url(base_url)
+        .build()
+        .unwrap();
+    assert!(cache.contains(&images[0]));
+    let report = cache.fetch_all(images.clone()).await.unwrap();
+    assert!(report.fetched.is_empty());
+    assert_eq!(report.skipped, images[0..2]);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(requests.load(Ordering::SeqCst), 4);
+
+    // Images from an unknown directory fail without a request
+    let icon = ImageRef::new(ImageKind::EquipmentIcon, "equip_icon_6_a2e6kq8r27wg.png").unwrap();
+    assert!(matches!(
+        cache.fetch(&icon).await,
+        Err(Error::UnknownImageLocation(ImageKind::EquipmentIcon))
+    ));
+    assert!(!cache.contains(&icon));
+    assert_eq!(cache.local_path(&icon), None);
+    assert_eq!(requests.load(Ordering::SeqCst), 4);
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[tokio::test]
+async fn test_ships_images() {
+    let ships = ShipsBuilder::new()
+        .book_from_reader(TCBOOK.as_ref())
+        .character_from_reader(CHARLIST.as_ref())
+        .build()
+        .await
+        .unwrap();
+
+    let images = ships.images();
+    for kind in [
+        ImageKind::Card,
+        ImageKind::Married,
+        ImageKind::Status,
+        ImageKind::Equipment,
+    ] {
+        assert!(
+            images.iter().any(|image| image.kind() == kind),
+            "no {kind} images"
+        );
+    }
+    assert!(images.is_sorted());
+    assert!(images.windows(2).all(|pair| pair[0] != pair[1]));
+}
@@ -0,0 +1,69 @@
+use chrono::NaiveDate;
+use kancolle_a::kekkon::{KekkonPlan, KekkonStatus, KEKKON_LEVEL};
+use kancolle_a::ships::ShipsBuilder;
+
+use lazy_static_include::*;
+
+lazy_static_include_bytes! {
+    TCBOOK => "tests/fixtures/latest/TcBook_info.json",
+    CHARLIST => "tests/fixtures/latest/CharacterList_info.json",
+}
+
+#[tokio::test]
+async fn test_kekkon_plan() {
+    let ships = ShipsBuilder::default()
+        .book_from_reader(TCBOOK.as_ref())
+        .character_from_reader(CHARLIST.as_ref())
+        .build()
+        .await
+        .unwrap();
+
+    let today = NaiveDate::from_ymd_opt(2024, 11, 1).unwrap();
+    let plan = KekkonPlan::new(&ships, today);
+
+    // Highest level first
+    assert!(plan
+        .candidates()
+        .windows(2)
+        .all(|pair| (pair[0].level(), pair[0].exp_percent())
+            >= (pair[1].level(), pair[1].exp_percent())));
+
+    let status = |name: &str| {
+        plan.candidates()
+            .iter()
+            .find(|candidate| candidate.shipmod().name() == name)
+            .unwrap()
+            .status()
+    };
+    // Married per the Character List and the TcBook
+    assert_eq!(status("伊勢改二"), KekkonStatus::Married);
+    assert_eq!(status("扶桑改"), KekkonStatus::Married);
+    // Same level as 伊勢改二, but a different form
+    assert_eq!(status("伊勢改"), KekkonStatus::Eligible);
+
+    let 伊勢改 = plan
+        .eligible()
+        .find(|candidate| candidate.shipmod().name() == "伊勢改")
+        .unwrap();
+    assert_eq!(伊勢改.married_forms(), &["伊勢改二"]);
+    assert!(伊勢改.start_time().unwrap() <= today);
+
+    for candidate in plan.eligible() {
+        assert!(candidate.level() >= KEKKON_LEVEL);
+    }
+    assert!(plan
+        .with_status(KekkonStatus::LevelNeeded)
+        .all(|candidate| candidate.level() < KEKKON_LEVEL));
+    assert_eq!(plan.not_yet_available().count(), 0);
+
+    // Before 伊勢改 was marriageable
+    let plan = KekkonPlan::new(&ships, NaiveDate::from_ymd_opt(2018, 1, 1).unwrap());
+    assert!(matches!(
+        plan.candidates()
+            .iter()
+            .find(|candidate| candidate.shipmod().name() == "伊勢改")
+            .unwrap()
+            .status(),
+        KekkonStatus::NotYetAvailable(_)
+    ));
+}
@@ -0,0 +1,96 @@
+use chrono::NaiveDate;
+use kancolle_a::planning::{RecommendationKind, RenovationPlanner};
+use kancolle_a::ships::ShipsBuilder;
+
+use lazy_static_include::*;
+
+lazy_static_include_bytes! {
+    TCBOOK => "tests/fixtures/latest/TcBook_info.json",
+    BPLIST => "tests/fixtures/latest/BlueprintList_info.json",
+    CHARLIST => "tests/fixtures/latest/CharacterList_info.json",
+}
+
+#[tokio::test]
+async fn test_renovation_planner() {
+    let ships = ShipsBuilder::default()
+        .book_from_reader(TCBOOK.as_ref())
+        .blueprint_from_reader(BPLIST.as_ref())
+        .character_from_reader(CHARLIST.as_ref())
+        .build()
+        .await
+        .unwrap();
+
+    let today = NaiveDate::from_ymd_opt(2024, 11, 1).unwrap();
+    let plan = RenovationPlanner::new().plan(&ships, today);
+
+    // Every kind is reported, in the default priority order
+    let kinds: Vec<RecommendationKind> = plan.iter().map(|rec| rec.kind()).collect();
+    assert!(kinds.is_sorted());
+    assert!(kinds.contains(&RecommendationKind::MissingAll));
+    assert!(kinds.contains(&RecommendationKind::Kekkonable));
+
+    for recommendation in &plan {
+        assert!(!recommendation.reason().is_empty());
+        match recommendation.kind() {
+            RecommendationKind::MissingAll | RecommendationKind::MissingBase => {
+                assert!(recommendation.current().is_none())
+            }
+            RecommendationKind::Constructable => {
+                let (cost, _) = recommendation.blueprint_cost().unwrap();
+                assert!(recommendation.blueprints_held() >= cost);
+                assert!(recommendation.next().is_some());
+            }
+            RecommendationKind::UpgradeReady => assert_eq!(recommendation.stars(), Some(5)),
+            RecommendationKind::UpgradeAvailable | RecommendationKind::StarsNeeded => {
+                assert!(recommendation.stars().unwrap() < 5)
+            }
+            RecommendationKind::Kekkonable => {
+                assert!(recommendation.current().unwrap().character().is_some())
+            }
+        }
+    }
+
+    // Fewest stars first within a kind
+    let stars: Vec<u16> = plan
+        .iter()
+        .filter(|rec| rec.kind() == RecommendationKind::StarsNeeded)
+        .map(|rec| rec.stars().unwrap())
+        .collect();
+    assert!(stars.is_sorted());
+
+    // 伊勢改 is marriageable on this date
+    assert!(plan
+        .iter()
+        .any(|rec| rec.kind() == RecommendationKind::Kekkonable
+            && rec.current().unwrap().name() == "伊勢改"));
+
+    // Reversed and restricted priorities
+    let priorities = vec![
+        RecommendationKind::StarsNeeded,
+        RecommendationKind::Kekkonable,
+    ];
+    let plan = RenovationPlanner::new()
+        .priorities(priorities.clone())
+        .plan(&ships, today);
+    assert!(!plan.is_empty());
+    assert!(plan
+        .iter()
+        .map(|rec| priorities
+            .iter()
+            .position(|kind| *kind == rec.kind())
+            .unwrap())
+        .is_sorted());
+
+    // Filtered by the name of any form
+    let plan = RenovationPlanner::new()
+        .ship_names(vec!["伊勢改二".to_string()])
+        .plan(&ships, today);
+    assert!(!plan.is_empty());
+    assert!(plan.iter().all(|rec| rec.ship_name() == "伊勢"));
+
+    // Nothing with no priorities
+    assert!(RenovationPlanner::new()
+        .priorities(vec![])
+        .plan(&ships, today)
+        .is_empty());
+}
@@ -1,4 +1,7 @@
+use kancolle_a::cards::{Card, CardVariation};
+use kancolle_a::importer::kancolle_arcade_net::BookShipCardPageSource;
 use kancolle_a::ships::{self, ShipsBuilder};
+use kancolle_a::{DataSource, Error, Warning};
 
 // This is an integration test, so we're only using it against "current" data.
 
@@ -180,10 +183,436 @@ async fn test_ships_characters_only_import() {
     // Opportunistic test for ship_remodel_level_guess
     assert!(ships
         .shipmod_iter()
-        .all(|ship| ships::ship_remodel_level_guess(ship.name())
+        .all(|ship| ships::ship_remodel_level_guess(ship.name()).unwrap()
             == ship.character().as_ref().unwrap().remodel_lv));
 }
 
+#[tokio::test]
+async fn test_ships_duplicate_import() {
+    // Same data twice, i.e. every ship is duplicated.
+    let kekkon = std::str::from_utf8(KANMUSU.as_ref()).unwrap().trim();
+    let doubled_kekkon = format!("{},{}", &kekkon[..kekkon.len() - 1], &kekkon[1..]);
+
+    let error = ShipsBuilder::new()
+        .kekkon_from_reader(std::io::Cursor::new(doubled_kekkon))
+        .build()
+        .await
+        .err()
+        .unwrap();
+
+    assert!(matches!(
+        error,
+        Error::DuplicateEntry {
+            data_source: DataSource::Kekkon,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_ships_lenient_import() {
+    // Add an unknown field to the first kekkon entry
+    let kekkon = std::str::from_utf8(KANMUSU.as_ref()).unwrap();
+    let modified_kekkon = kekkon.replacen('{', r#"{"newField": 1,"#, 1);
+
+    let error = ShipsBuilder::new()
+        .kekkon_from_reader(std::io::Cursor::new(modified_kekkon.clone()))
+        .build()
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(error, Error::SerdeJsonError(_)));
+
+    let ships = ShipsBuilder::new()
+        .kekkon_from_reader(std::io::Cursor::new(modified_kekkon))
+        .lenient()
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(ships.len(), FIXTURE_KANMUSU_SHIPS);
+    assert_eq!(ships.warnings().len(), 1);
+    let Warning::UnknownField { data_source, field } = &ships.warnings()[0] else {
+        panic!("Unexpected warning {:?}", ships.warnings()[0]);
+    };
+    assert_eq!(*data_source, DataSource::Kekkon);
+    assert_eq!(field.path, "[0].newField");
+}
+
+#[tokio::test]
+async fn test_ships_lenient_unknown_value() {
+    // Give the first character a ship type we don't know
+    let charlist = std::str::from_utf8(CHARLIST.as_ref()).unwrap();
+    let modified_charlist =
+        charlist.replacen(r#""shipType": "戦艦""#, r#""shipType": "宇宙戦艦""#, 1);
+    assert_ne!(charlist, modified_charlist);
+
+    let error = ShipsBuilder::new()
+        .character_from_reader(std::io::Cursor::new(modified_charlist.clone()))
+        .build()
+        .await
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("宇宙戦艦"));
+
+    let ships = ShipsBuilder::new()
+        .character_from_reader(std::io::Cursor::new(modified_charlist))
+        .lenient()
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(ships.warnings().len(), 1);
+    let Warning::UnknownValue { data_source, field } = &ships.warnings()[0] else {
+        panic!("Unexpected warning {:?}", ships.warnings()[0]);
+    };
+    assert_eq!(*data_source, DataSource::Character);
+    assert!(field.path.ends_with("].shipType"));
+    assert_eq!(field.value, "宇宙戦艦");
+}
+
+#[tokio::test]
+async fn test_ships_json_export() {
+    let ships = ShipsBuilder::new()
+        .kekkon_from_reader(KANMUSU.as_ref())
+        .book_from_reader(TCBOOK.as_ref())
+        .character_from_reader(CHARLIST.as_ref())
+        .blueprint_from_reader(BPLIST.as_ref())
+        .static_wiki_kansen_list()
+        .static_wiki_kaizou_kansen_list()
+        .build()
+        .await
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&ships.to_json().unwrap()).unwrap();
+    let json = json.as_object().unwrap();
+    assert_eq!(json.len(), KANSEN_TABLE_SHIPS);
+
+    let 長門 = &json["長門"];
+    assert_eq!(長門["name"], "長門");
+    assert_eq!(長門["blueprint"]["shipName"], "長門");
+    let 長門改 = &長門["mods"][1];
+    assert_eq!(長門改["name"], "長門改");
+    assert_eq!(長門改["remodelLevel"], 1);
+    assert_eq!(長門改["book"]["bookNo"], 1);
+    assert_eq!(長門改["character"]["shipName"], "長門改");
+    assert_eq!(長門改["kekkon"]["name"], "長門改");
+    assert_eq!(長門改["wikiListEntry"]["艦名"], "長門改");
+}
+
+#[tokio::test]
+async fn test_ships_by_class() {
+    let ships = ShipsBuilder::new()
+        .kekkon_from_reader(KANMUSU.as_ref())
+        .book_from_reader(TCBOOK.as_ref())
+        .character_from_reader(CHARLIST.as_ref())
+        .blueprint_from_reader(BPLIST.as_ref())
+        .static_wiki_kansen_list()
+        .static_wiki_kaizou_kansen_list()
+        .build()
+        .await
+        .unwrap();
+
+    let 千歳型 = ships.ship_class("千歳型").unwrap();
+    assert_eq!(*千歳型.id(), Some(5));
+    let members: Vec<(&str, Option<u16>)> = 千歳型
+        .members()
+        .iter()
+        .map(|member| (member.ship_name().as_str(), *member.class_index()))
+        .collect();
+    assert_eq!(members, vec![("千歳", Some(1)), ("千代田", Some(2))]);
+
+    // The book has no class for 明石, so this comes from the wiki
+    let 明石型 = ships.ship_class("明石型").unwrap();
+    assert_eq!(明石型.members().len(), 1);
+    assert_eq!(明石型.members()[0].ship_name(), "明石");
+
+    // The class of the first mod is used
+    assert!(ships.ship_class("春日丸級").is_some());
+    assert!(ships.ship_class("大鷹型").is_none());
+
+    // Every ship has a class here, thanks to the wiki data.
+    let by_class = ships.by_class();
+    assert_eq!(by_class.len(), ships.ship_classes().len());
+    assert_eq!(
+        by_class.iter().map(|(_, ships)| ships.len()).sum::<usize>(),
+        ships.len()
+    );
+    let (陽炎型, 陽炎型_ships) = by_class
+        .iter()
+        .find(|(class, _)| class.name().as_deref() == Some("陽炎型"))
+        .unwrap();
+    assert_eq!(陽炎型.id(), &Some(23));
+    assert_eq!(陽炎型_ships[0].name(), "陽炎");
+    assert_eq!(陽炎型_ships[1].name(), "不知火");
+
+    assert_eq!(
+        ships.get("千代田").unwrap().shipmod_blueprint_cost(4),
+        Some((6, 0))
+    );
+}
+
+#[tokio::test]
+async fn test_ships_remodel_graph() {
+    let ships = ShipsBuilder::default().build().await.unwrap();
+
+    let 最上 = ships.get("最上").unwrap();
+    assert_eq!(
+        最上
+            .mods()
+            .iter()
+            .map(|shipmod| shipmod.name().as_str())
+            .collect::<Vec<_>>(),
+        vec!["最上", "最上改", "最上改二", "最上改二特"]
+    );
+    let next_forms: Vec<(&str, bool)> = ships
+        .next_forms("最上改二")
+        .into_iter()
+        .map(|remodel| (remodel.to().as_str(), *remodel.conversion()))
+        .collect();
+    assert_eq!(next_forms, vec![("最上改二特", true)]);
+
+    // A graph with a branch not in the bundled data: 鳥海改二 converts to and from a
+    // hypothetical 鳥海改二甲 at the same remodel level.
+    let remodels = r#"[
+        {
+            "ship": "鳥海",
+            "forms": [
+                {"name": "鳥海", "level": 0},
+                {"name": "鳥海改", "level": 1},
+                {"name": "鳥海改二", "level": 2},
+                {"name": "鳥海改二甲", "level": 2}
+            ],
+            "remodels": [
+                {"from": "鳥海", "to": "鳥海改"},
+                {"from": "鳥海改", "to": "鳥海改二", "requirements": {"shipLevel": 65}},
+                {"from": "鳥海改二", "to": "鳥海改二甲", "conversion": true},
+                {"from": "鳥海改二甲", "to": "鳥海改二", "conversion": true}
+            ]
+        }
+    ]"#;
+    let kekkon = r#"[
+        {"id": 1, "web_id": 1, "name": "鳥海", "name_reading": "ちょうかい", "kind": "重巡洋艦", "category": "重巡級", "start_time": "2018/02/16"},
+        {"id": 2, "web_id": 2, "name": "鳥海改", "name_reading": "ちょうかいかい", "kind": "重巡洋艦", "category": "重巡級", "start_time": "2018/02/16"},
+        {"id": 3, "web_id": 3, "name": "鳥海改二", "name_reading": "ちょうかいかいに", "kind": "重巡洋艦", "category": "重巡級", "start_time": "2018/02/16"},
+        {"id": 4, "web_id": 4, "name": "鳥海改二甲", "name_reading": "ちょうかいかいにこう", "kind": "重巡洋艦", "category": "重巡級", "start_time": "2018/02/16"}
+    ]"#;
+    let ships = ShipsBuilder::new()
+        .kekkon_from_reader(kekkon.as_bytes())
+        .remodels_from_reader(remodels.as_bytes())
+        .build()
+        .await
+        .unwrap();
+
+    let 鳥海 = ships.get("鳥海").unwrap();
+    assert_eq!(鳥海.mods().len(), 4);
+    assert_eq!(
+        ships.shipmod_by_name("鳥海改二甲").unwrap().remodel_level(),
+        2
+    );
+    assert_eq!(
+        ships.next_forms("鳥海改")[0].requirements().ship_level(),
+        &Some(65)
+    );
+    assert_eq!(ships.previous_forms("鳥海改二").len(), 2);
+
+    // Without the graph, 改二甲 is guessed from its suffix.
+    let ships = ShipsBuilder::new()
+        .kekkon_from_reader(kekkon.as_bytes())
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(
+        ships.shipmod_by_name("鳥海改二甲").unwrap().remodel_level(),
+        3
+    );
+    assert!(ships.next_forms("鳥海改二").is_empty());
+}
+
+#[tokio::test]
+async fn test_ships_blueprint_costs() {
+    let ships = ShipsBuilder::default().build().await.unwrap();
+    let 最上 = ships.get("最上").unwrap();
+    assert_eq!(最上.shipmod_blueprint_cost(1), Some((3, 0)));
+    // Not remodelable with blueprints yet
+    assert_eq!(最上.shipmod_blueprint_cost(2), None);
+    assert_eq!(最上.shipmod_blueprint_cost(3), None);
+
+    // Overlaid on the bundled table
+    let blueprint_costs = r#"{
+        "version": 1,
+        "updated": "2026-10-18",
+        "shipTypes": [{"shipTypes": ["重巡洋艦"], "stages": [[3, 0], [8, 2], [8, 5]]}],
+        "ships": [{"ship": "最上"}, {"ship": "鳥海", "stages": [[4, 0], [9, 3]]}]
+    }"#;
+    let ships = ShipsBuilder::default()
+        .blueprint_costs_from_reader(blueprint_costs.as_bytes())
+        .build()
+        .await
+        .unwrap();
+    let 最上 = ships.get("最上").unwrap();
+    assert_eq!(最上.shipmod_blueprint_cost(2), Some((8, 2)));
+    assert_eq!(最上.shipmod_blueprint_cost(3), Some((8, 5)));
+    let 鳥海 = ships.get("鳥海").unwrap();
+    assert_eq!(鳥海.shipmod_blueprint_cost(2), Some((9, 3)));
+    // Bundled entries not overlaid are kept
+    assert_eq!(
+        ships.get("金剛").unwrap().shipmod_blueprint_cost(2),
+        Some((8, 2))
+    );
+}
+
+#[tokio::test]
+async fn test_ships_card_page_sources() {
+    let card_page_sources = r#"{
+        "version": 1,
+        "books": [{"bookNo": 6, "ships": "赤城, 赤城改", "sources": ["Christmas"]}]
+    }"#;
+    let ships = ShipsBuilder::new()
+        .book_from_reader(TCBOOK.as_ref())
+        .card_page_sources_from_reader(card_page_sources.as_bytes())
+        .build()
+        .await
+        .unwrap();
+    let 赤城 = ships
+        .shipmod_by_name("赤城")
+        .unwrap()
+        .book()
+        .as_ref()
+        .unwrap();
+    assert_eq!(
+        赤城.source_with(ships.card_page_sources(), 1),
+        BookShipCardPageSource::Christmas
+    );
+    // The deprecated source only knows the bundled CardPageSources.
+    #[allow(deprecated)]
+    let bundled_source = 赤城.source(1);
+    assert_eq!(bundled_source, BookShipCardPageSource::SundayBest);
+
+    let duplicate = r#"{
+        "version": 1,
+        "books": [
+            {"bookNo": 6, "ships": "赤城, 赤城改", "sources": ["Christmas"]},
+            {"bookNo": 6, "ships": "赤城, 赤城改", "sources": ["SundayBest"]}
+        ]
+    }"#;
+    assert!(matches!(
+        ShipsBuilder::new()
+            .card_page_sources_from_reader(duplicate.as_bytes())
+            .build()
+            .await,
+        Err(Error::DuplicateBookNo { book_no: 6, .. })
+    ));
+
+    // 加賀's last page is an Original Illustration with one card, not an event page
+    let mismatched = r#"{
+        "version": 1,
+        "books": [{"bookNo": 7, "ships": "加賀, 加賀改", "sources": ["SundayBest", "Christmas"]}]
+    }"#;
+    assert!(matches!(
+        ShipsBuilder::new()
+            .book_from_reader(TCBOOK.as_ref())
+            .card_page_sources_from_reader(mismatched.as_bytes())
+            .build()
+            .await,
+        Err(Error::InconsistentShip { ship_name, .. }) if ship_name == "加賀"
+    ));
+}
+
+#[tokio::test]
+async fn test_ships_card_pages() {
+    use BookShipCardPageSource::*;
+
+    let ships = ShipsBuilder::new()
+        .book_from_reader(TCBOOK.as_ref())
+        .build()
+        .await
+        .unwrap();
+    let sources = |ship_name: &str| -> Vec<BookShipCardPageSource> {
+        ships
+            .shipmod_by_name(ship_name)
+            .unwrap()
+            .card_pages()
+            .map(|(source, _)| source)
+            .collect()
+    };
+
+    // 雪風 has no swimsuits, but 雪風改 does, on the same TcBook page.
+    assert_eq!(sources("雪風"), vec![Normal]);
+    assert_eq!(sources("雪風改"), vec![Normal, Swimsuit]);
+    let 雪風 = ships.shipmod_by_name("雪風").unwrap();
+    assert_eq!(雪風.book().as_ref().unwrap().card_list.len(), 2);
+    assert_eq!(雪風.card_page_source(1), None);
+
+    // Each of 春雨 and 春雨改 has one of the two Original Illustrations.
+    assert_eq!(sources("春雨"), vec![Normal, OriginalIllustration1(false)]);
+    assert_eq!(sources("春雨改"), vec![Normal, OriginalIllustration1(true)]);
+    assert!(ships
+        .shipmod_by_name("春雨改")
+        .unwrap()
+        .card_pages()
+        .all(|(_, page)| !page.card_img_list.is_empty()));
+
+    // Not split
+    assert_eq!(
+        sources("時雨改二"),
+        ships
+            .shipmod_by_name("時雨改二")
+            .unwrap()
+            .book()
+            .as_ref()
+            .map(|book| (0..book.card_list.len() as u16)
+                .map(|priority| book.source_with(ships.card_page_sources(), priority))
+                .collect::<Vec<_>>())
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_ships_cards() {
+    let ships = ShipsBuilder::new()
+        .book_from_reader(TCBOOK.as_ref())
+        .build()
+        .await
+        .unwrap();
+
+    let acquired: u16 = ships
+        .shipmod_iter()
+        .filter_map(|shipmod| shipmod.book().as_ref())
+        .map(|book| book.acquire_num)
+        .sum();
+    assert_eq!(
+        ships.cards().filter(|card| card.owned()).count(),
+        acquired as usize
+    );
+
+    let 春雨改: Vec<Card> = ships
+        .cards()
+        .filter(|card| card.ship_name() == "春雨改")
+        .collect();
+    assert_eq!(
+        春雨改
+            .iter()
+            .map(|card| card.variation())
+            .collect::<Vec<_>>(),
+        vec![
+            CardVariation::Normal,
+            CardVariation::Holo,
+            CardVariation::Damaged,
+            CardVariation::OriginalIllustration
+        ]
+    );
+    assert_eq!(
+        春雨改[3].source(),
+        &BookShipCardPageSource::OriginalIllustration1(true)
+    );
+    assert!(ships
+        .cards()
+        .filter_map(|card| card.image_ref())
+        .all(|image| image
+            .url()
+            .unwrap()
+            .starts_with("https://kancolle-arcade.net/ac/resources/pictureBook/s/tc_")));
+}
+
 #[tokio::test]
 async fn test_ships_full_import() {
     let ships = ShipsBuilder::new()
@@ -261,8 +690,8 @@ async fn test_ships_full_import() {
 
     // Not really a test, more a record of the data in the integration tests.
     let unowned_ships: Vec<&str> = ships
-        .iter()
-        .filter_map(|(_, ship)| {
+        .values()
+        .filter_map(|ship| {
             if ship
                 .mods()
                 .iter()
@@ -303,3 +732,62 @@ async fn test_ships_full_import() {
         assert_eq!(bp_ship.ship_type, base_wiki_ship.ship_type);
     }
 }
+
+#[tokio::test]
+async fn test_ships_disp_sort() {
+    let ships = ShipsBuilder::new()
+        .book_from_reader(TCBOOK.as_ref())
+        .blueprint_from_reader(BPLIST.as_ref())
+        .character_from_reader(CHARLIST.as_ref())
+        .build()
+        .await
+        .unwrap();
+
+    // The Character List is in the in-game order already
+    let mut shipmods: Vec<_> = ships.shipmod_iter().collect();
+    shipmods.sort();
+    let character_order: Vec<u64> = shipmods
+        .iter()
+        .filter_map(|shipmod| shipmod.character().as_ref())
+        .map(|character| character.disp_sort_no)
+        .collect();
+    assert!(character_order.is_sorted());
+
+    for ship in ships.values() {
+        if let (Some(class_id), Some(character)) = (
+            ship.class_id(),
+            ship.mods()
+                .iter()
+                .find_map(|shipmod| shipmod.character().as_ref()),
+        ) {
+            assert_eq!(
+                character.disp_sort().ship_class_id,
+                class_id,
+                "{}",
+                ship.name()
+            );
+        }
+        for shipmod in ship.mods() {
+            assert_eq!(shipmod.disp_sort().remodel_lv, shipmod.remodel_level());
+        }
+    }
+
+    let mut ship_list: Vec<_> = ships.values().collect();
+    ship_list.sort();
+    assert_eq!(
+        ship_list
+            .iter()
+            .take(4)
+            .map(|ship| ship.name().as_str())
+            .collect::<Vec<_>>(),
+        vec!["長門", "陸奥", "伊勢", "日向"]
+    );
+
+    // ShipMods without Character data still sort with their Ship
+    let pola = shipmods
+        .iter()
+        .position(|shipmod| shipmod.name() == "Pola")
+        .unwrap();
+    assert_eq!(shipmods[pola + 1].name(), "Pola改");
+    assert!(shipmods[pola + 1].character().is_none());
+}
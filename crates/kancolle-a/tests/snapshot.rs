@@ -0,0 +1,155 @@
+//! Tests for the snapshot store, against the dated test fixtures and a temporary store.
+#![cfg(not(target_arch = "wasm32"))]
+
+use chrono::{TimeZone, Utc};
+use kancolle_a::importer::kancolle_arcade_net::snapshot::SnapshotStore;
+use kancolle_a::importer::kancolle_arcade_net::ApiEndpoint;
+use kancolle_a::ships::ShipsBuilder;
+use kancolle_a::Error;
+use std::io::Read;
+use std::path::PathBuf;
+
+use lazy_static_include::*;
+
+lazy_static_include_bytes! {
+    TCBOOK => "tests/fixtures/latest/TcBook_info.json",
+    BPLIST => "tests/fixtures/latest/BlueprintList_info.json",
+}
+
+fn store_directory(name: &str) -> PathBuf {
+    let directory =
+        std::env::temp_dir().join(format!("kancolle-a-snapshot-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&directory);
+    directory
+}
+
+#[tokio::test]
+async fn test_snapshot_store_fixtures() {
+    // The dated fixture directories are date-only snapshots; "latest" is not a snapshot.
+    let store = SnapshotStore::new("tests/fixtures");
+    let snapshots = store.snapshots().unwrap();
+    assert_eq!(snapshots.len(), 11);
+    assert!(snapshots
+        .windows(2)
+        .all(|pair| pair[0].taken_at() < pair[1].taken_at()));
+    assert_eq!(
+        store.latest().unwrap().unwrap().taken_at(),
+        Utc.with_ymd_and_hms(2024, 10, 31, 0, 0, 0).unwrap()
+    );
+
+    let snapshot = store
+        .at(Utc.with_ymd_and_hms(2024, 6, 15, 12, 0, 0).unwrap())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        snapshot.taken_at(),
+        Utc.with_ymd_and_hms(2024, 6, 10, 0, 0, 0).unwrap()
+    );
+    assert!(snapshot.contains(&ApiEndpoint::TcBookInfo));
+    assert!(!snapshot.contains(&ApiEndpoint::CharacterListInfo));
+    assert!(snapshot
+        .reader(&ApiEndpoint::CharacterListInfo)
+        .unwrap()
+        .is_none());
+
+    let ships = snapshot
+        .apply(ShipsBuilder::default())
+        .unwrap()
+        .build()
+        .await
+        .unwrap();
+    assert!(ships.values().any(|ship| ship.blueprint().is_some()));
+
+    assert!(store
+        .at(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_snapshot_store_save() {
+    let directory = store_directory("save");
+    let store = SnapshotStore::new(&directory);
+    assert!(store.snapshots().unwrap().is_empty());
+
+    let taken_at = Utc.with_ymd_and_hms(2025, 6, 1, 7, 15, 30).unwrap();
+    let data: Vec<(ApiEndpoint, Box<dyn Read>)> = vec![
+        (ApiEndpoint::TcBookInfo, Box::new(TCBOOK.as_ref())),
+        (ApiEndpoint::BlueprintListInfo, Box::new(BPLIST.as_ref())),
+    ];
+    let snapshot = store.save(taken_at, data).unwrap();
+    assert_eq!(snapshot.taken_at(), taken_at);
+    assert_eq!(snapshot.directory(), directory.join("2025-06-01T071530Z"));
+    assert_eq!(
+        std::fs::read(directory.join("2025-06-01T071530Z/TcBook_info.json")).unwrap(),
+        TCBOOK.as_ref()
+    );
+
+    // Listed as saved, and no partial directory left behind
+    assert_eq!(store.snapshots().unwrap(), vec![snapshot.clone()]);
+    assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 1);
+
+    // Snapshots are never overwritten
+    let error = store.save(taken_at, vec![]).unwrap_err();
+    assert!(matches!(error, Error::SnapshotExists(_)));
+
+    let later = store
+        .save(Utc.with_ymd_and_hms(2025, 7, 1, 0, 0, 0).unwrap(), vec![])
+        .unwrap();
+    assert_eq!(store.latest().unwrap(), Some(later));
+    assert_eq!(store.at(taken_at).unwrap(), Some(snapshot.clone()));
+
+    let ships = snapshot
+        .apply(ShipsBuilder::default())
+        .unwrap()
+        .build()
+        .await
+        .unwrap();
+    assert!(ships.shipmod_iter().any(|shipmod| shipmod.book().is_some()));
+    assert!(ships.values().any(|ship| ship.blueprint().is_some()));
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_snapshot_store_file_names() {
+    assert_eq!(ApiEndpoint::AuthLogin.file_name(), None);
+    assert_eq!(
+        ApiEndpoint::Other("Ranking/monthly/current".to_string()).file_name(),
+        ApiEndpoint::RankingMonthlyCurrent.file_name()
+    );
+    assert_eq!(
+        ApiEndpoint::Other("../../etc/passwd".to_string()).file_name(),
+        Some("______etc_passwd.json".to_string())
+    );
+    assert_eq!(ApiEndpoint::Other("/../".to_string()).file_name(), None);
+
+    let directory = store_directory("file-names");
+    let store = SnapshotStore::new(&directory);
+
+    // Nothing is saved if any endpoint has no file name
+    let data: Vec<(ApiEndpoint, Box<dyn Read>)> = vec![
+        (ApiEndpoint::TcBookInfo, Box::new(TCBOOK.as_ref())),
+        (ApiEndpoint::AuthLogin, Box::new(&b"{}"[..])),
+    ];
+    let error = store
+        .save(Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap(), data)
+        .unwrap_err();
+    assert!(matches!(error, Error::NoFileName(_)));
+    assert!(!directory.exists());
+
+    // Other paths stay inside the snapshot
+    let escaping = ApiEndpoint::Other("../escaped".to_string());
+    let data: Vec<(ApiEndpoint, Box<dyn Read>)> = vec![(escaping.clone(), Box::new(&b"{}"[..]))];
+    let snapshot = store
+        .save(Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap(), data)
+        .unwrap();
+    assert!(snapshot.contains(&escaping));
+    assert!(snapshot.directory().join("___escaped.json").is_file());
+    assert!(!directory.join("escaped.json").exists());
+
+    assert!(!snapshot.contains(&ApiEndpoint::AuthLogin));
+    assert!(snapshot.reader(&ApiEndpoint::AuthLogin).unwrap().is_none());
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,139 @@
+//! Tests for the JavaScript facade, run under Node.js via wasm-bindgen-test.
+//! `cargo test --target wasm32-unknown-unknown --test wasm`
+#![cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+
+use kancolle_a::wasm::{self, ClientBuilder, ShipsBuilder};
+use wasm_bindgen_test::*;
+
+// This is an integration test, so we're only using it against "current" data.
+// include_str rather than lazy_static_include, as there's no filesystem at runtime.
+const TCBOOK: &str = include_str!("fixtures/latest/TcBook_info.json");
+const KANMUSU: &str = include_str!("fixtures/latest/kanmusu_list.json");
+const BPLIST: &str = include_str!("fixtures/latest/BlueprintList_info.json");
+const CHARLIST: &str = include_str!("fixtures/latest/CharacterList_info.json");
+const PLACES: &str = include_str!("fixtures/latest/Place_places.json");
+const DISTRICTS: &str = include_str!("fixtures/latest/Place_districts.json");
+
+// See tests/ships.rs for the derivation of these.
+const KANSEN_TABLE_SHIPS: usize = 192;
+const FIXTURE_BPLIST_COUNT: usize = 149;
+
+#[wasm_bindgen_test]
+async fn test_wasm_ships_null_import() {
+    let ships = ShipsBuilder::new().build().await.unwrap();
+    assert_eq!(ships.length(), 0);
+}
+
+#[wasm_bindgen_test]
+async fn test_wasm_ships_default_import() {
+    let ships = ShipsBuilder::with_defaults().build().await.unwrap();
+    assert_eq!(ships.length(), KANSEN_TABLE_SHIPS);
+}
+
+#[wasm_bindgen_test]
+async fn test_wasm_ships_next_forms() {
+    let ships = ShipsBuilder::with_defaults().build().await.unwrap();
+    assert_eq!(ships.next_forms("最上改二"), vec!["最上改二特".to_string()]);
+    assert!(ships
+        .next_forms("最上改二特")
+        .contains(&"最上改二".to_string()));
+}
+
+#[wasm_bindgen_test]
+async fn test_wasm_ships_blueprint_only_import() {
+    let ships = ShipsBuilder::new()
+        .blueprint_from_json(BPLIST.to_string())
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(ships.length(), FIXTURE_BPLIST_COUNT);
+    assert!(ships
+        .ship_names()
+        .iter()
+        .all(|ship_name| ships.blueprint_total(ship_name).is_some()));
+}
+
+#[wasm_bindgen_test]
+async fn test_wasm_ships_full_import() {
+    let ships = ShipsBuilder::with_defaults()
+        .kekkon_from_json(KANMUSU.to_string())
+        .book_from_json(TCBOOK.to_string())
+        .character_from_json(CHARLIST.to_string())
+        .blueprint_from_json(BPLIST.to_string())
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(ships.length(), KANSEN_TABLE_SHIPS);
+
+    assert_eq!(
+        ships.shipmod_names("春日丸").unwrap(),
+        vec!["春日丸", "大鷹", "大鷹改"]
+    );
+    assert_eq!(ships.remodel_level("大鷹改"), Some(2));
+    assert_eq!(ships.remodel_level("Not a ship"), None);
+    assert_eq!(ships.shipmod_blueprint_cost("長門", 1), Some(3));
+}
+
+#[wasm_bindgen_test]
+async fn test_wasm_ships_lenient_import() {
+    let kekkon = KANMUSU.replacen('{', r#"{"newField": 1,"#, 1);
+    assert!(ShipsBuilder::new()
+        .kekkon_from_json(kekkon.clone())
+        .build()
+        .await
+        .is_err());
+
+    let ships = ShipsBuilder::new()
+        .kekkon_from_json(kekkon)
+        .lenient()
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(ships.warnings().len(), 1);
+}
+
+#[wasm_bindgen_test]
+async fn test_wasm_ships_bad_json() {
+    assert!(ShipsBuilder::new()
+        .book_from_json("{}".to_string())
+        .build()
+        .await
+        .is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_client_jsessionid() {
+    // This used to panic in wasm32 builds
+    ClientBuilder::new()
+        .jsessionid("JSESSIONID".to_string())
+        .build()
+        .unwrap();
+}
+
+/// The length of the JSON array returned by an importer
+fn json_len(json: String) -> usize {
+    serde_json::from_str::<Vec<serde_json::Value>>(&json)
+        .unwrap()
+        .len()
+}
+
+#[wasm_bindgen_test]
+fn test_wasm_importers() {
+    assert_eq!(json_len(wasm::read_tcbook(TCBOOK).unwrap()), 291);
+    assert_eq!(
+        json_len(wasm::read_blueprint_list(BPLIST).unwrap()),
+        FIXTURE_BPLIST_COUNT
+    );
+    assert_eq!(json_len(wasm::read_character_list(CHARLIST).unwrap()), 415);
+    assert_eq!(
+        json_len(wasm::read_kekkonkakkokari_list(KANMUSU).unwrap()),
+        441
+    );
+    assert!(json_len(wasm::read_place_places(PLACES).unwrap()) > 0);
+    assert!(json_len(wasm::read_place_districts(DISTRICTS).unwrap()) > 0);
+    assert!(wasm::read_tcbook("[{}]").is_err());
+
+    // The data round-trips through the importer's own output
+    let tcbook = wasm::read_tcbook(TCBOOK).unwrap();
+    assert_eq!(wasm::read_tcbook(&tcbook).unwrap(), tcbook);
+}